@@ -0,0 +1,68 @@
+//! Benchmarks transforming curve families of increasing sizes, comparing the [batched][mb::transform_curve_matrices]
+//! hot path against transforming the curves one at a time.
+//!
+//! Run with `cargo bench --bench transform`, adding `--features rayon` to transform the batches in parallel.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use mobius_app as mb;
+use nalgebra::Matrix2;
+
+/// A dense family of `n` curves, alternating between circles and lines spread over a grid.
+fn dense_family(n: usize) -> Vec<Matrix2<mb::Complexf>> {
+    let side = (n as f64).sqrt().ceil() as usize;
+    (0..n)
+        .map(|i| {
+            let point = mb::Complexf::new((i % side) as f64, (i / side) as f64) - side as f64 / 2.0;
+            let curve = if i % 2 == 0 {
+                mb::Curve::Circle {
+                    center: point,
+                    radius: 0.5 + (i % 7) as f64,
+                }
+            } else {
+                mb::Curve::Line {
+                    point,
+                    slope: mb::Complexf::from_polar(1.0, i as f64),
+                }
+            };
+            mb::curve_to_matrix(&curve)
+        })
+        .collect()
+}
+
+fn transform(c: &mut Criterion) {
+    let inv_tform = Matrix2::new(
+        mb::Complexf::new(1.0, 2.0),
+        mb::Complexf::new(-0.5, 0.25),
+        mb::Complexf::new(0.3, -1.0),
+        mb::Complexf::new(2.0, 0.0),
+    );
+
+    let mut group = c.benchmark_group("transform");
+    for n in [1_000, 10_000, 100_000, 1_000_000] {
+        let matrices = dense_family(n);
+        group.throughput(Throughput::Elements(n as u64));
+        if n >= 100_000 {
+            group.sample_size(10);
+        }
+
+        group.bench_with_input(
+            BenchmarkId::new("unbatched", n),
+            &matrices,
+            |b, matrices| {
+                b.iter(|| {
+                    matrices
+                        .iter()
+                        .map(|m| mb::matrix_to_curve(&(m * inv_tform)))
+                        .collect::<Vec<_>>()
+                })
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("batched", n), &matrices, |b, matrices| {
+            b.iter(|| mb::transform_curve_matrices(matrices, &inv_tform))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, transform);
+criterion_main!(benches);
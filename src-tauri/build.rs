@@ -1,3 +1,5 @@
 fn main() {
+    // only the app itself needs the Tauri config and icons, see the `tauri` feature
+    #[cfg(feature = "tauri")]
     tauri_build::build()
 }
@@ -0,0 +1,178 @@
+#ifndef MOBIUS_H
+#define MOBIUS_H
+
+/* This file is generated by cbindgen from src/ffi.rs, do not edit it by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The result of calling a function of the C API.
+typedef enum MobiusStatus {
+  // The function succeeded, and its result was written to the out-pointer.
+  MOBIUS_STATUS_OK = 0,
+  // One of the pointers passed in was null.
+  MOBIUS_STATUS_NULL_POINTER = 1,
+  // One of the numbers passed in was nan.
+  MOBIUS_STATUS_INVALID_NUMBER = 2,
+  // Two of the inputs are (nearly) the same point, so no transformation exists.
+  MOBIUS_STATUS_DUPLICATE_INPUTS = 3,
+  // Two of the outputs are (nearly) the same point, so no transformation exists.
+  MOBIUS_STATUS_DUPLICATE_OUTPUTS = 4,
+  // The matrix doesn't describe an actual circle or line, e.g. because it has a radius of 0.
+  MOBIUS_STATUS_DEGENERATE_CURVE = 5,
+  // The `kind` of a curve passed in is not one of the values of `MobiusCurveKind`.
+  MOBIUS_STATUS_INVALID_CURVE_KIND = 6,
+} MobiusStatus;
+
+// The kinds of [`MobiusCurve`], matching the variants of [`Curve`].
+//
+// A [`MobiusCurve`] stores its `kind` as a plain `u32` rather than as this enum,
+// since C code can pass in any integer, and reading one which isn't a variant would be undefined behavior.
+enum MobiusCurveKind
+#ifdef __cplusplus
+  : uint32_t
+#endif // __cplusplus
+ {
+  MOBIUS_CURVE_KIND_LINE = 0,
+  MOBIUS_CURVE_KIND_CIRCLE = 1,
+  MOBIUS_CURVE_KIND_ARC = 2,
+  MOBIUS_CURVE_KIND_SEGMENT = 3,
+  MOBIUS_CURVE_KIND_RAY = 4,
+};
+#ifndef __cplusplus
+typedef uint32_t MobiusCurveKind;
+#endif // __cplusplus
+
+// A finite complex number.
+typedef struct MobiusComplex {
+  double re;
+  double im;
+} MobiusComplex;
+
+// An element of the extended complex plane, i.e. an [`ExtComplex`].
+//
+// The point is infinite when `is_infinite` is non-zero, in which case `value` is ignored.
+// It is a plain `u32` rather than a `bool` for the same reason as the `kind` of a [`MobiusCurve`].
+typedef struct MobiusPoint {
+  uint32_t is_infinite;
+  struct MobiusComplex value;
+} MobiusPoint;
+
+// A 2x2 complex matrix `[[m11, m12], [m21, m22]]`, representing either a Mobius transformation
+// `(m11 z + m12) / (m21 z + m22)` or a circle/line.
+typedef struct MobiusMatrix {
+  struct MobiusComplex m11;
+  struct MobiusComplex m12;
+  struct MobiusComplex m21;
+  struct MobiusComplex m22;
+} MobiusMatrix;
+
+// A line, circle or piece of one, i.e. a [`Curve`].
+//
+// Which fields are used depends on the `kind` (one of the values of [`MobiusCurveKind`]),
+// and the unused fields are set to 0:
+//  - `Line`: the points `point + direction * t`
+//  - `Circle`: the points `|z - point| = radius`
+//  - `Arc`: the part of the circle `|z - point| = radius` traced counterclockwise from `start` to `end`
+//  - `Segment`: the points `start + (end - start) * t` for `0 <= t <= 1`
+//  - `Ray`: the points `point + direction * t` for `t >= 0`
+typedef struct MobiusCurve {
+  uint32_t kind;
+  struct MobiusComplex point;
+  struct MobiusComplex direction;
+  double radius;
+  struct MobiusComplex start;
+  struct MobiusComplex end;
+} MobiusCurve;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Computes the Mobius transformation mapping each of the three `inputs` onto the corresponding `outputs`
+// (see [`compute_mobius_tform`]), and writes its matrix to `out`.
+//
+// Fails if any of the points are nan, or if two of the inputs or two of the outputs are (nearly) the same point.
+//
+// # Safety
+//
+// `inputs` and `outputs` must each point to 3 valid points, and `out` must be valid for writes.
+enum MobiusStatus mobius_compute_tform(const struct MobiusPoint *inputs,
+                                       const struct MobiusPoint *outputs,
+                                       struct MobiusMatrix *out);
+
+// Computes the Mobius transformation mapping the three `points` onto 0, infinity and 1 respectively,
+// and writes its matrix to `out`.
+//
+// This is the building block of [`mobius_compute_tform`], which handles any of the points being infinite.
+// The matrix is singular if any two of the points are the same, so no error is reported in that case.
+//
+// # Safety
+//
+// `points` must point to 3 valid points, and `out` must be valid for writes.
+enum MobiusStatus mobius_compute_partial_tform(const struct MobiusPoint *points,
+                                               struct MobiusMatrix *out);
+
+// Computes the inverse of a Mobius transformation (see [`invert_mobius_tform`]), and writes its matrix to `out`.
+//
+// # Safety
+//
+// `tform` must point to a valid matrix, and `out` must be valid for writes.
+enum MobiusStatus mobius_invert_tform(const struct MobiusMatrix *tform,
+                                      struct MobiusMatrix *out);
+
+// Maps `count` points under a Mobius transformation (see [`apply_mobius_tform`]), writing the images to `out`.
+//
+// `points` and `out` may be the same array, in which case the points are mapped in place.
+// If any of the points are nan, nothing is written.
+//
+// # Safety
+//
+// `tform` must point to a valid matrix, and `points` and `out` must each point to `count` points.
+enum MobiusStatus mobius_apply_tform(const struct MobiusMatrix *tform,
+                                     const struct MobiusPoint *points,
+                                     size_t count,
+                                     struct MobiusPoint *out);
+
+// Transforms the circle/line represented by `curve` under a Mobius transformation, and writes the matrix
+// representing the image to `out`.
+//
+// Since curve matrices are transformed contravariantly, this multiplies `curve` by the inverse of `tform`.
+//
+// # Safety
+//
+// `tform` and `curve` must point to valid matrices, and `out` must be valid for writes.
+enum MobiusStatus mobius_transform_curve(const struct MobiusMatrix *tform,
+                                         const struct MobiusMatrix *curve,
+                                         struct MobiusMatrix *out);
+
+// Converts the matrix of a circle/line into the curve itself (see [`matrix_to_curve`]), and writes it to `out`.
+//
+// Fails if the matrix doesn't describe an actual circle or line.
+//
+// # Safety
+//
+// `matrix` must point to a valid matrix, and `out` must be valid for writes.
+enum MobiusStatus mobius_matrix_to_curve(const struct MobiusMatrix *matrix,
+                                         struct MobiusCurve *out);
+
+// Converts a circle or line into its matrix representation (see [`curve_to_matrix`]), and writes it to `out`.
+//
+// Pieces of circles and lines (i.e. arcs, segments and rays) are converted as the whole circle/line containing them.
+// Fails if the `kind` of the curve is not one of the values of [`MobiusCurveKind`],
+// or if the curve is degenerate, e.g. a circle with a radius of 0.
+//
+// # Safety
+//
+// `curve` must point to a valid curve, and `out` must be valid for writes.
+enum MobiusStatus mobius_curve_to_matrix(const struct MobiusCurve *curve,
+                                         struct MobiusMatrix *out);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* MOBIUS_H */
@@ -0,0 +1,111 @@
+/*
+ * Exercises the C API of the math core, see `mobius.h`.
+ * Build and run it from this directory with `make test`.
+ */
+
+#include <math.h>
+#include <stdio.h>
+
+#include "mobius.h"
+
+static int failures = 0;
+
+#define CHECK(cond)                                                                    \
+    do {                                                                               \
+        if (!(cond)) {                                                                 \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #cond);  \
+            failures++;                                                                \
+        }                                                                              \
+    } while (0)
+
+static MobiusPoint finite(double re, double im) {
+    MobiusPoint p = {false, {re, im}};
+    return p;
+}
+
+static MobiusPoint infinity(void) {
+    MobiusPoint p = {true, {0.0, 0.0}};
+    return p;
+}
+
+static int close_to(MobiusComplex z, double re, double im) {
+    return fabs(z.re - re) < 1e-9 && fabs(z.im - im) < 1e-9;
+}
+
+static void test_inversion(void) {
+    /* z -> 1/z swaps 0 and infinity, and fixes 1 */
+    MobiusPoint inputs[3] = {finite(0.0, 0.0), finite(1.0, 0.0), infinity()};
+    MobiusPoint outputs[3] = {infinity(), finite(1.0, 0.0), finite(0.0, 0.0)};
+    MobiusMatrix tform;
+    CHECK(mobius_compute_tform(inputs, outputs, &tform) == MOBIUS_STATUS_OK);
+
+    MobiusPoint points[3] = {finite(2.0, 0.0), finite(0.0, 0.0), finite(0.0, 1.0)};
+    CHECK(mobius_apply_tform(&tform, points, 3, points) == MOBIUS_STATUS_OK);
+    CHECK(!points[0].is_infinite && close_to(points[0].value, 0.5, 0.0));
+    CHECK(points[1].is_infinite);
+    CHECK(!points[2].is_infinite && close_to(points[2].value, 0.0, -1.0));
+
+    MobiusMatrix inverse;
+    CHECK(mobius_invert_tform(&tform, &inverse) == MOBIUS_STATUS_OK);
+    MobiusPoint point = finite(0.5, 0.0);
+    CHECK(mobius_apply_tform(&inverse, &point, 1, &point) == MOBIUS_STATUS_OK);
+    CHECK(close_to(point.value, 2.0, 0.0));
+}
+
+static void test_errors(void) {
+    MobiusPoint distinct[3] = {finite(0.0, 0.0), finite(1.0, 0.0), infinity()};
+    MobiusPoint duplicate[3] = {finite(0.0, 0.0), finite(0.0, 0.0), infinity()};
+    MobiusPoint invalid[3] = {finite(0.0, 0.0), finite(NAN, 0.0), infinity()};
+    MobiusMatrix tform;
+    CHECK(mobius_compute_tform(duplicate, distinct, &tform) == MOBIUS_STATUS_DUPLICATE_INPUTS);
+    CHECK(mobius_compute_tform(distinct, duplicate, &tform) == MOBIUS_STATUS_DUPLICATE_OUTPUTS);
+    CHECK(mobius_compute_tform(distinct, invalid, &tform) == MOBIUS_STATUS_INVALID_NUMBER);
+    CHECK(mobius_compute_tform(NULL, distinct, &tform) == MOBIUS_STATUS_NULL_POINTER);
+    CHECK(mobius_compute_tform(distinct, distinct, NULL) == MOBIUS_STATUS_NULL_POINTER);
+
+    /* coinciding points make the partial transformation singular, rather than failing */
+    CHECK(mobius_compute_partial_tform(duplicate, &tform) == MOBIUS_STATUS_OK);
+}
+
+static void test_curves(void) {
+    /* translating the unit circle by 2 gives the circle of radius 1 around 2 */
+    MobiusMatrix translation = {{1.0, 0.0}, {2.0, 0.0}, {0.0, 0.0}, {1.0, 0.0}};
+    MobiusCurve circle = {MOBIUS_CURVE_KIND_CIRCLE, {0.0, 0.0}, {0.0, 0.0}, 1.0, {0.0, 0.0}, {0.0, 0.0}};
+    MobiusMatrix matrix;
+    CHECK(mobius_curve_to_matrix(&circle, &matrix) == MOBIUS_STATUS_OK);
+    CHECK(mobius_transform_curve(&translation, &matrix, &matrix) == MOBIUS_STATUS_OK);
+
+    MobiusCurve image;
+    CHECK(mobius_matrix_to_curve(&matrix, &image) == MOBIUS_STATUS_OK);
+    CHECK(image.kind == MOBIUS_CURVE_KIND_CIRCLE);
+    CHECK(close_to(image.point, 2.0, 0.0));
+    CHECK(fabs(image.radius - 1.0) < 1e-9);
+
+    /* the unit circle passes through 1, so it is mapped to a line by z -> 1/(z - 1) */
+    MobiusMatrix pole = {{0.0, 0.0}, {1.0, 0.0}, {1.0, 0.0}, {-1.0, 0.0}};
+    CHECK(mobius_curve_to_matrix(&circle, &matrix) == MOBIUS_STATUS_OK);
+    CHECK(mobius_transform_curve(&pole, &matrix, &matrix) == MOBIUS_STATUS_OK);
+    CHECK(mobius_matrix_to_curve(&matrix, &image) == MOBIUS_STATUS_OK);
+    CHECK(image.kind == MOBIUS_CURVE_KIND_LINE);
+    CHECK(fabs(image.point.re + 0.5) < 1e-9 && fabs(image.direction.re) < 1e-9);
+
+    circle.radius = 0.0;
+    CHECK(mobius_curve_to_matrix(&circle, &matrix) == MOBIUS_STATUS_DEGENERATE_CURVE);
+
+    /* kinds outside of MobiusCurveKind are rejected rather than misread */
+    circle.radius = 1.0;
+    circle.kind = 42;
+    CHECK(mobius_curve_to_matrix(&circle, &matrix) == MOBIUS_STATUS_INVALID_CURVE_KIND);
+}
+
+int main(void) {
+    test_inversion();
+    test_errors();
+    test_curves();
+    if (failures > 0) {
+        fprintf(stderr, "%d check(s) failed\n", failures);
+        return 1;
+    }
+    printf("all checks passed\n");
+    return 0;
+}
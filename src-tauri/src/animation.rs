@@ -0,0 +1,210 @@
+//! Contains the functionality for exporting the action of a Mobius transformation as an animated image.
+
+use std::io::{self, Write};
+
+use nalgebra::Matrix2;
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+/// The maximum number of frames in an animation.
+pub const MAX_ANIMATION_FRAMES: u32 = 1000;
+
+/// The maximum number of pixels across all the frames of an animation,
+/// which bounds the memory used since every frame is rendered before being encoded.
+pub const MAX_ANIMATION_PIXELS: usize = 1 << 28;
+
+/// The animated image formats an animation can be encoded as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnimationFormat {
+    Gif,
+    Apng,
+}
+
+/// Settings controlling how an animation is rendered and encoded.
+///
+/// Any setting not provided when deserializing falls back to its [default][AnimationOptions::default].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnimationOptions {
+    pub format: AnimationFormat,
+    /// width of each frame in pixels
+    pub width: u32,
+    /// height of each frame in pixels
+    pub height: u32,
+    /// the region of the complex plane to render, which is scaled to fit inside each frame
+    pub viewport: Viewport,
+    /// number of frames rendered when interpolating from the identity to the final transformation
+    pub frames: u32,
+    /// how long each frame is displayed for, in milliseconds
+    pub frame_delay: u16,
+    /// width of the curves in pixels
+    pub line_width: f64,
+    pub background: Rgba,
+    pub foreground: Rgba,
+}
+
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        // colors are taken from the default (dark) theme of the frontend
+        AnimationOptions {
+            format: AnimationFormat::Gif,
+            width: 600,
+            height: 600,
+            viewport: Viewport {
+                min: Complexf::new(-20.0, -20.0),
+                max: Complexf::new(20.0, 20.0),
+            },
+            frames: 60,
+            frame_delay: 50,
+            line_width: 1.0,
+            background: [0x1f, 0x21, 0x26, 0xff],
+            foreground: [0x75, 0x7d, 0x92, 0xff],
+        }
+    }
+}
+
+impl AnimationOptions {
+    /// Check that the options describe an animation that can actually be rendered and encoded.
+    pub fn validate(&self) -> io::Result<()> {
+        let invalid = |msg: &str| Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+        if self.width == 0 || self.height == 0 {
+            return invalid("frame dimensions must be non-zero");
+        }
+        if self.format == AnimationFormat::Gif
+            && (self.width > u16::MAX as u32 || self.height > u16::MAX as u32)
+        {
+            return invalid("frame dimensions are too large for the GIF format");
+        }
+        let Some(frame_pixels) = canvas_pixels(self.width, self.height) else {
+            return invalid("frame dimensions are too large");
+        };
+        if self.frames == 0 {
+            return invalid("animation must contain at least one frame");
+        }
+        if self.frames > MAX_ANIMATION_FRAMES
+            || frame_pixels.saturating_mul(self.frames as usize) > MAX_ANIMATION_PIXELS
+        {
+            return invalid("animation contains too many frames");
+        }
+        // written to also reject nan values
+        let is_positive = |x: f64| x > 0.0;
+        if !is_positive(self.viewport.width()) || !is_positive(self.viewport.height()) {
+            return invalid("viewport must have a positive width and height");
+        }
+        if !is_positive(self.line_width) {
+            return invalid("line width must be positive");
+        }
+        Ok(())
+    }
+}
+
+/// Render the frames of an animation visualizing a Mobius transformation.
+///
+/// The transformation is interpolated from the identity (on the first frame) to the target transformation (on the last frame),
+/// and each family of curves (and of arcs) is transformed and drawn onto every frame.
+/// Since curves are transformed contravariantly (see [`generate_mobius_transformation`]),
+/// the *inverse* of the target transformation must be provided.
+///
+/// Each frame is returned as RGBA pixel data, stored row by row starting from the top-left corner.
+pub fn render_animation_frames(
+    families: &[&[Matrix2<Complexf>]],
+    arc_families: &[&[ThreePointArc]],
+    inv_tform: &Matrix2<Complexf>,
+    options: &AnimationOptions,
+) -> Vec<Vec<u8>> {
+    let last_frame = options.frames.saturating_sub(1).max(1) as f64;
+    (0..options.frames)
+        .map(|i| {
+            let step = if options.frames == 1 {
+                1.0
+            } else {
+                i as f64 / last_frame
+            };
+            let inv_tform_step = math::interpolate_mobius_tform(inv_tform, step);
+
+            let mut canvas = Canvas::new(
+                options.width,
+                options.height,
+                &options.viewport,
+                options.background,
+            );
+            for m in families.iter().flat_map(|family| family.iter()) {
+                let curve = math::matrix_to_curve(&(m * inv_tform_step));
+                canvas.draw_curve(&curve, options.foreground, options.line_width);
+            }
+            let tform_step = math::invert_mobius_tform(&inv_tform_step);
+            for arc in arc_families.iter().flat_map(|family| family.iter()) {
+                let arc = math::apply_mobius_tform_arc(&tform_step, arc);
+                for curve in math::arc_to_curves(&arc) {
+                    canvas.draw_curve(&curve, options.foreground, options.line_width);
+                }
+            }
+            canvas.into_pixels()
+        })
+        .collect()
+}
+
+/// Encode rendered frames as an animated image that loops forever, and write it to `writer`.
+///
+/// The frames must have the dimensions specified in `options`.
+pub fn encode_animation<W: Write>(
+    writer: W,
+    frames: &[Vec<u8>],
+    options: &AnimationOptions,
+) -> io::Result<()> {
+    match options.format {
+        AnimationFormat::Gif => encode_gif(writer, frames, options),
+        AnimationFormat::Apng => encode_apng(writer, frames, options),
+    }
+}
+
+fn encode_gif<W: Write>(
+    writer: W,
+    frames: &[Vec<u8>],
+    options: &AnimationOptions,
+) -> io::Result<()> {
+    let width = options.width as u16;
+    let height = options.height as u16;
+    let mut encoder = gif::Encoder::new(writer, width, height, &[]).map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+
+    for pixels in frames {
+        // the encoder quantizes the frame in-place, so a copy is needed
+        let mut pixels = pixels.clone();
+        let mut frame = gif::Frame::from_rgba_speed(width, height, &mut pixels, 10);
+        // GIF frame delays are measured in hundredths of a second
+        frame.delay = options.frame_delay.div_ceil(10);
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
+
+fn encode_apng<W: Write>(
+    writer: W,
+    frames: &[Vec<u8>],
+    options: &AnimationOptions,
+) -> io::Result<()> {
+    let mut encoder = png::Encoder::new(writer, options.width, options.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .set_animated(frames.len() as u32, 0)
+        .map_err(io::Error::other)?;
+    encoder
+        .set_frame_delay(options.frame_delay, 1000)
+        .map_err(io::Error::other)?;
+
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    for pixels in frames {
+        writer.write_image_data(pixels).map_err(io::Error::other)?;
+    }
+    writer.finish().map_err(io::Error::other)
+}
+
+#[cfg(test)]
+#[path = "animation_tests.rs"]
+mod tests;
@@ -0,0 +1,132 @@
+use super::*;
+
+fn small_options(format: AnimationFormat) -> AnimationOptions {
+    AnimationOptions {
+        format,
+        width: 32,
+        height: 24,
+        frames: 3,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn render_frames_count_and_size() {
+    let options = small_options(AnimationFormat::Gif);
+    let xy = CurveFamilyRegistry::default()
+        .get("xy")
+        .unwrap()
+        .generate(&FamilyParams::new());
+    let frames = render_animation_frames(&[&xy], &[], &Matrix2::identity(), &options);
+
+    assert_eq!(frames.len(), 3);
+    assert!(frames.iter().all(|f| f.len() == 32 * 24 * 4));
+}
+
+#[test]
+fn render_frames_interpolates() {
+    let options = small_options(AnimationFormat::Gif);
+    let polar = CurveFamilyRegistry::default()
+        .get("polar")
+        .unwrap()
+        .generate(&FamilyParams::new());
+    let families = [polar.as_slice()];
+    let inv_tform = Matrix2::new(
+        Complexf::ONE,
+        Complexf::new(7.0, 3.0),
+        Complexf::ZERO,
+        Complexf::ONE,
+    );
+    let frames = render_animation_frames(&families, &[], &inv_tform, &options);
+
+    // the first frame shows the untransformed curves, and the curves should move in subsequent frames
+    let identity_frames = render_animation_frames(&families, &[], &Matrix2::identity(), &options);
+    assert_eq!(frames[0], identity_frames[0]);
+    assert_ne!(frames[0], frames[2]);
+}
+
+#[test]
+fn encode_gif_header() {
+    let options = small_options(AnimationFormat::Gif);
+    let frames = vec![vec![255; 32 * 24 * 4]; 3];
+    let mut buffer = Vec::new();
+    encode_animation(&mut buffer, &frames, &options).expect("encoding should succeed");
+
+    assert!(buffer.starts_with(b"GIF89a"));
+}
+
+#[test]
+fn encode_apng_header() {
+    let options = small_options(AnimationFormat::Apng);
+    let frames = vec![vec![255; 32 * 24 * 4]; 3];
+    let mut buffer = Vec::new();
+    encode_animation(&mut buffer, &frames, &options).expect("encoding should succeed");
+
+    assert!(buffer.starts_with(b"\x89PNG"));
+    // the animation control chunk marks the PNG as animated
+    assert!(buffer.windows(4).any(|w| w == b"acTL"));
+}
+
+#[test]
+fn validate_options_failure() {
+    let options = AnimationOptions {
+        frames: 0,
+        ..Default::default()
+    };
+    assert!(options.validate().is_err());
+
+    let options = AnimationOptions {
+        format: AnimationFormat::Gif,
+        width: 100_000,
+        ..Default::default()
+    };
+    assert!(options.validate().is_err());
+}
+
+#[test]
+fn validate_options_too_large() {
+    // the number of pixels overflows a u32
+    let options = AnimationOptions {
+        format: AnimationFormat::Apng,
+        width: 65536,
+        height: 65536,
+        ..Default::default()
+    };
+    assert!(options.validate().is_err());
+
+    let options = AnimationOptions {
+        frames: u32::MAX,
+        ..Default::default()
+    };
+    assert!(options.validate().is_err());
+
+    // each frame is small, but they don't all fit in memory together
+    let options = AnimationOptions {
+        width: 8192,
+        height: 8192,
+        frames: 100,
+        ..Default::default()
+    };
+    assert!(options.validate().is_err());
+
+    let options = AnimationOptions {
+        width: 100,
+        height: 100,
+        frames: MAX_ANIMATION_FRAMES,
+        ..Default::default()
+    };
+    assert!(options.validate().is_ok());
+}
+
+#[test]
+fn deserialize_options_defaults() {
+    let options = serde_json::from_str::<AnimationOptions>(r#"{ "format": "apng", "frames": 10 }"#)
+        .expect("deserialization should succeed");
+    let expected = AnimationOptions {
+        format: AnimationFormat::Apng,
+        frames: 10,
+        ..Default::default()
+    };
+
+    assert_eq!(options, expected);
+}
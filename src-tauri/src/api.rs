@@ -1,51 +1,252 @@
 //! Contains definitions for the API exposed to the frontend.
+//!
+//! The functions in this module are independent of Tauri, and are wrapped by the Tauri [`commands`].
 
 use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 
+use nalgebra::Matrix2;
 use serde::Serialize;
 
 use super::*;
 
 /// Visualizes a Mobius transformation based on the provided data.
 ///
-/// This method is provided to the frontend to invoke via Tauri.
-///
 /// The Mobius transformation corresponding to the provided input/output provides is computed (if one exists),
 /// then is applied to pre-defined [`Curves`][Curve] which are returned in the response.
 /// The frontend can use the returned data to visualize the Mobius transformation.
 ///
-/// Returns an error when the Mobius transformation is unable to be computed for whatever reason.
+/// Returns an error when any of the control points are invalid, when any of the requested curve families do not exist,
+/// or when the Mobius transformation is unable to be computed for whatever reason (see [`Error`]).
 ///
 /// # Params
+///  - `registry`: the curve families available to be rendered
 ///  - `inputs`: input control points
 ///  - `outputs`: output control points
-///  - `curves`: list of curve families that should be rendered
-#[tauri::command]
+///  - `curves`: list of curve families that should be rendered, along with their parameters
+///  - `viewport`: if provided, the transformed curves are [clipped][clip_curve] to the pieces visible inside it
 pub fn generate_mobius_transformation<'a>(
+    registry: &CurveFamilyRegistry,
     inputs: [ExtComplex; 3],
     outputs: [ExtComplex; 3],
-    curves: Vec<&'a str>,
+    curves: Vec<CurveFamilyRequest<'a>>,
+    viewport: Option<Viewport>,
 ) -> Result<GenerateMobiusResponse<'a>, Error> {
+    generate_mobius_transformation_unless(registry, inputs, outputs, curves, viewport, &|| false)
+        .map(|response| response.expect("the request is never superseded"))
+}
+
+/// Does the same as [`generate_mobius_transformation`], but only answers the latest of a stream of requests
+/// tagged with sequence numbers (see [`RequestSequence`]), e.g. those sent while the user drags a control point.
+///
+/// A request which was superseded before it is handled is skipped, while one superseded while its curves are being
+/// transformed is abandoned (between families) so that the later request isn't held up.
+/// Requests may still be answered out of order, so the caller should discard an answer older than one it has already
+/// received, which is why each answer carries the sequence number of its request.
+pub fn generate_latest_mobius_transformation<'a>(
+    registry: &CurveFamilyRegistry,
+    sequence: &RequestSequence,
+    seq: u64,
+    inputs: [ExtComplex; 3],
+    outputs: [ExtComplex; 3],
+    curves: Vec<CurveFamilyRequest<'a>>,
+    viewport: Option<Viewport>,
+) -> Sequenced<GenerateMobiusResponse<'a>> {
+    let superseded = || Sequenced::Superseded {
+        seq,
+        latest: sequence.latest(),
+    };
+    if !sequence.begin(seq) {
+        return superseded();
+    }
+
+    let is_superseded = || sequence.is_superseded(seq);
+    let result = generate_mobius_transformation_unless(
+        registry,
+        inputs,
+        outputs,
+        curves,
+        viewport,
+        &is_superseded,
+    );
+    match result {
+        // even a finished request is stale if a later one came in meanwhile
+        _ if is_superseded() => superseded(),
+        Ok(Some(result)) => Sequenced::Completed { seq, result },
+        Ok(None) => superseded(),
+        Err(error) => Sequenced::Failed { seq, error },
+    }
+}
+
+/// Internal helper used by [`generate_mobius_transformation`] and [`generate_latest_mobius_transformation`],
+/// which checks whether the request has been superseded before transforming each family, returning `None` if so.
+fn generate_mobius_transformation_unless<'a>(
+    registry: &CurveFamilyRegistry,
+    inputs: [ExtComplex; 3],
+    outputs: [ExtComplex; 3],
+    curves: Vec<CurveFamilyRequest<'a>>,
+    viewport: Option<Viewport>,
+    is_superseded: &dyn Fn() -> bool,
+) -> Result<Option<GenerateMobiusResponse<'a>>, Error> {
+    let families = lookup_curve_families(registry, &curves)?;
     // Compute inverse transform since that is what is needed for transforming the curves.
     // In more detail, curves are represented as a constraint on the *input* of a function
     // (as opposed to a transformation on the *output* of a function), hence it is contravariant w.r.t. transformations.
     // To transform contravariant variables, we apply the inverse transform to it.
-    let inv_tform = math::compute_mobius_tform(&outputs, &inputs).ok_or(Error::DoesNotExist)?;
+    let inv_tform = math::compute_mobius_tform(&outputs, &inputs)
+        .ok_or_else(|| diagnose_singular_tform(&inputs, &outputs))?;
+
+    let mut transformed = HashMap::new();
+    for (family, request) in families.into_iter().zip(curves) {
+        if is_superseded() {
+            return Ok(None);
+        }
+        transformed.extend(transform_curve_families(
+            vec![family],
+            vec![request],
+            &inv_tform,
+            viewport.as_ref(),
+        )?);
+    }
+
+    return Ok(Some(GenerateMobiusResponse {
+        curves: transformed,
+        // a transformation has the same fixed points as its inverse
+        fixed_points: math::compute_fixed_points(&inv_tform),
+        transform: describe_mobius_tform(&math::invert_mobius_tform(&inv_tform)),
+    }));
+}
+
+/// Keeps track of the latest request in a stream of requests tagged with sequence numbers,
+/// e.g. the moves sent while the user drags a control point (see [`Session::set_latest_control_point`]
+/// and [`generate_latest_mobius_transformation`]).
+///
+/// Can be shared between threads, so that requests handled concurrently can see when they have been superseded.
+#[derive(Debug, Default)]
+pub struct RequestSequence {
+    latest: AtomicU64,
+}
+
+impl RequestSequence {
+    /// Records that the request `seq` was made, returning `false` if a later request was already made.
+    pub fn begin(&self, seq: u64) -> bool {
+        self.latest.fetch_max(seq, Ordering::SeqCst) <= seq
+    }
+
+    /// Checks whether a request later than `seq` has been made.
+    pub fn is_superseded(&self, seq: u64) -> bool {
+        self.latest() > seq
+    }
+
+    /// The sequence number of the latest request made so far, or 0 if none have been made.
+    pub fn latest(&self) -> u64 {
+        self.latest.load(Ordering::SeqCst)
+    }
+}
+
+/// The outcome of a request tagged with the sequence number `seq` (see [`RequestSequence`]).
+///
+/// Serialized as an object whose `status` is the name of the variant in snake case, along with the fields of the variant,
+/// e.g. `{"status": "superseded", "seq": 3, "latest": 5}`.
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Sequenced<T> {
+    /// The request was handled successfully.
+    Completed { seq: u64, result: T },
+    /// The request was handled, but failed.
+    Failed { seq: u64, error: Error },
+    /// The request was skipped or abandoned since the request `latest` was made after it.
+    Superseded { seq: u64, latest: u64 },
+}
+
+/// Works out why no Mobius transformation maps the inputs onto the outputs.
+///
+/// A transformation fails to exist exactly when two of the inputs or two of the outputs coincide,
+/// but because of floating point error (see [`math::compute_mobius_tform`]) the points may only be nearly equal.
+/// Hence the pair of points closest together (on the Riemann sphere) is blamed.
+pub(crate) fn diagnose_singular_tform(
+    inputs: &[ExtComplex; 3],
+    outputs: &[ExtComplex; 3],
+) -> Error {
+    let closest_pair = |points: &[ExtComplex; 3]| {
+        [[0, 1], [1, 2], [0, 2]]
+            .into_iter()
+            .map(|[i, j]| (math::chordal_distance(&points[i], &points[j]), [i, j]))
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .unwrap()
+    };
+    let (input_distance, input_indices) = closest_pair(inputs);
+    let (output_distance, output_indices) = closest_pair(outputs);
+
+    if input_distance <= output_distance {
+        Error::DuplicateInputs {
+            indices: input_indices,
+        }
+    } else {
+        Error::DuplicateOutputs {
+            indices: output_indices,
+        }
+    }
+}
+
+/// Looks up each of the requested curve families, returning an error if any of them do not exist.
+pub(crate) fn lookup_curve_families<'r>(
+    registry: &'r CurveFamilyRegistry,
+    curves: &[CurveFamilyRequest],
+) -> Result<Vec<&'r dyn CurveFamily>, Error> {
+    curves
+        .iter()
+        .map(|request| {
+            registry
+                .get(request.name())
+                .ok_or_else(|| Error::UnknownCurveFamily {
+                    name: request.name().to_owned(),
+                })
+        })
+        .collect()
+}
+
+/// Transforms each of the requested curve families (previously [looked up][lookup_curve_families]) by the *inverse* of a Mobius transformation.
+/// The curves are transformed in [batches][transform_curve_matrices], and are clipped to the viewport if one is provided.
+///
+/// Returns an error if any of the transformed curves are degenerate.
+pub(crate) fn transform_curve_families<'a>(
+    families: Vec<&dyn CurveFamily>,
+    curves: Vec<CurveFamilyRequest<'a>>,
+    inv_tform: &Matrix2<Complexf>,
+    viewport: Option<&Viewport>,
+) -> Result<HashMap<&'a str, Vec<Curve>>, Error> {
+    // arcs are represented by points rather than constraints, so they are transformed by the forward transformation
+    let tform = math::invert_mobius_tform(inv_tform);
 
-    // lookup and transform each curve family requested, if it exists
     let mut output_curve_map = HashMap::new();
-    for name in curves {
-        if let Some(input_curves) = data::lookup_curve_family(name) {
-            let output_curves = input_curves
+    for (family, request) in families.into_iter().zip(curves) {
+        let params = request.params();
+        let mut output_curves = transform_curve_matrices(&family.generate(&params), inv_tform)
+            .map_err(|index| Error::DegenerateCurve {
+                family: request.name().to_owned(),
+                index,
+            })?;
+        // degenerate arcs are dropped when converted into curves
+        output_curves.extend(
+            family
+                .generate_arcs(&params)
                 .iter()
-                .map(|m| math::matrix_to_curve(&(m * inv_tform)))
-                .collect::<Vec<_>>();
-            output_curve_map.insert(name, output_curves);
-        }
+                .flat_map(|arc| math::arc_to_curves(&math::apply_mobius_tform_arc(&tform, arc))),
+        );
+
+        let output_curves = match viewport {
+            Some(viewport) => output_curves
+                .iter()
+                .flat_map(|curve| clip::clip_curve(curve, viewport))
+                .collect(),
+            None => output_curves,
+        };
+        output_curve_map.insert(request.name(), output_curves);
     }
-    return Ok(GenerateMobiusResponse {
-        curves: output_curve_map,
-    });
+    Ok(output_curve_map)
 }
 
 /// The output of [`generate_mobius_transformation`].
@@ -53,21 +254,466 @@ pub fn generate_mobius_transformation<'a>(
 pub struct GenerateMobiusResponse<'a> {
     /// The list of requested [`Curves`][Curve] after being transformed by the computed Mobius transformation.
     ///
-    /// Each key matches one of the curve family names passed in the input of [`generate_mobius_transformation`],
-    /// assuming that family existed.
+    /// Each key matches one of the curve family names passed in the input of [`generate_mobius_transformation`].
+    pub curves: HashMap<&'a str, Vec<Curve>>,
+
+    /// The [fixed points][math::compute_fixed_points] of the computed Mobius transformation,
+    /// e.g. for use as the foci of the Apollonian circles.
+    ///
+    /// Is `None` if the transformation is the identity.
+    pub fixed_points: Option<[ExtComplex; 2]>,
+
+    /// The coefficients of the computed Mobius transformation, along with some of its properties.
+    pub transform: MobiusTformInfo,
+}
+
+/// Describes a Mobius transformation `f(z) = (az + b) / (cz + d)`, so that the frontend can display it.
+#[derive(Debug, Serialize)]
+pub struct MobiusTformInfo {
+    /// The coefficients `[[a, b], [c, d]]` of the transformation, [normalized][math::normalize_mobius_tform].
+    pub matrix: [[Complexf; 2]; 2],
+    /// The coefficients of the inverse transformation, normalized in the same way.
+    pub inverse: [[Complexf; 2]; 2],
+    /// The transformation written out as a formula in `z`, e.g. `(2z + i) / (z - 1)`.
+    pub formula: String,
+    /// The determinant `ad - bc` of the normalized coefficients.
+    pub determinant: Complexf,
+    /// The images of 0, 1 and infinity under the transformation, in that order.
+    pub images: [ExtComplex; 3],
+}
+
+/// Internal helper used by [`generate_mobius_transformation`] (and [`Session`]) to describe the computed transformation.
+pub(crate) fn describe_mobius_tform(tform: &Matrix2<Complexf>) -> MobiusTformInfo {
+    let matrix = math::normalize_mobius_tform(tform);
+    let inverse = math::normalize_mobius_tform(&math::invert_mobius_tform(tform));
+    let rows = |m: Matrix2<Complexf>| [[m.m11, m.m12], [m.m21, m.m22]];
+
+    MobiusTformInfo {
+        formula: format_mobius_tform(&matrix),
+        determinant: matrix.determinant(),
+        images: [
+            ExtComplex::new(0.0, 0.0),
+            ExtComplex::new(1.0, 0.0),
+            ExtComplex::Inf,
+        ]
+        .map(|p| math::apply_mobius_tform(&matrix, &p)),
+        matrix: rows(matrix),
+        inverse: rows(inverse),
+    }
+}
+
+/// Writes out a Mobius transformation as a formula in `z`, rounding the coefficients to 4 decimal places.
+///
+/// Terms with a coefficient of 0 are left out, and the denominator is left out if it is 1.
+fn format_mobius_tform(tform: &Matrix2<Complexf>) -> String {
+    let numerator = format_linear(tform.m11, tform.m12);
+    let denominator = format_linear(tform.m21, tform.m22);
+    if denominator == "1" {
+        numerator
+    } else {
+        format!("({numerator}) / ({denominator})")
+    }
+}
+
+/// Internal helper used by [`format_mobius_tform`] to write out the expression `az + b`.
+fn format_linear(a: Complexf, b: Complexf) -> String {
+    let terms = [(a, "z"), (b, "")]
+        .into_iter()
+        .filter_map(|(coefficient, variable)| {
+            let (negative, coefficient) = format_coefficient(coefficient)?;
+            Some(match (coefficient.as_str(), variable) {
+                ("1", "z") => (negative, "z".to_owned()),
+                _ => (negative, coefficient + variable),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut formula = String::new();
+    for (i, (negative, term)) in terms.iter().enumerate() {
+        formula += match (i, negative) {
+            (0, false) => "",
+            (0, true) => "-",
+            (_, false) => " + ",
+            (_, true) => " - ",
+        };
+        formula += term;
+    }
+    if formula.is_empty() {
+        formula += "0";
+    }
+    formula
+}
+
+/// Internal helper used by [`format_linear`] to write out a coefficient, separately from its sign.
+///
+/// Real and imaginary coefficients can be negative, while coefficients with both parts are wrapped in brackets.
+/// Returns nothing if the coefficient rounds to 0.
+fn format_coefficient(c: Complexf) -> Option<(bool, String)> {
+    // round to a fixed number of decimal places, trimming any trailing zeros
+    let format_real = |x: f64| {
+        let formatted = format!("{:.4}", x.abs());
+        let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+        (x < 0.0 && formatted != "0", formatted.to_owned())
+    };
+    let (re_negative, re) = format_real(c.re);
+    let (im_negative, im) = format_real(c.im);
+    let im = if im == "1" { "i".to_owned() } else { im + "i" };
+
+    match (re.as_str(), im.as_str()) {
+        ("0", "0i") => None,
+        (_, "0i") => Some((re_negative, re)),
+        ("0", _) => Some((im_negative, im)),
+        _ => {
+            let re = if re_negative { format!("-{re}") } else { re };
+            let sign = if im_negative { '-' } else { '+' };
+            Some((false, format!("({re} {sign} {im})")))
+        }
+    }
+}
+
+/// Visualizes an isometry of the hyperbolic plane based on the provided data.
+///
+/// The Mobius transformation corresponding to the provided input/output points is computed (if one exists),
+/// and is then projected onto the nearest isometry of the given [`HyperbolicModel`].
+/// The isometry is applied to pre-defined [`Curves`][Curve] as in [`generate_mobius_transformation`].
+/// Since the isometry generally won't map the inputs exactly onto the provided outputs,
+/// the actual images of the inputs are also returned so the frontend can snap the control points onto them.
+///
+/// Returns an error when any of the control points are invalid, when any of the requested curve families do not exist,
+/// or when the Mobius transformation is unable to be computed or projected onto an isometry.
+///
+/// # Params
+///  - `registry`: the curve families available to be rendered
+///  - `model`: the model of the hyperbolic plane whose isometries the transformation is restricted to
+///  - `inputs`: input control points
+///  - `outputs`: output control points
+///  - `curves`: list of curve families that should be rendered, along with their parameters
+///  - `viewport`: if provided, the transformed curves are [clipped][clip_curve] to the pieces visible inside it
+pub fn generate_hyperbolic_transformation<'a>(
+    registry: &CurveFamilyRegistry,
+    model: HyperbolicModel,
+    inputs: [ExtComplex; 3],
+    outputs: [ExtComplex; 3],
+    curves: Vec<CurveFamilyRequest<'a>>,
+    viewport: Option<Viewport>,
+) -> Result<GenerateHyperbolicResponse<'a>, Error> {
+    let families = lookup_curve_families(registry, &curves)?;
+    let tform = math::compute_mobius_tform(&inputs, &outputs)
+        .ok_or_else(|| diagnose_singular_tform(&inputs, &outputs))?;
+    let isometry = model.project_isometry(&tform).ok_or(Error::NoIsometry)?;
+    let inv_isometry = math::invert_mobius_tform(&isometry);
+
+    Ok(GenerateHyperbolicResponse {
+        curves: transform_curve_families(families, curves, &inv_isometry, viewport.as_ref())?,
+        outputs: inputs.map(|p| math::apply_mobius_tform(&isometry, &p)),
+        distances: [(0, 1), (1, 2), (2, 0)].map(|(i, j)| model.distance(&inputs[i], &inputs[j])),
+    })
+}
+
+/// The output of [`generate_hyperbolic_transformation`].
+#[derive(Debug, Serialize)]
+pub struct GenerateHyperbolicResponse<'a> {
+    /// The list of requested [`Curves`][Curve] after being transformed by the computed isometry.
+    ///
+    /// Each key matches one of the curve family names passed in the input of [`generate_hyperbolic_transformation`].
     pub curves: HashMap<&'a str, Vec<Curve>>,
+    /// The images of the input control points under the computed isometry,
+    /// i.e. the output control points adjusted to be consistent with an isometry.
+    pub outputs: [ExtComplex; 3],
+    /// The hyperbolic distances between the 1st/2nd, 2nd/3rd and 3rd/1st input control points, in that order.
+    ///
+    /// These are preserved by the isometry, so they are also the distances between the corresponding output control points.
+    /// The distance is `null` if either point lies outside the model.
+    pub distances: [Option<f64>; 3],
+}
+
+/// Exports an animation of a Mobius transformation to an image file.
+///
+/// The Mobius transformation corresponding to the provided input/output points is computed (if one exists),
+/// then is interpolated from the identity to the computed transformation.
+/// The pre-defined [`Curves`][Curve] are rendered at each step of the interpolation,
+/// and the resulting frames are encoded as an animated image (see [`AnimationFormat`]) which is written to disk.
+///
+/// Returns an error when any of the control points are invalid, when any of the requested curve families do not exist,
+/// when the Mobius transformation is unable to be computed, or when the export fails.
+///
+/// # Params
+///  - `registry`: the curve families available to be rendered
+///  - `inputs`: input control points
+///  - `outputs`: output control points
+///  - `curves`: list of curve families that should be rendered, along with their parameters
+///  - `options`: settings for rendering and encoding the animation
+///  - `path`: file the animation is written to
+#[cfg(feature = "render")]
+pub fn export_mobius_animation(
+    registry: &CurveFamilyRegistry,
+    inputs: [ExtComplex; 3],
+    outputs: [ExtComplex; 3],
+    curves: Vec<CurveFamilyRequest>,
+    options: AnimationOptions,
+    path: PathBuf,
+) -> Result<(), Error> {
+    let families = lookup_curve_families(registry, &curves)?;
+    // inverse transform is needed to transform curves, see `generate_mobius_transformation`
+    let inv_tform = math::compute_mobius_tform(&outputs, &inputs)
+        .ok_or_else(|| diagnose_singular_tform(&inputs, &outputs))?;
+    let (families, arc_families): (Vec<_>, Vec<_>) = families
+        .into_iter()
+        .zip(&curves)
+        .map(|(family, request)| {
+            let params = request.params();
+            (family.generate(&params), family.generate_arcs(&params))
+        })
+        .unzip();
+
+    let export = || {
+        options.validate()?;
+        let frames = animation::render_animation_frames(
+            &families.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+            &arc_families.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+            &inv_tform,
+            &options,
+        );
+        let writer = std::io::BufWriter::new(std::fs::File::create(&path)?);
+        animation::encode_animation(writer, &frames, &options)
+    };
+    export().map_err(|err| Error::ExportFailed {
+        reason: err.to_string(),
+    })
+}
+
+/// Maps arbitrary points under a Mobius transformation, e.g. to show probe points or the image of the cursor.
+///
+/// The Mobius transformation corresponding to the provided input/output points is computed (if one exists),
+/// then it (or its inverse) is applied to each of the points.
+///
+/// Returns an error when any of the control points are invalid, or when the Mobius transformation is unable to be computed.
+///
+/// # Params
+///  - `inputs`: input control points
+///  - `outputs`: output control points
+///  - `points`: the points to map
+///  - `inverse`: whether to map the points under the inverse transformation, i.e. to find their preimages
+pub fn map_points(
+    inputs: [ExtComplex; 3],
+    outputs: [ExtComplex; 3],
+    points: Vec<ExtComplex>,
+    inverse: bool,
+) -> Result<Vec<ExtComplex>, Error> {
+    let tform = if inverse {
+        math::compute_mobius_tform(&outputs, &inputs)
+    } else {
+        math::compute_mobius_tform(&inputs, &outputs)
+    }
+    .ok_or_else(|| diagnose_singular_tform(&inputs, &outputs))?;
+
+    Ok(points
+        .iter()
+        .map(|p| math::apply_mobius_tform(&tform, p))
+        .collect())
+}
+
+/// Saves the state of a session (see [`Session::to_scene`]) to a scene file.
+///
+/// # Params
+///  - `session`: the session to save
+///  - `path`: file the scene is written to, which should end in [`SCENE_FILE_EXTENSION`]
+pub fn save_scene(session: &Session, path: PathBuf) -> Result<(), Error> {
+    write_scene_file(&session.to_scene(), &path).map_err(|err| Error::SceneFailed {
+        reason: err.to_string(),
+    })
+}
+
+/// Loads a scene from a file, which the frontend can then [start a session][Session::start] with.
+///
+/// Returns an error if the file can't be read, is malformed, or was written by a newer version of the app.
+/// The scene itself is not validated, so starting a session with it may fail, e.g. if it uses an unknown curve family.
+///
+/// # Params
+///  - `path`: file the scene is read from
+pub fn load_scene(path: PathBuf) -> Result<Scene, Error> {
+    read_scene_file(&path).map_err(|err| Error::SceneFailed {
+        reason: err.to_string(),
+    })
+}
+
+/// Encodes the control points and curve families of a session as a [share code][encode_share_code].
+pub fn share_scene(session: &Session) -> Result<String, Error> {
+    encode_share_code(&session.to_scene()).map_err(|err| Error::ShareFailed {
+        reason: err.to_string(),
+    })
+}
+
+/// Decodes a [share code][decode_share_code] into a scene, which the frontend can then [start a session][Session::start] with.
+///
+/// As with [`load_scene`], the scene itself is not validated.
+///
+/// # Params
+///  - `code`: the share code, as produced by [`share_scene`]
+pub fn load_share_code(code: &str) -> Result<Scene, Error> {
+    decode_share_code(code).map_err(|err| Error::InvalidShareCode {
+        reason: err.to_string(),
+    })
+}
+
+/// Lists the built-in [presets][presets()], i.e. well-known transformations the user can start from.
+pub fn list_presets() -> Vec<Preset> {
+    presets()
+}
+
+/// Looks up a built-in preset, returning its scene which the frontend can then [start a session][Session::start] with.
+///
+/// Returns an error if there is no preset with the given key.
+///
+/// # Params
+///  - `key`: the key of the preset, as returned by [`list_presets`]
+pub fn load_preset(key: &str) -> Result<Scene, Error> {
+    find_preset(key)
+        .map(|preset| preset.to_scene())
+        .ok_or_else(|| Error::UnknownPreset {
+            key: key.to_owned(),
+        })
+}
+
+/// Lists the curve families available to be rendered, in the order they were registered.
+///
+/// The frontend uses this to offer the families (and controls for their parameters) to the user,
+/// so that it doesn't need to know about the families ahead of time.
+pub fn list_curve_families(registry: &CurveFamilyRegistry) -> Vec<CurveFamilyInfo> {
+    registry
+        .iter()
+        .map(|family| CurveFamilyInfo {
+            key: family.name().to_owned(),
+            display_name: family.display_name().to_owned(),
+            description: family.description().to_owned(),
+            params: family.params(),
+            default_style: family.default_style(),
+        })
+        .collect()
 }
 
-/// Represents the errors that can occur when calling [`generate_mobius_transformation`].
+/// Describes a curve family, as returned by [`list_curve_families`].
 #[derive(Debug, Serialize)]
-#[serde(tag = "kind")]
+pub struct CurveFamilyInfo {
+    /// The name used to request the family, e.g. in [`generate_mobius_transformation`].
+    pub key: String,
+    pub display_name: String,
+    pub description: String,
+    pub params: Vec<ParamSchema>,
+    pub default_style: CurveStyle,
+}
+
+/// Represents the errors that can occur when calling the API.
+///
+/// Errors are serialized as an object whose `kind` is the name of the variant, along with the fields of the variant
+/// and a human-readable `message` (see the [`Display`][fmt::Display] implementation), e.g.
+/// `{"kind": "DuplicateInputs", "indices": [0, 2], "message": "..."}`.
+#[derive(Debug, Serialize)]
+#[serde(remote = "Self", tag = "kind")]
 pub enum Error {
     /// Indicates that no Mobius transformation satisfies the input/output pairs provided,
-    /// i.e. the corresponding matrix is singular.
+    /// because the inputs at the given indices (nearly) coincide, so the corresponding matrix is singular.
+    ///
+    /// A Mobius transformation is one-to-one, so it can't map the same point to two different outputs.
+    DuplicateInputs { indices: [usize; 2] },
+    /// Indicates that no Mobius transformation satisfies the input/output pairs provided,
+    /// because the outputs at the given indices (nearly) coincide, so the corresponding matrix is singular.
     ///
-    /// This occurs when the input/output pairs contains duplicates,
-    /// meaning the defined relation is not one-to-one.
-    DoesNotExist,
+    /// A Mobius transformation is one-to-one, so it can't map two different points to the same output.
+    DuplicateOutputs { indices: [usize; 2] },
+    /// Indicates that a [`Session`] was asked to update a control point which doesn't exist, i.e. whose index is not 0, 1 or 2.
+    UnknownControlPoint { index: usize },
+    /// Indicates that a [`Session`] was given a threshold in its [`Tolerances`] which is negative or not finite.
+    InvalidTolerance { name: String },
+    /// Indicates that the Mobius transformation is too far from an isometry of the requested hyperbolic model
+    /// to be projected onto one, e.g. because it swaps the interior and exterior of the model.
+    NoIsometry,
+    /// Indicates that one of the requested curve families is not in the registry.
+    UnknownCurveFamily { name: String },
+    /// Indicates that the curve at the given index of a family is degenerate after being transformed
+    /// (see [`Curve::is_degenerate`]), e.g. because the family generated a singular matrix.
+    DegenerateCurve { family: String, index: usize },
+    /// Indicates that an animation could not be rendered or written to disk.
+    ExportFailed { reason: String },
+    /// Indicates that a scene could not be saved or loaded, e.g. because the file is malformed (see [`SceneError`]).
+    SceneFailed { reason: String },
+    /// Indicates that a scene could not be encoded as a share code, e.g. because it has too many curve families
+    /// (see [`ShareCodeError`]).
+    ShareFailed { reason: String },
+    /// Indicates that a share code could not be decoded (see [`ShareCodeError`]).
+    InvalidShareCode { reason: String },
+    /// Indicates that there is no preset with the given key.
+    UnknownPreset { key: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // control points are numbered from 1 in the frontend
+        match self {
+            Self::DuplicateInputs { indices: [i, j] } => write!(
+                f,
+                "Inputs {} and {} are (nearly) the same point, so no Mobius transformation can map them to different outputs.",
+                i + 1,
+                j + 1
+            ),
+            Self::DuplicateOutputs { indices: [i, j] } => write!(
+                f,
+                "Outputs {} and {} are (nearly) the same point, so no Mobius transformation can map different inputs onto them.",
+                i + 1,
+                j + 1
+            ),
+            Self::UnknownControlPoint { index } => {
+                write!(f, "There is no control point numbered {}.", index + 1)
+            }
+            Self::InvalidTolerance { name } => {
+                write!(f, "The {name} tolerance must be a finite, non-negative number.")
+            }
+            Self::NoIsometry => write!(
+                f,
+                "The transformation is not close enough to an isometry of the hyperbolic plane."
+            ),
+            Self::UnknownCurveFamily { name } => write!(f, "There is no curve family named \"{name}\"."),
+            Self::DegenerateCurve { family, index } => write!(
+                f,
+                "Curve {index} of the curve family \"{family}\" is degenerate."
+            ),
+            Self::ExportFailed { reason } => write!(f, "Failed to export the animation: {reason}"),
+            Self::SceneFailed { reason } => write!(f, "Failed to save or load the scene: {reason}"),
+            Self::ShareFailed { reason } => write!(f, "Failed to share the scene: {reason}"),
+            Self::InvalidShareCode { reason } => write!(f, "Failed to load the share code: {reason}"),
+            Self::UnknownPreset { key } => write!(f, "There is no preset named \"{key}\"."),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Serialize for Error {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        // serialize the variant using the derived (remote) implementation, and add the message alongside its fields
+        #[derive(Serialize)]
+        struct ErrorWithMessage<'e> {
+            #[serde(flatten, serialize_with = "serialize_variant")]
+            error: &'e Error,
+            message: String,
+        }
+
+        fn serialize_variant<S: serde::Serializer>(
+            error: &&Error,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            Error::serialize(error, serializer)
+        }
+
+        ErrorWithMessage {
+            error: self,
+            message: self.to_string(),
+        }
+        .serialize(serializer)
+    }
 }
 
 #[cfg(test)]
@@ -3,12 +3,14 @@ use serde_json::json;
 use super::*;
 
 #[test]
-fn serialize_error_nexist() {
-    let err = Error::DoesNotExist;
+fn serialize_error_duplicate_inputs() {
+    let err = Error::DuplicateInputs { indices: [0, 2] };
     let json = serde_json::to_string(&err).expect("serialization should succeed");
     let expected = serde_json::to_string(&json!(
         {
-            "kind": "DoesNotExist"
+            "kind": "DuplicateInputs",
+            "indices": [0, 2],
+            "message": "Inputs 1 and 3 are (nearly) the same point, so no Mobius transformation can map them to different outputs."
         }
     ))
     .unwrap();
@@ -16,6 +18,15 @@ fn serialize_error_nexist() {
     assert_eq!(json, expected);
 }
 
+#[test]
+fn serialize_error_unit_variant() {
+    let err = Error::NoIsometry;
+    let json = serde_json::to_value(&err).expect("serialization should succeed");
+
+    assert_eq!(json["kind"], "NoIsometry");
+    assert_eq!(json.as_object().unwrap().len(), 2);
+}
+
 #[test]
 fn serialize_response() {
     let response = GenerateMobiusResponse {
@@ -32,6 +43,13 @@ fn serialize_response() {
                 },
             ],
         )]),
+        fixed_points: Some([ExtComplex::new(0.0, 0.0), ExtComplex::Inf]),
+        transform: describe_mobius_tform(&Matrix2::new(
+            Complexf::from(2.0),
+            Complexf::ZERO,
+            Complexf::ZERO,
+            Complexf::ONE,
+        )),
     };
     let json = serde_json::to_string(&response).expect("serialization should succeed");
     let expected = serde_json::to_string(&json!(
@@ -49,6 +67,14 @@ fn serialize_response() {
                         "radius": 5.0
                     }
                 ]
+            },
+            "fixed_points": [[0.0, 0.0], "inf"],
+            "transform": {
+                "matrix": [[[2.0, 0.0], [0.0, 0.0]], [[0.0, 0.0], [1.0, 0.0]]],
+                "inverse": [[[0.5, 0.0], [0.0, 0.0]], [[0.0, 0.0], [1.0, 0.0]]],
+                "formula": "2z",
+                "determinant": [2.0, 0.0],
+                "images": [[0.0, 0.0], [2.0, 0.0], "inf"]
             }
         }
     ))
@@ -61,11 +87,21 @@ fn serialize_response() {
 fn serialize_empty_response() {
     let response = GenerateMobiusResponse {
         curves: HashMap::new(),
+        fixed_points: None,
+        transform: describe_mobius_tform(&Matrix2::identity()),
     };
     let json = serde_json::to_string(&response).expect("serialization should succeed");
     let expected = serde_json::to_string(&json!(
         {
-            "curves": {}
+            "curves": {},
+            "fixed_points": null,
+            "transform": {
+                "matrix": [[[1.0, 0.0], [0.0, 0.0]], [[0.0, 0.0], [1.0, 0.0]]],
+                "inverse": [[[1.0, 0.0], [0.0, 0.0]], [[0.0, 0.0], [1.0, 0.0]]],
+                "formula": "z",
+                "determinant": [1.0, 0.0],
+                "images": [[0.0, 0.0], [1.0, 0.0], "inf"]
+            }
         }
     ))
     .unwrap();
@@ -79,7 +115,572 @@ fn invoke_api_error_nexist() {
     let inputs = [ExtComplex::Inf, ExtComplex::Inf, ExtComplex::Inf];
     let outputs = [ExtComplex::Inf, ExtComplex::Inf, ExtComplex::Inf];
     let curves = vec![];
-    let result = generate_mobius_transformation(inputs, outputs, curves);
+    let result = generate_mobius_transformation(
+        &CurveFamilyRegistry::default(),
+        inputs,
+        outputs,
+        curves,
+        None,
+    );
 
     assert!(result.is_err());
 }
+
+#[test]
+fn invoke_api_latest() {
+    let registry = CurveFamilyRegistry::default();
+    let sequence = RequestSequence::default();
+    let inputs = [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::Inf,
+    ];
+    let outputs = [
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::new(2.0, 0.0),
+        ExtComplex::Inf,
+    ];
+
+    let expected =
+        generate_mobius_transformation(&registry, inputs, outputs, vec!["xy".into()], None)
+            .unwrap();
+    match generate_latest_mobius_transformation(
+        &registry,
+        &sequence,
+        2,
+        inputs,
+        outputs,
+        vec!["xy".into()],
+        None,
+    ) {
+        Sequenced::Completed { seq: 2, result } => {
+            assert_eq!(result.curves, expected.curves);
+            assert_eq!(result.transform.formula, expected.transform.formula);
+        }
+        other => panic!("unexpected outcome {other:?}"),
+    }
+
+    // the request made before the one already answered is skipped
+    let result = generate_latest_mobius_transformation(
+        &registry,
+        &sequence,
+        1,
+        inputs,
+        outputs,
+        vec!["xy".into()],
+        None,
+    );
+    assert!(matches!(
+        result,
+        Sequenced::Superseded { seq: 1, latest: 2 }
+    ));
+
+    let result = generate_latest_mobius_transformation(
+        &registry,
+        &sequence,
+        3,
+        inputs,
+        inputs,
+        vec![],
+        None,
+    );
+    assert!(matches!(result, Sequenced::Completed { seq: 3, .. }));
+    let result = generate_latest_mobius_transformation(
+        &registry,
+        &sequence,
+        4,
+        inputs,
+        [ExtComplex::Inf; 3],
+        vec![],
+        None,
+    );
+    assert!(matches!(
+        result,
+        Sequenced::Failed {
+            seq: 4,
+            error: Error::DuplicateOutputs { .. }
+        }
+    ));
+}
+
+#[test]
+fn invoke_api_error_duplicate_points() {
+    let distinct = [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::Inf,
+    ];
+    let duplicates = [
+        ExtComplex::new(2.0, 0.0),
+        ExtComplex::new(1.0, 1.0),
+        ExtComplex::new(2.0, 1e-17),
+    ];
+    let registry = CurveFamilyRegistry::default();
+
+    assert!(matches!(
+        generate_mobius_transformation(&registry, duplicates, distinct, vec![], None),
+        Err(Error::DuplicateInputs { indices: [0, 2] })
+    ));
+    assert!(matches!(
+        generate_mobius_transformation(&registry, distinct, duplicates, vec![], None),
+        Err(Error::DuplicateOutputs { indices: [0, 2] })
+    ));
+}
+
+#[test]
+fn invoke_api_error_degenerate_curve() {
+    const CURVES: &[Matrix2<Complexf>] = &[
+        Matrix2::new(Complexf::ONE, Complexf::ZERO, Complexf::ZERO, Complexf::ONE),
+        Matrix2::new(
+            Complexf::ZERO,
+            Complexf::ZERO,
+            Complexf::ZERO,
+            Complexf::ZERO,
+        ),
+    ];
+    let mut registry = CurveFamilyRegistry::default();
+    registry.register(StaticFamily {
+        name: "degenerate",
+        display_name: "Degenerate",
+        curves: CURVES,
+    });
+    let inputs = [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::Inf,
+    ];
+    let result =
+        generate_mobius_transformation(&registry, inputs, inputs, vec!["degenerate".into()], None);
+
+    assert!(matches!(
+        result,
+        Err(Error::DegenerateCurve { family, index: 1 }) if family == "degenerate"
+    ));
+}
+
+#[cfg(feature = "render")]
+#[test]
+fn invoke_export_animation() {
+    let inputs = [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::Inf,
+    ];
+    let outputs = [
+        ExtComplex::new(1.0, 1.0),
+        ExtComplex::new(2.0, 0.0),
+        ExtComplex::Inf,
+    ];
+    let options = AnimationOptions {
+        width: 16,
+        height: 16,
+        frames: 2,
+        ..Default::default()
+    };
+    let path = std::env::temp_dir().join("mobius_app_invoke_export_animation.gif");
+    export_mobius_animation(
+        &CurveFamilyRegistry::default(),
+        inputs,
+        outputs,
+        vec!["xy".into()],
+        options,
+        path.clone(),
+    )
+    .expect("export should succeed");
+
+    let contents = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(contents.starts_with(b"GIF89a"));
+}
+
+#[cfg(feature = "render")]
+#[test]
+fn invoke_export_animation_error_invalid_options() {
+    let inputs = [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::Inf,
+    ];
+    let options = AnimationOptions {
+        frames: 0,
+        ..Default::default()
+    };
+    let path = std::env::temp_dir().join("mobius_app_invoke_export_animation_error.gif");
+    let result = export_mobius_animation(
+        &CurveFamilyRegistry::default(),
+        inputs,
+        inputs,
+        vec![],
+        options,
+        path,
+    );
+
+    assert!(matches!(result, Err(Error::ExportFailed { .. })));
+}
+
+#[test]
+fn invoke_hyperbolic_api_snaps_outputs() {
+    // translation is not an isometry of the disk, so the outputs should be moved
+    let inputs = [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::new(0.0, 1.0),
+    ];
+    let outputs = [
+        ExtComplex::new(2.0, 0.0),
+        ExtComplex::new(3.0, 0.0),
+        ExtComplex::new(2.0, 1.0),
+    ];
+    let response = generate_hyperbolic_transformation(
+        &CurveFamilyRegistry::default(),
+        HyperbolicModel::Disk,
+        inputs,
+        outputs,
+        vec!["geodesic_disk".into()],
+        None,
+    )
+    .expect("isometry should exist");
+
+    assert_ne!(response.outputs, outputs);
+    assert_eq!(response.curves["geodesic_disk"].len(), 42);
+    assert!(response.distances.iter().all(|d| d.is_some()));
+}
+
+#[test]
+fn invoke_hyperbolic_api_error_no_isometry() {
+    // maps the center of the disk to infinity
+    let inputs = [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::Inf,
+    ];
+    let outputs = [
+        ExtComplex::Inf,
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::new(0.0, 0.0),
+    ];
+    let result = generate_hyperbolic_transformation(
+        &CurveFamilyRegistry::default(),
+        HyperbolicModel::Disk,
+        inputs,
+        outputs,
+        vec![],
+        None,
+    );
+
+    assert!(matches!(result, Err(Error::NoIsometry)));
+}
+
+#[test]
+fn invoke_api_arc_family() {
+    let inputs = [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::new(0.0, 1.0),
+    ];
+    let registry = CurveFamilyRegistry::default();
+    let response =
+        generate_mobius_transformation(&registry, inputs, inputs, vec!["tiling_disk".into()], None)
+            .unwrap();
+    let curves = &response.curves["tiling_disk"];
+
+    // the edges of the tiling lie inside the disk, so none of them pass through infinity
+    assert_eq!(
+        curves.len(),
+        registry
+            .get("tiling_disk")
+            .unwrap()
+            .generate_arcs(&FamilyParams::new())
+            .len()
+    );
+    assert!(curves
+        .iter()
+        .all(|c| matches!(c, Curve::Arc { .. } | Curve::Segment { .. })));
+}
+
+#[test]
+fn invoke_api_clip_to_viewport() {
+    let inputs = [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::new(0.0, 1.0),
+    ];
+    let viewport = Viewport {
+        min: Complexf::new(-2.5, -2.5),
+        max: Complexf::new(2.5, 2.5),
+    };
+    let response = generate_mobius_transformation(
+        &CurveFamilyRegistry::default(),
+        inputs,
+        inputs,
+        vec!["xy".into()],
+        Some(viewport),
+    )
+    .unwrap();
+
+    // only the gridlines x = -2, ..., 2 and y = -2, ..., 2 are visible, and are trimmed to the viewport
+    let curves = &response.curves["xy"];
+    assert_eq!(curves.len(), 10);
+    for curve in curves {
+        match *curve {
+            Curve::Segment { start, end } => {
+                approx::assert_relative_eq!((end - start).norm(), 5.0, epsilon = 1e-9)
+            }
+            curve => panic!("expected a segment, got {curve:?}"),
+        }
+    }
+}
+
+#[test]
+fn invoke_api_family_params() {
+    let inputs = [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::new(0.0, 1.0),
+    ];
+    let curves = serde_json::from_str::<Vec<CurveFamilyRequest>>(
+        r#"["apollo", { "name": "steiner", "n": 4 }]"#,
+    )
+    .unwrap();
+    let response = generate_mobius_transformation(
+        &CurveFamilyRegistry::default(),
+        inputs,
+        inputs,
+        curves,
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(response.curves.len(), 2);
+    assert_eq!(response.curves["steiner"].len(), 6);
+}
+
+#[test]
+fn invoke_api_apollonian_foci_at_fixed_points() {
+    // z -> 2z fixes 0 and infinity
+    let inputs = [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::Inf,
+    ];
+    let outputs = [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(2.0, 0.0),
+        ExtComplex::Inf,
+    ];
+    let registry = CurveFamilyRegistry::default();
+    let fixed_points = generate_mobius_transformation(&registry, inputs, outputs, vec![], None)
+        .unwrap()
+        .fixed_points
+        .expect("tform is not the identity");
+    assert_eq!(fixed_points, [ExtComplex::new(0.0, 0.0), ExtComplex::Inf]);
+
+    // with the foci snapped to the fixed points, the curves are polar gridlines which are scaled into each other
+    let request =
+        json!([{ "name": "apollo", "foci": fixed_points, "ratio": 2.0, "count": 3 }]).to_string();
+    let curves = serde_json::from_str::<Vec<CurveFamilyRequest>>(&request).unwrap();
+    let response =
+        generate_mobius_transformation(&registry, inputs, outputs, curves, None).unwrap();
+
+    for (k, curve) in response.curves["apollo"][..6].iter().enumerate() {
+        match *curve {
+            Curve::Circle { center, radius } => {
+                approx::assert_abs_diff_eq!(center, Complexf::ZERO, epsilon = 1e-9);
+                approx::assert_relative_eq!(radius, 2f64.powi(k as i32 - 2), epsilon = 1e-9);
+            }
+            curve => panic!("expected a circle, got {curve:?}"),
+        }
+    }
+}
+
+#[test]
+fn invoke_api_error_unknown_family() {
+    let inputs = [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::new(0.0, 1.0),
+    ];
+    let result = generate_mobius_transformation(
+        &CurveFamilyRegistry::default(),
+        inputs,
+        inputs,
+        vec!["xy".into(), "unknown".into()],
+        None,
+    );
+
+    assert!(matches!(result, Err(Error::UnknownCurveFamily { name }) if name == "unknown"));
+}
+
+#[test]
+fn serialize_error_unknown_family() {
+    let err = Error::UnknownCurveFamily {
+        name: "unknown".to_owned(),
+    };
+    let json = serde_json::to_string(&err).expect("serialization should succeed");
+    let expected = serde_json::to_string(&json!(
+        {
+            "kind": "UnknownCurveFamily",
+            "name": "unknown",
+            "message": "There is no curve family named \"unknown\"."
+        }
+    ))
+    .unwrap();
+
+    assert_eq!(json, expected);
+}
+
+#[test]
+fn invoke_list_curve_families() {
+    let registry = CurveFamilyRegistry::default();
+    let families = list_curve_families(&registry);
+
+    assert_eq!(families.len(), registry.iter().count());
+    let json = serde_json::to_value(&families[0]).expect("serialization should succeed");
+    let expected = json!(
+        {
+            "key": "xy",
+            "display_name": "Cartesian gridlines",
+            "description": "Evenly spaced horizontal and vertical lines.",
+            "params": [
+                {
+                    "name": "step",
+                    "description": "Spacing between consecutive gridlines",
+                    "type": "number",
+                    "default": 1.0
+                },
+                {
+                    "name": "extent",
+                    "description": "Distance from the axes up to which gridlines are generated",
+                    "type": "number",
+                    "default": 20.0
+                }
+            ],
+            "default_style": {
+                "color": null,
+                "line_width": 1.0,
+                "dashed": false
+            }
+        }
+    );
+
+    assert_eq!(json, expected);
+}
+
+#[test]
+fn invoke_api_describes_transform() {
+    // maps 0 -> i, 1 -> 0 and inf -> -1, which is z -> (-z + 1) / (z - i)
+    let inputs = [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::Inf,
+    ];
+    let outputs = [
+        ExtComplex::new(0.0, 1.0),
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(-1.0, 0.0),
+    ];
+    let transform = generate_mobius_transformation(
+        &CurveFamilyRegistry::default(),
+        inputs,
+        outputs,
+        vec![],
+        None,
+    )
+    .expect("transformation should exist")
+    .transform;
+
+    assert_eq!(transform.formula, "(-z + 1) / (z - i)");
+    approx::assert_abs_diff_eq!(
+        transform.determinant,
+        Complexf::new(-1.0, 1.0),
+        epsilon = 1e-9
+    );
+    approx::assert_abs_diff_eq!(transform.matrix[1][0], Complexf::ONE, epsilon = 1e-9);
+    approx::assert_abs_diff_eq!(transform.inverse[0][0], Complexf::I, epsilon = 1e-9);
+    for (image, expected) in transform.images.iter().zip(&outputs) {
+        assert!(math::chordal_distance(image, expected) < 1e-9);
+    }
+}
+
+#[test]
+fn format_mobius_tform_coefficients() {
+    for (tform, expected) in [
+        ((1.0, 0.0, 0.0, 1.0), "z"),
+        ((-1.0, 0.0, 0.0, 1.0), "-z"),
+        ((2.0, -3.5, 0.0, 1.0), "2z - 3.5"),
+        ((0.0, 1.0, 1.0, 0.0), "(1) / (z)"),
+        ((0.0, 0.0, 1.0, 1.0), "(0) / (z + 1)"),
+        ((1.0 / 3.0, 0.0, 1.0, -2.0), "(0.3333z) / (z - 2)"),
+    ] {
+        let (a, b, c, d) = tform;
+        let tform = Matrix2::new(
+            Complexf::from(a),
+            Complexf::from(b),
+            Complexf::from(c),
+            Complexf::from(d),
+        );
+        assert_eq!(format_mobius_tform(&tform), expected);
+    }
+
+    let tform = Matrix2::new(
+        Complexf::new(1.0, -2.0),
+        Complexf::new(0.0, -1.0),
+        Complexf::ZERO,
+        Complexf::ONE,
+    );
+    assert_eq!(format_mobius_tform(&tform), "(1 - 2i)z - i");
+}
+
+#[test]
+fn invoke_map_points() {
+    // z -> 2z + 1
+    let inputs = [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::Inf,
+    ];
+    let outputs = [
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::new(3.0, 0.0),
+        ExtComplex::Inf,
+    ];
+    let points = vec![
+        ExtComplex::new(0.0, 1.0),
+        ExtComplex::Inf,
+        ExtComplex::new(-0.5, 0.0),
+    ];
+
+    let images = map_points(inputs, outputs, points.clone(), false).unwrap();
+    assert_eq!(
+        images,
+        [
+            ExtComplex::new(1.0, 2.0),
+            ExtComplex::Inf,
+            ExtComplex::new(0.0, 0.0)
+        ]
+    );
+    let preimages = map_points(inputs, outputs, images, true).unwrap();
+    assert_eq!(preimages, points);
+}
+
+#[test]
+fn invoke_map_points_error_duplicate_points() {
+    let inputs = [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::Inf,
+    ];
+    let outputs = [
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::new(2.0, 0.0),
+        ExtComplex::Inf,
+    ];
+
+    for inverse in [false, true] {
+        let result = map_points(inputs, outputs, vec![ExtComplex::Inf], inverse);
+        assert!(matches!(
+            result,
+            Err(Error::DuplicateInputs { indices: [0, 1] })
+        ));
+    }
+}
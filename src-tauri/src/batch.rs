@@ -0,0 +1,176 @@
+//! Contains a batched version of the hot path of rendering a curve family,
+//! i.e. multiplying the matrix of each curve with a transformation and [converting][math::matrix_to_curve] it back into a curve.
+//!
+//! The matrices are laid out as a structure of arrays (see [`CurveMatrixBatch`]),
+//! so that the multiplication is a loop over contiguous `f64`s which the compiler can vectorize.
+//! The conversion is the same as for a single curve, which only branches on the kind of curve it ends up picking.
+//! Large families are split into chunks of [`BATCH_SIZE`] curves, which are transformed in parallel with the `rayon` feature.
+
+use nalgebra::Matrix2;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use super::*;
+
+/// The number of curves transformed together, small enough for a batch to stay in the cache.
+pub const BATCH_SIZE: usize = 1024;
+
+/// Fills the output before the transformed curves are written into it.
+const PLACEHOLDER_CURVE: Curve = Curve::Circle {
+    center: Complexf::ZERO,
+    radius: 0.0,
+};
+
+/// Transforms the curves represented by `matrices` (see [`math::curve_to_matrix`]) by multiplying each of them on the right
+/// by `inv_tform`, i.e. by the *inverse* of a Mobius transformation.
+///
+/// Gives the same curves as [converting][math::matrix_to_curve] each `m * inv_tform` one at a time, up to rounding.
+/// Returns the index of the first curve which is degenerate after being transformed, if any.
+pub fn transform_curve_matrices(
+    matrices: &[Matrix2<Complexf>],
+    inv_tform: &Matrix2<Complexf>,
+) -> Result<Vec<Curve>, usize> {
+    // each batch writes its curves straight into its own part of the output, rather than being collected and copied over
+    let mut curves = vec![PLACEHOLDER_CURVE; matrices.len()];
+    let transform_batch = |(chunk, output): (&[Matrix2<Complexf>], &mut [Curve])| {
+        let mut batch = CurveMatrixBatch::from_matrices(chunk);
+        batch.mul_right(inv_tform);
+        batch.write_curves(output);
+    };
+    #[cfg(feature = "rayon")]
+    matrices
+        .par_chunks(BATCH_SIZE)
+        .zip(curves.par_chunks_mut(BATCH_SIZE))
+        .for_each(transform_batch);
+    #[cfg(not(feature = "rayon"))]
+    matrices
+        .chunks(BATCH_SIZE)
+        .zip(curves.chunks_mut(BATCH_SIZE))
+        .for_each(transform_batch);
+
+    match curves.iter().position(Curve::is_degenerate) {
+        Some(index) => Err(index),
+        None => Ok(curves),
+    }
+}
+
+/// A batch of 2x2 complex matrices, e.g. representing curves, stored as a structure of arrays.
+///
+/// Each entry of the matrices is split into one array for its real part and one for its imaginary part,
+/// e.g. `re12[i]` is the real part of the top-right entry of the `i`th matrix.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CurveMatrixBatch {
+    re11: Vec<f64>,
+    im11: Vec<f64>,
+    re12: Vec<f64>,
+    im12: Vec<f64>,
+    re21: Vec<f64>,
+    im21: Vec<f64>,
+    re22: Vec<f64>,
+    im22: Vec<f64>,
+}
+
+impl CurveMatrixBatch {
+    /// Create a batch holding a copy of `matrices`.
+    pub fn from_matrices(matrices: &[Matrix2<Complexf>]) -> Self {
+        let entry = |f: fn(&Matrix2<Complexf>) -> f64| matrices.iter().map(f).collect();
+        CurveMatrixBatch {
+            re11: entry(|m| m.m11.re),
+            im11: entry(|m| m.m11.im),
+            re12: entry(|m| m.m12.re),
+            im12: entry(|m| m.m12.im),
+            re21: entry(|m| m.m21.re),
+            im21: entry(|m| m.m21.im),
+            re22: entry(|m| m.m22.re),
+            im22: entry(|m| m.m22.im),
+        }
+    }
+
+    /// The number of matrices in the batch.
+    pub fn len(&self) -> usize {
+        self.re11.len()
+    }
+
+    /// Checks whether the batch has no matrices.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the `i`th matrix of the batch.
+    ///
+    /// # Panics
+    /// If `i` is out of bounds.
+    pub fn matrix(&self, i: usize) -> Matrix2<Complexf> {
+        Matrix2::new(
+            Complexf::new(self.re11[i], self.im11[i]),
+            Complexf::new(self.re12[i], self.im12[i]),
+            Complexf::new(self.re21[i], self.im21[i]),
+            Complexf::new(self.re22[i], self.im22[i]),
+        )
+    }
+
+    /// Multiplies each matrix of the batch on the right by `tform`, in place.
+    pub fn mul_right(&mut self, tform: &Matrix2<Complexf>) {
+        // the rows of a matrix are multiplied independently, as row vectors
+        mul_rows_right(
+            &mut self.re11,
+            &mut self.im11,
+            &mut self.re12,
+            &mut self.im12,
+            tform,
+        );
+        mul_rows_right(
+            &mut self.re21,
+            &mut self.im21,
+            &mut self.re22,
+            &mut self.im22,
+            tform,
+        );
+    }
+
+    /// Converts each matrix of the batch into the [`Curve`] it represents (see [`math::matrix_to_curve`]).
+    pub fn to_curves(&self) -> Vec<Curve> {
+        let mut curves = vec![PLACEHOLDER_CURVE; self.len()];
+        self.write_curves(&mut curves);
+        curves
+    }
+
+    /// Internal helper used by [`CurveMatrixBatch::to_curves`] and [`transform_curve_matrices`],
+    /// which writes the curve represented by each matrix into `curves`.
+    ///
+    /// # Panics
+    /// If `curves` has a different length to the batch.
+    fn write_curves(&self, curves: &mut [Curve]) {
+        assert_eq!(curves.len(), self.len(), "expected one curve per matrix");
+        for (i, curve) in curves.iter_mut().enumerate() {
+            *curve = math::matrix_to_curve(&self.matrix(i));
+        }
+    }
+}
+
+/// Internal helper used by [`CurveMatrixBatch::mul_right`], which multiplies a batch of row vectors `[a, b]`
+/// on the right by `tform`.
+fn mul_rows_right(
+    a_re: &mut [f64],
+    a_im: &mut [f64],
+    b_re: &mut [f64],
+    b_im: &mut [f64],
+    tform: &Matrix2<Complexf>,
+) {
+    // zipping the slices rather than indexing them lets the compiler drop the bounds checks and vectorize the loop
+    let rows = a_re
+        .iter_mut()
+        .zip(a_im.iter_mut())
+        .zip(b_re.iter_mut().zip(b_im.iter_mut()));
+    for ((a_re, a_im), (b_re, b_im)) in rows {
+        let a = Complexf::new(*a_re, *a_im);
+        let b = Complexf::new(*b_re, *b_im);
+        let c = a * tform.m11 + b * tform.m21;
+        let d = a * tform.m12 + b * tform.m22;
+        (*a_re, *a_im, *b_re, *b_im) = (c.re, c.im, d.re, d.im);
+    }
+}
+
+#[cfg(test)]
+#[path = "batch_tests.rs"]
+mod tests;
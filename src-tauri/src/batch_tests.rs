@@ -0,0 +1,94 @@
+use super::*;
+
+// a Mobius transformation with no special structure, so that all the entries of the products are non-trivial
+fn inv_tform() -> Matrix2<Complexf> {
+    Matrix2::new(
+        Complexf::new(1.0, 2.0),
+        Complexf::new(-0.5, 0.25),
+        Complexf::new(0.3, -1.0),
+        Complexf::new(2.0, 0.0),
+    )
+}
+
+// the curves of all the built-in families, which are a mix of lines and circles
+fn family_matrices() -> Vec<Matrix2<Complexf>> {
+    let registry = CurveFamilyRegistry::default();
+    registry
+        .iter()
+        .flat_map(|family| family.generate(&FamilyParams::default()))
+        .collect()
+}
+
+#[test]
+fn batch_roundtrip() {
+    let matrices = family_matrices();
+    let batch = CurveMatrixBatch::from_matrices(&matrices);
+
+    assert_eq!(batch.len(), matrices.len());
+    for (i, m) in matrices.iter().enumerate() {
+        assert_eq!(batch.matrix(i), *m);
+    }
+    assert!(CurveMatrixBatch::from_matrices(&[]).is_empty());
+}
+
+#[test]
+fn batch_mul_right() {
+    let matrices = family_matrices();
+    let mut batch = CurveMatrixBatch::from_matrices(&matrices);
+    batch.mul_right(&inv_tform());
+
+    for (i, m) in matrices.iter().enumerate() {
+        approx::assert_relative_eq!(batch.matrix(i), m * inv_tform(), max_relative = 1e-12);
+    }
+}
+
+#[test]
+fn batch_transform_matches_unbatched() {
+    // repeat the curves to span several batches, with the last one partially full
+    let matrices: Vec<_> = family_matrices()
+        .into_iter()
+        .cycle()
+        .take(2 * BATCH_SIZE + 17)
+        .collect();
+    let curves = transform_curve_matrices(&matrices, &inv_tform()).unwrap();
+
+    assert_eq!(curves.len(), matrices.len());
+    for (curve, m) in curves.iter().zip(&matrices) {
+        match (*curve, math::matrix_to_curve(&(m * inv_tform()))) {
+            (
+                Curve::Circle { center, radius },
+                Curve::Circle {
+                    center: expected_center,
+                    radius: expected_radius,
+                },
+            ) => {
+                approx::assert_relative_eq!(center, expected_center, max_relative = 1e-9);
+                approx::assert_relative_eq!(radius, expected_radius, max_relative = 1e-9);
+            }
+            (
+                Curve::Line { point, slope },
+                Curve::Line {
+                    point: expected_point,
+                    slope: expected_slope,
+                },
+            ) => {
+                approx::assert_relative_eq!(point, expected_point, max_relative = 1e-9);
+                approx::assert_relative_eq!(slope, expected_slope, max_relative = 1e-9);
+            }
+            (actual, expected) => panic!("expected {expected:?}, got {actual:?}"),
+        }
+    }
+}
+
+#[test]
+fn batch_transform_degenerate() {
+    let mut matrices = vec![Matrix2::identity(); BATCH_SIZE + 10];
+    matrices[BATCH_SIZE + 3] = Matrix2::zeros();
+    matrices[BATCH_SIZE + 5] = Matrix2::zeros();
+
+    assert_eq!(
+        transform_curve_matrices(&matrices, &inv_tform()),
+        Err(BATCH_SIZE + 3)
+    );
+    assert_eq!(transform_curve_matrices(&[], &inv_tform()), Ok(vec![]));
+}
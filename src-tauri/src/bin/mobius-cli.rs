@@ -0,0 +1,355 @@
+//! A headless command-line interface for rendering Mobius transformations without launching the app,
+//! e.g. to generate figures in bulk or to test the whole pipeline in CI.
+//! It can also serve the API as JSON-RPC (see [`mb::RpcServer`]) for scripts to drive.
+//!
+//! Run with `--help` for usage.
+//! SVG and PNG images can only be written with the `render` feature, whereas JSON curves and serving don't need it.
+
+use std::ffi::OsStr;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use mobius_app as mb;
+
+const USAGE: &str = "\
+Renders curve families under a Mobius transformation, as JSON curves, an SVG image or a PNG image.
+
+Usage: mobius-cli [OPTIONS]
+       mobius-cli serve [--tcp <ADDRESS>] [--families-file <PATH>]
+
+The transformation is given by a scene file, a preset, or control points on the command line.
+Points are written like 1.5-2i, -5, i or inf.
+
+Options:
+  --scene <PATH>             Load control points, curve families and viewport from a scene file
+  --preset <KEY>             Load control points and curve families from a built-in preset
+  --inputs <P,P,P>           Input control points [default: 0,-5,5]
+  --outputs <P,P,P>          Output control points [default: the inputs]
+  --family <NAME>            Curve family to render, may be repeated [default: xy]
+  --families-file <PATH>     Load user-defined curve families from a file
+  --viewport <X0,Y0,X1,Y1>   Region of the plane to render, given by its bottom-left and top-right corners
+  --format <json|svg|png>    Output format [default: inferred from the output file, otherwise json]
+  --width <PIXELS>           Width of the image [default: 600]
+  --height <PIXELS>          Height of the image [default: 600]
+  --line-width <PIXELS>      Width of the curves in the image [default: 1]
+  -o, --output <PATH>        File to write to [default: stdout]
+  -h, --help                 Print this message
+
+Serving:
+  serve                      Serve the API as JSON-RPC 2.0 instead, one message per line over stdio
+  --tcp <ADDRESS>            Serve over TCP instead of stdio, on a local address like 127.0.0.1:7878 (or just a port)
+";
+
+/// The formats the curves can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    #[cfg(feature = "render")]
+    Svg,
+    #[cfg(feature = "render")]
+    Png,
+}
+
+impl Format {
+    fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "json" => Ok(Format::Json),
+            #[cfg(feature = "render")]
+            "svg" => Ok(Format::Svg),
+            #[cfg(feature = "render")]
+            "png" => Ok(Format::Png),
+            #[cfg(not(feature = "render"))]
+            "svg" | "png" => Err(format!(
+                "the {s} format requires building with the render feature"
+            )),
+            _ => Err(format!("unknown format \"{s}\", expected json, svg or png")),
+        }
+    }
+
+    /// The format implied by the extension of the output file, if it is one of the formats.
+    fn from_extension(path: &Path) -> Result<Option<Self>, String> {
+        match path.extension().and_then(OsStr::to_str) {
+            Some(ext @ ("json" | "svg" | "png")) => Format::parse(ext).map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// The parsed command line arguments.
+#[derive(Debug, Default)]
+// the image options are still accepted without the `render` feature, but only JSON curves can be written
+#[cfg_attr(not(feature = "render"), allow(dead_code))]
+struct Args {
+    scene: Option<PathBuf>,
+    preset: Option<String>,
+    inputs: Option<[mb::ExtComplex; 3]>,
+    outputs: Option<[mb::ExtComplex; 3]>,
+    families: Vec<String>,
+    families_file: Option<PathBuf>,
+    viewport: Option<mb::Viewport>,
+    format: Option<Format>,
+    width: Option<u32>,
+    height: Option<u32>,
+    line_width: Option<f64>,
+    output: Option<PathBuf>,
+    serve: bool,
+    tcp: Option<SocketAddr>,
+    help: bool,
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("error: {err}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    if args.help {
+        print!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+    let result = if args.serve { serve(args) } else { run(args) };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        // accept both `--flag value` and `--flag=value`
+        let (flag, mut inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                (flag.to_owned(), Some(value.to_owned()))
+            }
+            _ => (arg, None),
+        };
+        if flag == "-h" || flag == "--help" {
+            parsed.help = true;
+            continue;
+        }
+        if flag == "serve" {
+            parsed.serve = true;
+            continue;
+        }
+        let mut value = || {
+            inline_value
+                .take()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("missing value for {flag}"))
+        };
+        match flag.as_str() {
+            "--scene" => parsed.scene = Some(value()?.into()),
+            "--preset" => parsed.preset = Some(value()?),
+            "--inputs" => parsed.inputs = Some(parse_points(&value()?)?),
+            "--outputs" => parsed.outputs = Some(parse_points(&value()?)?),
+            "--family" => parsed.families.push(value()?),
+            "--families-file" => parsed.families_file = Some(value()?.into()),
+            "--viewport" => parsed.viewport = Some(parse_viewport(&value()?)?),
+            "--format" => parsed.format = Some(Format::parse(&value()?)?),
+            "--width" => parsed.width = Some(parse_number(&flag, &value()?)?),
+            "--height" => parsed.height = Some(parse_number(&flag, &value()?)?),
+            "--line-width" => parsed.line_width = Some(parse_positive(&flag, &value()?)?),
+            "-o" | "--output" => parsed.output = Some(value()?.into()),
+            "--tcp" => parsed.tcp = Some(parse_address(&value()?)?),
+            _ => return Err(format!("unknown option {flag}")),
+        }
+    }
+    if parsed.scene.is_some() && parsed.preset.is_some() {
+        return Err("--scene and --preset can't be used together".to_owned());
+    }
+    if parsed.tcp.is_some() && !parsed.serve {
+        return Err("--tcp can only be used with serve".to_owned());
+    }
+    Ok(parsed)
+}
+
+fn parse_points(s: &str) -> Result<[mb::ExtComplex; 3], String> {
+    let points = s
+        .split(',')
+        .map(|p| {
+            p.parse()
+                .map_err(|err: mb::ParseExtComplexError| err.to_string())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    points
+        .try_into()
+        .map_err(|_| format!("expected 3 comma-separated points, got \"{s}\""))
+}
+
+fn parse_viewport(s: &str) -> Result<mb::Viewport, String> {
+    let bounds = s
+        .split(',')
+        .map(|x| x.trim().parse::<f64>().ok().filter(|x| x.is_finite()))
+        .collect::<Option<Vec<_>>>();
+    match bounds.as_deref() {
+        Some(&[x0, y0, x1, y1]) if x0 < x1 && y0 < y1 => Ok(mb::Viewport {
+            min: mb::Complexf::new(x0, y0),
+            max: mb::Complexf::new(x1, y1),
+        }),
+        _ => Err(format!(
+            "expected a viewport like -10,-10,10,10 with a positive width and height, got \"{s}\""
+        )),
+    }
+}
+
+fn parse_address(s: &str) -> Result<SocketAddr, String> {
+    let address = match s.parse::<u16>() {
+        Ok(port) => SocketAddr::from((Ipv4Addr::LOCALHOST, port)),
+        Err(_) => s
+            .parse()
+            .map_err(|_| format!("invalid address \"{s}\", expected one like 127.0.0.1:7878"))?,
+    };
+    // the server has no authentication, so it must not be reachable from other machines
+    if !address.ip().is_loopback() {
+        return Err(format!("can only serve on a local address, got {address}"));
+    }
+    Ok(address)
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, s: &str) -> Result<T, String> {
+    s.parse()
+        .map_err(|_| format!("invalid value \"{s}\" for {flag}"))
+}
+
+fn parse_positive(flag: &str, s: &str) -> Result<f64, String> {
+    Some(parse_number::<f64>(flag, s)?)
+        .filter(|x| x.is_finite() && *x > 0.0)
+        .ok_or_else(|| format!("expected a positive number for {flag}, got \"{s}\""))
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let mut registry = mb::CurveFamilyRegistry::default();
+    if let Some(path) = &args.families_file {
+        mb::load_custom_families(path, &mut registry).map_err(|err| err.to_string())?;
+    }
+
+    let scene = if let Some(path) = &args.scene {
+        Some(mb::load_scene(path.clone()).map_err(|err| err.to_string())?)
+    } else if let Some(key) = &args.preset {
+        Some(mb::load_preset(key).map_err(|err| err.to_string())?)
+    } else {
+        None
+    };
+
+    let default_inputs = [0.0, -5.0, 5.0].map(|re| mb::ExtComplex::new(re, 0.0));
+    let inputs = args
+        .inputs
+        .or(scene.as_ref().map(mb::Scene::inputs))
+        .unwrap_or(default_inputs);
+    let outputs = args
+        .outputs
+        .or(scene.as_ref().map(mb::Scene::outputs))
+        .unwrap_or(inputs);
+    let families = if !args.families.is_empty() {
+        args.families
+            .iter()
+            .map(|name| mb::CurveFamilyRequest::Name(name))
+            .collect()
+    } else if let Some(scene) = &scene {
+        scene.family_requests()
+    } else {
+        vec![mb::CurveFamilyRequest::Name("xy")]
+    };
+
+    let format = match args.format {
+        Some(format) => format,
+        None => args
+            .output
+            .as_deref()
+            .map(Format::from_extension)
+            .transpose()?
+            .flatten()
+            .unwrap_or(Format::Json),
+    };
+    #[cfg(feature = "render")]
+    let options = figure_options(&args, scene.as_ref(), format)?;
+
+    // JSON curves are only clipped when a viewport is asked for, whereas images are always clipped to what is visible
+    let viewport = match format {
+        Format::Json => args
+            .viewport
+            .or(scene.as_ref().and_then(|scene| scene.viewport)),
+        #[cfg(feature = "render")]
+        Format::Svg | Format::Png => Some(options.visible_viewport()),
+    };
+    let response =
+        mb::generate_mobius_transformation(&registry, inputs, outputs, families, viewport)
+            .map_err(|err| err.to_string())?;
+
+    let writer: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(
+            File::create(path)
+                .map_err(|err| format!("could not create {}: {err}", path.display()))?,
+        ),
+        None => Box::new(io::stdout().lock()),
+    };
+    let mut writer = BufWriter::new(writer);
+    #[cfg(feature = "render")]
+    let curves: Vec<mb::Curve> = response.curves.values().flatten().copied().collect();
+    match format {
+        Format::Json => serde_json::to_writer_pretty(&mut writer, &response)
+            .map_err(io::Error::from)
+            .and_then(|()| writeln!(writer)),
+        #[cfg(feature = "render")]
+        Format::Svg => mb::write_svg(&mut writer, &curves, &options),
+        #[cfg(feature = "render")]
+        Format::Png => mb::write_png(&mut writer, &curves, &options),
+    }
+    .and_then(|()| writer.flush())
+    .map_err(|err| format!("could not write the output: {err}"))
+}
+
+/// Internal helper used by [`run`], which works out how images are drawn from the arguments and the scene.
+#[cfg(feature = "render")]
+fn figure_options(
+    args: &Args,
+    scene: Option<&mb::Scene>,
+    format: Format,
+) -> Result<mb::FigureOptions, String> {
+    let defaults = mb::FigureOptions::default();
+    let options = mb::FigureOptions {
+        width: args.width.unwrap_or(defaults.width),
+        height: args.height.unwrap_or(defaults.height),
+        viewport: args
+            .viewport
+            .or(scene.and_then(|scene| scene.viewport))
+            .unwrap_or(defaults.viewport),
+        line_width: args.line_width.unwrap_or(defaults.line_width),
+        ..defaults
+    };
+    // catches a viewport loaded from a scene which is empty, since those given with --viewport are checked when parsed
+    options.validate().map_err(|err| err.to_string())?;
+    if format == Format::Png && mb::canvas_pixels(options.width, options.height).is_none() {
+        return Err("image dimensions are too large".to_owned());
+    }
+    Ok(options)
+}
+
+fn serve(args: Args) -> Result<(), String> {
+    let mut registry = mb::CurveFamilyRegistry::default();
+    if let Some(path) = &args.families_file {
+        mb::load_custom_families(path, &mut registry).map_err(|err| err.to_string())?;
+    }
+    let server = mb::RpcServer::new(registry);
+
+    let result = match args.tcp {
+        Some(address) => {
+            let listener = TcpListener::bind(address)
+                .map_err(|err| format!("could not listen on {address}: {err}"))?;
+            eprintln!("serving JSON-RPC on {address}");
+            server.serve_tcp(&listener, |err| eprintln!("connection failed: {err}"))
+        }
+        None => server.serve(io::stdin().lock(), io::stdout().lock()),
+    };
+    result.map_err(|err| format!("could not serve: {err}"))
+}
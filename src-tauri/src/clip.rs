@@ -0,0 +1,191 @@
+//! Contains the functionality for trimming [`Curves`][Curve] down to the pieces lying inside a [`Viewport`].
+
+use std::f64::consts::TAU;
+
+use super::*;
+
+/// Clip a curve to a viewport, returning the pieces of the curve which are visible.
+///
+/// The viewport is treated as a closed rectangle, so curves lying along its boundary are kept.
+/// Lines, rays and segments are clipped into a single [`Curve::Segment`] (if visible),
+/// and circles and arcs are clipped into (possibly several) [`Curve::Arc`].
+/// A curve which lies entirely inside the viewport is returned unchanged,
+/// and a curve which doesn't intersect the viewport (or only touches it at a single point) is dropped.
+pub fn clip_curve(curve: &Curve, viewport: &Viewport) -> Vec<Curve> {
+    match *curve {
+        Curve::Line { point, slope } => {
+            clip_line(point, slope, (f64::NEG_INFINITY, f64::INFINITY), viewport)
+                .into_iter()
+                .collect()
+        }
+        Curve::Ray { point, direction } => {
+            clip_line(point, direction, (0.0, f64::INFINITY), viewport)
+                .into_iter()
+                .collect()
+        }
+        Curve::Segment { start, end } => {
+            // avoid perturbing the endpoints of segments which don't need to be clipped
+            if contains(viewport, start, 0.0) && contains(viewport, end, 0.0) {
+                vec![*curve]
+            } else {
+                clip_line(start, end - start, (0.0, 1.0), viewport)
+                    .into_iter()
+                    .collect()
+            }
+        }
+        Curve::Circle { center, radius } => {
+            clip_circle(center, radius, (0.0, TAU), viewport).unwrap_or_else(|| vec![*curve])
+        }
+        Curve::Arc {
+            center,
+            radius,
+            start,
+            end,
+        } => {
+            let start_angle = (start - center).arg();
+            let sweep = ((end - center).arg() - start_angle).rem_euclid(TAU);
+            clip_circle(center, radius, (start_angle, sweep), viewport)
+                .unwrap_or_else(|| vec![*curve])
+        }
+    }
+}
+
+/// Clip the points `point + direction * t` for `t` in the given range using the Liang-Barsky algorithm.
+///
+/// Returns the visible piece as a [`Curve::Segment`], or nothing if no piece of positive length is visible.
+fn clip_line(
+    point: Complexf,
+    direction: Complexf,
+    (mut t_min, mut t_max): (f64, f64),
+    viewport: &Viewport,
+) -> Option<Curve> {
+    if direction.norm_sqr() == 0.0 {
+        return None;
+    }
+
+    // each edge of the viewport constrains `p * t <= q`
+    let constraints = [
+        (-direction.re, point.re - viewport.min.re),
+        (direction.re, viewport.max.re - point.re),
+        (-direction.im, point.im - viewport.min.im),
+        (direction.im, viewport.max.im - point.im),
+    ];
+    for (p, q) in constraints {
+        if p == 0.0 {
+            // parallel to the edge, so either every point or no point satisfies the constraint
+            if q < 0.0 {
+                return None;
+            }
+        } else if p < 0.0 {
+            t_min = t_min.max(q / p);
+        } else {
+            t_max = t_max.min(q / p);
+        }
+    }
+
+    (t_min < t_max).then(|| Curve::Segment {
+        start: point + direction * t_min,
+        end: point + direction * t_max,
+    })
+}
+
+/// Clip the arc of the circle `|z - center| = radius` running counterclockwise
+/// from the angle `start_angle` through an angle of `sweep`.
+///
+/// Returns the visible pieces as [`Curve::Arc`], or nothing if the whole arc is visible.
+fn clip_circle(
+    center: Complexf,
+    radius: f64,
+    (start_angle, sweep): (f64, f64),
+    viewport: &Viewport,
+) -> Option<Vec<Curve>> {
+    // allow for floating point error when checking whether points lie on the boundary of the viewport
+    let tolerance = 1e-9 * viewport.width().max(viewport.height());
+    let point_at = |angle: f64| center + Complexf::from_polar(radius, start_angle + angle);
+
+    // find where the circle crosses the boundary of the viewport, as angles relative to the start of the arc
+    let mut breaks = vec![0.0, sweep];
+    let mut add_crossing = |offset: Complexf| {
+        let angle = (offset.arg() - start_angle).rem_euclid(TAU);
+        if angle < sweep {
+            breaks.push(angle);
+        }
+    };
+    for x in [viewport.min.re, viewport.max.re] {
+        let dx = x - center.re;
+        if dx.abs() <= radius {
+            let dy = (radius * radius - dx * dx).sqrt();
+            for y in [center.im + dy, center.im - dy] {
+                if y >= viewport.min.im - tolerance && y <= viewport.max.im + tolerance {
+                    add_crossing(Complexf::new(dx, y - center.im));
+                }
+            }
+        }
+    }
+    for y in [viewport.min.im, viewport.max.im] {
+        let dy = y - center.im;
+        if dy.abs() <= radius {
+            let dx = (radius * radius - dy * dy).sqrt();
+            for x in [center.re + dx, center.re - dx] {
+                if x >= viewport.min.re - tolerance && x <= viewport.max.re + tolerance {
+                    add_crossing(Complexf::new(x - center.re, dy));
+                }
+            }
+        }
+    }
+    breaks.sort_by(f64::total_cmp);
+    breaks.dedup();
+
+    // between consecutive crossings, the arc is either entirely inside or entirely outside the viewport
+    let mut pieces: Vec<(f64, f64)> = Vec::new();
+    for window in breaks.windows(2) {
+        let (lo, hi) = (window[0], window[1]);
+        if hi - lo <= f64::EPSILON || !contains(viewport, point_at(0.5 * (lo + hi)), tolerance) {
+            continue;
+        }
+        // merge with the previous piece if they meet, e.g. at a point where the circle touches the boundary
+        match pieces.last_mut() {
+            Some(last) if last.1 == lo => last.1 = hi,
+            _ => pieces.push((lo, hi)),
+        }
+    }
+
+    // a full circle may have a piece wrapping around from the end back to the start
+    if sweep == TAU && pieces.len() > 1 {
+        let (first, last) = (pieces[0], pieces[pieces.len() - 1]);
+        if first.0 == 0.0 && last.1 == TAU {
+            pieces.pop();
+            pieces[0] = (last.0 - TAU, first.1);
+        }
+    }
+
+    if let [(lo, hi)] = pieces[..] {
+        if lo == 0.0 && hi == sweep {
+            return None;
+        }
+    }
+    Some(
+        pieces
+            .into_iter()
+            .map(|(lo, hi)| Curve::Arc {
+                center,
+                radius,
+                start: point_at(lo),
+                end: point_at(hi),
+            })
+            .collect(),
+    )
+}
+
+/// Check whether a point lies inside the viewport, allowing it to lie up to `tolerance` outside.
+#[inline(always)]
+fn contains(viewport: &Viewport, p: Complexf, tolerance: f64) -> bool {
+    p.re >= viewport.min.re - tolerance
+        && p.re <= viewport.max.re + tolerance
+        && p.im >= viewport.min.im - tolerance
+        && p.im <= viewport.max.im + tolerance
+}
+
+#[cfg(test)]
+#[path = "clip_tests.rs"]
+mod tests;
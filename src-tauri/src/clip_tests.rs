@@ -0,0 +1,285 @@
+use paste::paste;
+
+use super::*;
+
+// viewport covering the square with corners -1 - i and 1 + i
+fn unit_viewport() -> Viewport {
+    Viewport {
+        min: Complexf::new(-1.0, -1.0),
+        max: Complexf::new(1.0, 1.0),
+    }
+}
+
+// checks that two curves are equal, allowing for floating point error
+fn assert_curve_eq(actual: &Curve, expected: &Curve) {
+    match (*actual, *expected) {
+        (Curve::Segment { start: s1, end: e1 }, Curve::Segment { start: s2, end: e2 }) => {
+            approx::assert_abs_diff_eq!(s1, s2, epsilon = 1e-9);
+            approx::assert_abs_diff_eq!(e1, e2, epsilon = 1e-9);
+        }
+        (
+            Curve::Arc {
+                center: c1,
+                radius: r1,
+                start: s1,
+                end: e1,
+            },
+            Curve::Arc {
+                center: c2,
+                radius: r2,
+                start: s2,
+                end: e2,
+            },
+        ) => {
+            approx::assert_abs_diff_eq!(c1, c2, epsilon = 1e-9);
+            approx::assert_abs_diff_eq!(r1, r2, epsilon = 1e-9);
+            approx::assert_abs_diff_eq!(s1, s2, epsilon = 1e-9);
+            approx::assert_abs_diff_eq!(e1, e2, epsilon = 1e-9);
+        }
+        (actual, expected) => assert_eq!(actual, expected),
+    }
+}
+
+// test clipping a curve against the unit viewport
+macro_rules! clip_curve_test {
+    ($case:ident, $curve:expr, [$($expected:expr),* $(,)?]) => {
+        paste! {
+            #[test]
+            fn [<clip_curve_ $case>]() {
+                let clipped = clip_curve(&$curve, &unit_viewport());
+                let expected: Vec<Curve> = vec![$($expected),*];
+
+                assert_eq!(clipped.len(), expected.len(), "unexpected pieces {clipped:?}");
+                for (actual, expected) in clipped.iter().zip(&expected) {
+                    assert_curve_eq(actual, expected);
+                }
+            }
+        }
+    };
+}
+
+/* lines, rays and segments */
+
+clip_curve_test!(
+    line_diagonal,
+    Curve::Line {
+        point: Complexf::ZERO,
+        slope: Complexf::new(1.0, 1.0),
+    },
+    [Curve::Segment {
+        start: Complexf::new(-1.0, -1.0),
+        end: Complexf::new(1.0, 1.0),
+    }]
+);
+clip_curve_test!(
+    line_outside,
+    Curve::Line {
+        point: Complexf::new(0.0, 2.0),
+        slope: Complexf::ONE,
+    },
+    []
+);
+// lines along the boundary of the viewport are kept
+clip_curve_test!(
+    line_along_edge,
+    Curve::Line {
+        point: Complexf::new(5.0, 1.0),
+        slope: Complexf::new(-2.0, 0.0),
+    },
+    [Curve::Segment {
+        start: Complexf::new(1.0, 1.0),
+        end: Complexf::new(-1.0, 1.0),
+    }]
+);
+// lines which only touch a corner of the viewport are dropped
+clip_curve_test!(
+    line_through_corner,
+    Curve::Line {
+        point: Complexf::new(1.0, 1.0),
+        slope: Complexf::new(1.0, -1.0),
+    },
+    []
+);
+clip_curve_test!(
+    ray_from_inside,
+    Curve::Ray {
+        point: Complexf::new(0.5, 0.0),
+        direction: Complexf::I,
+    },
+    [Curve::Segment {
+        start: Complexf::new(0.5, 0.0),
+        end: Complexf::new(0.5, 1.0),
+    }]
+);
+clip_curve_test!(
+    ray_pointing_away,
+    Curve::Ray {
+        point: Complexf::new(2.0, 0.0),
+        direction: Complexf::ONE,
+    },
+    []
+);
+clip_curve_test!(
+    segment_inside,
+    Curve::Segment {
+        start: Complexf::new(-0.5, 0.25),
+        end: Complexf::new(0.5, -0.25),
+    },
+    [Curve::Segment {
+        start: Complexf::new(-0.5, 0.25),
+        end: Complexf::new(0.5, -0.25),
+    }]
+);
+clip_curve_test!(
+    segment_crossing,
+    Curve::Segment {
+        start: Complexf::new(-3.0, 0.0),
+        end: Complexf::new(0.0, 0.0),
+    },
+    [Curve::Segment {
+        start: Complexf::new(-1.0, 0.0),
+        end: Complexf::new(0.0, 0.0),
+    }]
+);
+
+/* circles and arcs */
+
+clip_curve_test!(
+    circle_inside,
+    Curve::Circle {
+        center: Complexf::ZERO,
+        radius: 0.5,
+    },
+    [Curve::Circle {
+        center: Complexf::ZERO,
+        radius: 0.5,
+    }]
+);
+// circles touching the boundary from the inside are kept whole
+clip_curve_test!(
+    circle_inscribed,
+    Curve::Circle {
+        center: Complexf::ZERO,
+        radius: 1.0,
+    },
+    [Curve::Circle {
+        center: Complexf::ZERO,
+        radius: 1.0,
+    }]
+);
+// circles touching the boundary from the outside are dropped
+clip_curve_test!(
+    circle_touching_outside,
+    Curve::Circle {
+        center: Complexf::new(2.0, 0.0),
+        radius: 1.0,
+    },
+    []
+);
+clip_curve_test!(
+    circle_far_away,
+    Curve::Circle {
+        center: Complexf::new(1e9, 0.0),
+        radius: 1.0,
+    },
+    []
+);
+// circles enclosing the viewport are not visible at all
+clip_curve_test!(
+    circle_enclosing,
+    Curve::Circle {
+        center: Complexf::ZERO,
+        radius: 2.0,
+    },
+    []
+);
+// the visible piece wraps around the start of the circle (i.e. the angle 0)
+clip_curve_test!(
+    circle_crossing_left_edge,
+    Curve::Circle {
+        center: Complexf::new(-2.0, 0.0),
+        radius: 1.25,
+    },
+    [Curve::Arc {
+        center: Complexf::new(-2.0, 0.0),
+        radius: 1.25,
+        start: Complexf::new(-1.0, -0.75),
+        end: Complexf::new(-1.0, 0.75),
+    }]
+);
+// circle passing through all four corners, of which only the four caps outside the viewport are removed
+clip_curve_test!(
+    circle_circumscribed,
+    Curve::Circle {
+        center: Complexf::ZERO,
+        radius: 1.2,
+    },
+    [
+        Curve::Arc {
+            center: Complexf::ZERO,
+            radius: 1.2,
+            start: Complexf::new(1.0, 0.44f64.sqrt()),
+            end: Complexf::new(0.44f64.sqrt(), 1.0),
+        },
+        Curve::Arc {
+            center: Complexf::ZERO,
+            radius: 1.2,
+            start: Complexf::new(-(0.44f64.sqrt()), 1.0),
+            end: Complexf::new(-1.0, 0.44f64.sqrt()),
+        },
+        Curve::Arc {
+            center: Complexf::ZERO,
+            radius: 1.2,
+            start: Complexf::new(-1.0, -(0.44f64.sqrt())),
+            end: Complexf::new(-(0.44f64.sqrt()), -1.0),
+        },
+        Curve::Arc {
+            center: Complexf::ZERO,
+            radius: 1.2,
+            start: Complexf::new(0.44f64.sqrt(), -1.0),
+            end: Complexf::new(1.0, -(0.44f64.sqrt())),
+        },
+    ]
+);
+clip_curve_test!(
+    arc_inside,
+    Curve::Arc {
+        center: Complexf::ZERO,
+        radius: 0.5,
+        start: Complexf::new(0.5, 0.0),
+        end: Complexf::new(0.0, 0.5),
+    },
+    [Curve::Arc {
+        center: Complexf::ZERO,
+        radius: 0.5,
+        start: Complexf::new(0.5, 0.0),
+        end: Complexf::new(0.0, 0.5),
+    }]
+);
+// only the part of the arc inside the viewport is kept, even though the rest of the circle is visible too
+clip_curve_test!(
+    arc_crossing_left_edge,
+    Curve::Arc {
+        center: Complexf::new(-1.0, 0.0),
+        radius: 0.5,
+        start: Complexf::new(-1.0, 0.5),
+        end: Complexf::new(-1.0, -0.5),
+    },
+    []
+);
+// the arc starts on the boundary, then leaves and re-enters the viewport
+clip_curve_test!(
+    arc_crossing_top_edge,
+    Curve::Arc {
+        center: Complexf::new(0.0, 1.0),
+        radius: 0.5,
+        start: Complexf::new(0.5, 1.0),
+        end: Complexf::new(0.0, 0.5),
+    },
+    [Curve::Arc {
+        center: Complexf::new(0.0, 1.0),
+        radius: 0.5,
+        start: Complexf::new(-0.5, 1.0),
+        end: Complexf::new(0.0, 0.5),
+    }]
+);
@@ -0,0 +1,207 @@
+//! Contains the Tauri commands invoked by the frontend.
+//!
+//! Each command is a thin wrapper around the corresponding function in [`api`],
+//! which pulls any shared data it needs out of Tauri's managed state.
+
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use tauri::{async_runtime, AppHandle, Manager, State};
+
+use super::*;
+
+/// Tauri command wrapping [`api::generate_latest_mobius_transformation`].
+///
+/// Runs asynchronously so that the requests sent while dragging a control point don't queue up behind each other.
+/// It shares its [`RequestSequence`] with [`set_control_point`], since either gives the latest frame to render.
+#[tauri::command(async)]
+pub fn generate_mobius_transformation<'a>(
+    registry: State<'_, CurveFamilyRegistry>,
+    sequence: State<'_, RequestSequence>,
+    seq: u64,
+    inputs: [ExtComplex; 3],
+    outputs: [ExtComplex; 3],
+    curves: Vec<CurveFamilyRequest<'a>>,
+    viewport: Option<Viewport>,
+) -> Sequenced<GenerateMobiusResponse<'a>> {
+    api::generate_latest_mobius_transformation(
+        &registry, &sequence, seq, inputs, outputs, curves, viewport,
+    )
+}
+
+/// Tauri command wrapping [`api::generate_hyperbolic_transformation`].
+#[tauri::command]
+pub fn generate_hyperbolic_transformation<'a>(
+    registry: State<'_, CurveFamilyRegistry>,
+    model: HyperbolicModel,
+    inputs: [ExtComplex; 3],
+    outputs: [ExtComplex; 3],
+    curves: Vec<CurveFamilyRequest<'a>>,
+    viewport: Option<Viewport>,
+) -> Result<GenerateHyperbolicResponse<'a>, Error> {
+    api::generate_hyperbolic_transformation(&registry, model, inputs, outputs, curves, viewport)
+}
+
+/// Tauri command wrapping [`api::export_mobius_animation`].
+///
+/// Runs asynchronously since rendering and encoding an animation can take a while.
+#[tauri::command(async)]
+pub fn export_mobius_animation(
+    registry: State<'_, CurveFamilyRegistry>,
+    inputs: [ExtComplex; 3],
+    outputs: [ExtComplex; 3],
+    curves: Vec<CurveFamilyRequest>,
+    options: AnimationOptions,
+    path: PathBuf,
+) -> Result<(), Error> {
+    api::export_mobius_animation(&registry, inputs, outputs, curves, options, path)
+}
+
+/// Tauri command wrapping [`api::map_points`].
+#[tauri::command]
+pub fn map_points(
+    inputs: [ExtComplex; 3],
+    outputs: [ExtComplex; 3],
+    points: Vec<ExtComplex>,
+    inverse: bool,
+) -> Result<Vec<ExtComplex>, Error> {
+    api::map_points(inputs, outputs, points, inverse)
+}
+
+/// Tauri command wrapping [`api::list_curve_families`].
+#[tauri::command]
+pub fn list_curve_families(registry: State<'_, CurveFamilyRegistry>) -> Vec<CurveFamilyInfo> {
+    api::list_curve_families(&registry)
+}
+
+/// Internal helper which locks the session.
+///
+/// A panic while the session is locked can't leave it in an inconsistent state, since it is only modified once an
+/// update has been validated, so the lock is recovered rather than failing every later command.
+fn lock_session<'s>(session: &'s State<'_, Mutex<Session>>) -> MutexGuard<'s, Session> {
+    session.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Tauri command wrapping [`Session::start`].
+#[tauri::command]
+pub fn start_session(
+    registry: State<'_, CurveFamilyRegistry>,
+    session: State<'_, Mutex<Session>>,
+    inputs: [ExtComplex; 3],
+    outputs: [ExtComplex; 3],
+    curves: Vec<CurveFamilyRequest>,
+    viewport: Option<Viewport>,
+) -> Result<SessionUpdate, Error> {
+    lock_session(&session).start(&registry, inputs, outputs, curves, viewport)
+}
+
+/// Tauri command wrapping [`Session::set_latest_control_point`].
+///
+/// Runs asynchronously so that the moves sent while dragging a control point don't queue up behind each other,
+/// waiting for the session on a thread where blocking is allowed rather than on the async runtime.
+#[tauri::command]
+pub async fn set_control_point(
+    app: AppHandle,
+    seq: u64,
+    index: usize,
+    side: ControlPointSide,
+    value: ExtComplex,
+) -> Sequenced<SessionUpdate> {
+    // the move is recorded before waiting for the session, so that a move still holding it can see it is superseded
+    let sequence = app.state::<RequestSequence>();
+    if !sequence.begin(seq) {
+        return Sequenced::Superseded {
+            seq,
+            latest: sequence.latest(),
+        };
+    }
+    async_runtime::spawn_blocking(move || {
+        let registry = app.state::<CurveFamilyRegistry>();
+        let sequence = app.state::<RequestSequence>();
+        lock_session(&app.state())
+            .set_latest_control_point(&registry, &sequence, seq, index, side, value)
+    })
+    .await
+    .expect("moving a control point should not panic")
+}
+
+/// Tauri command wrapping [`Session::toggle_family`].
+#[tauri::command]
+pub fn toggle_family(
+    registry: State<'_, CurveFamilyRegistry>,
+    session: State<'_, Mutex<Session>>,
+    family: CurveFamilyRequest,
+    enabled: bool,
+) -> Result<SessionUpdate, Error> {
+    lock_session(&session).toggle_family(&registry, family, enabled)
+}
+
+/// Tauri command wrapping [`Session::set_tolerances`].
+#[tauri::command]
+pub fn set_tolerances(
+    registry: State<'_, CurveFamilyRegistry>,
+    session: State<'_, Mutex<Session>>,
+    tolerances: Tolerances,
+) -> Result<SessionUpdate, Error> {
+    lock_session(&session).set_tolerances(&registry, tolerances)
+}
+
+/// Tauri command wrapping [`Session::undo`].
+#[tauri::command]
+pub fn undo(
+    registry: State<'_, CurveFamilyRegistry>,
+    session: State<'_, Mutex<Session>>,
+) -> Result<SessionUpdate, Error> {
+    lock_session(&session).undo(&registry)
+}
+
+/// Tauri command wrapping [`Session::redo`].
+#[tauri::command]
+pub fn redo(
+    registry: State<'_, CurveFamilyRegistry>,
+    session: State<'_, Mutex<Session>>,
+) -> Result<SessionUpdate, Error> {
+    lock_session(&session).redo(&registry)
+}
+
+/// Tauri command wrapping [`Session::history`].
+#[tauri::command]
+pub fn history(session: State<'_, Mutex<Session>>) -> SessionHistory {
+    lock_session(&session).history()
+}
+
+/// Tauri command wrapping [`api::save_scene`].
+#[tauri::command]
+pub fn save_scene(session: State<'_, Mutex<Session>>, path: PathBuf) -> Result<(), Error> {
+    api::save_scene(&lock_session(&session), path)
+}
+
+/// Tauri command wrapping [`api::load_scene`].
+#[tauri::command]
+pub fn load_scene(path: PathBuf) -> Result<Scene, Error> {
+    api::load_scene(path)
+}
+
+/// Tauri command wrapping [`api::share_scene`].
+#[tauri::command]
+pub fn share_scene(session: State<'_, Mutex<Session>>) -> Result<String, Error> {
+    api::share_scene(&lock_session(&session))
+}
+
+/// Tauri command wrapping [`api::load_share_code`].
+#[tauri::command]
+pub fn load_share_code(code: &str) -> Result<Scene, Error> {
+    api::load_share_code(code)
+}
+
+/// Tauri command wrapping [`api::list_presets`].
+#[tauri::command]
+pub fn list_presets() -> Vec<Preset> {
+    api::list_presets()
+}
+
+/// Tauri command wrapping [`api::load_preset`].
+#[tauri::command]
+pub fn load_preset(key: &str) -> Result<Scene, Error> {
+    api::load_preset(key)
+}
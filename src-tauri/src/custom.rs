@@ -0,0 +1,322 @@
+//! Contains the loading of user-defined curve families from a file in the app config directory.
+//!
+//! The file is a JSON object with a list of `families`, each made up of a list of curve definitions, e.g.
+//! ```json
+//! {
+//!     "families": [
+//!         {
+//!             "name": "exercise_1",
+//!             "display_name": "Exercise 1",
+//!             "description": "Circles and lines to be mapped onto each other",
+//!             "style": { "color": "#d08770", "dashed": true },
+//!             "curves": [
+//!                 { "type": "circle", "center": [0, 0], "radius": 2 },
+//!                 { "type": "line", "point": [0, 1], "slope": [1, 0] },
+//!                 { "type": "pencil", "kind": "elliptic", "foci": [[-1, 0], [1, 0]], "count": 6 },
+//!                 { "type": "family", "name": "polar", "step": 0.5, "extent": 3 }
+//!             ]
+//!         }
+//!     ]
+//! }
+//! ```
+
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+use nalgebra::Matrix2;
+use serde::Deserialize;
+
+use super::*;
+
+/// Name of the file (inside the app config directory) which user-defined curve families are loaded from.
+pub const CUSTOM_FAMILIES_FILE: &str = "curve_families.json";
+
+/// A curve family defined by the user, consisting of a fixed set of curves.
+pub struct CustomFamily {
+    name: String,
+    display_name: String,
+    description: String,
+    style: CurveStyle,
+    curves: Vec<Matrix2<Complexf>>,
+    arcs: Vec<ThreePointArc>,
+}
+
+impl CurveFamily for CustomFamily {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn default_style(&self) -> CurveStyle {
+        self.style.clone()
+    }
+
+    fn generate(&self, _params: &FamilyParams) -> Vec<Matrix2<Complexf>> {
+        self.curves.clone()
+    }
+
+    fn generate_arcs(&self, _params: &FamilyParams) -> Vec<ThreePointArc> {
+        self.arcs.clone()
+    }
+}
+
+/// Schema of the file containing user-defined curve families.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CustomFamiliesFile {
+    families: Vec<FamilyDefinition>,
+}
+
+/// Schema of a single user-defined curve family. The display name defaults to the name if not provided.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FamilyDefinition {
+    name: String,
+    display_name: Option<String>,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    style: CurveStyle,
+    curves: Vec<CurveDefinition>,
+}
+
+/// Schema of an entry in a user-defined curve family, each of which may contribute several curves.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum CurveDefinition {
+    /// The circle `|z - center| = radius`.
+    Circle { center: Complexf, radius: f64 },
+    /// The line through `point` running parallel to `slope`.
+    Line { point: Complexf, slope: Complexf },
+    /// A pencil of circles determined by two foci, see [`PencilKind`].
+    /// Elliptic pencils have `count` circles, while hyperbolic pencils have `count` circles on either side of the foci's
+    /// perpendicular bisector, with `ratio` between consecutive circles.
+    Pencil {
+        kind: PencilKind,
+        foci: [ExtComplex; 2],
+        count: usize,
+        ratio: Option<f64>,
+    },
+    /// The curves of another family (built-in, or defined earlier in the file) generated with the given parameters.
+    Family {
+        name: String,
+        #[serde(flatten)]
+        params: FamilyParams,
+    },
+}
+
+/// The kinds of pencils of circles which can be defined by two foci.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum PencilKind {
+    /// Circles passing through both foci, see [`elliptic_pencil`].
+    Elliptic,
+    /// Circles separating the foci, see [`hyperbolic_pencil`].
+    Hyperbolic,
+}
+
+/// Ratio between consecutive circles of a hyperbolic pencil if not provided.
+const DEFAULT_PENCIL_RATIO: f64 = 2.0;
+
+/// An error encountered while loading user-defined curve families.
+#[derive(Debug)]
+pub enum CustomFamiliesError {
+    /// The file could not be read.
+    Io(io::Error),
+    /// The file is not valid JSON, or does not match the expected schema.
+    Parse(serde_json::Error),
+    /// More than one family in the file has the same name.
+    DuplicateName { name: String },
+    /// An entry of a family could not be turned into curves.
+    InvalidCurve {
+        family: String,
+        index: usize,
+        reason: String,
+    },
+}
+
+impl fmt::Display for CustomFamiliesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read {CUSTOM_FAMILIES_FILE}: {err}"),
+            Self::Parse(err) => write!(f, "failed to parse {CUSTOM_FAMILIES_FILE}: {err}"),
+            Self::DuplicateName { name } => {
+                write!(f, "family \"{name}\" is defined more than once")
+            }
+            Self::InvalidCurve {
+                family,
+                index,
+                reason,
+            } => write!(
+                f,
+                "curve {index} of family \"{family}\" is invalid: {reason}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CustomFamiliesError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Parse(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Load the user-defined curve families from a file and add them to the registry.
+///
+/// Families are added in the order they are defined, replacing any existing family with the same name.
+/// A missing file is treated as defining no families.
+/// If any family is invalid, none of the families are added.
+///
+/// Returns the number of families added.
+pub fn load_custom_families(
+    path: &Path,
+    registry: &mut CurveFamilyRegistry,
+) -> Result<usize, CustomFamiliesError> {
+    let json = match std::fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(err) => return Err(CustomFamiliesError::Io(err)),
+    };
+
+    // every family is validated before any are registered, so an invalid file leaves the registry unchanged
+    let families = parse_custom_families(&json, registry)?;
+    let count = families.len();
+    for family in families {
+        registry.register(family);
+    }
+    Ok(count)
+}
+
+/// Parse and validate the contents of a file containing user-defined curve families.
+///
+/// Entries of type `family` are resolved against the given registry, along with any families defined earlier in the file.
+pub fn parse_custom_families(
+    json: &str,
+    registry: &CurveFamilyRegistry,
+) -> Result<Vec<CustomFamily>, CustomFamiliesError> {
+    let file: CustomFamiliesFile =
+        serde_json::from_str(json).map_err(CustomFamiliesError::Parse)?;
+
+    let mut families: Vec<CustomFamily> = Vec::with_capacity(file.families.len());
+    for definition in file.families {
+        if families.iter().any(|f| f.name == definition.name) {
+            return Err(CustomFamiliesError::DuplicateName {
+                name: definition.name,
+            });
+        }
+
+        let mut family = CustomFamily {
+            display_name: definition
+                .display_name
+                .unwrap_or_else(|| definition.name.clone()),
+            name: definition.name,
+            description: definition.description,
+            style: definition.style,
+            curves: Vec::new(),
+            arcs: Vec::new(),
+        };
+        for (index, curve) in definition.curves.iter().enumerate() {
+            let lookup = |name: &str| {
+                families
+                    .iter()
+                    .rev()
+                    .map(|f| f as &dyn CurveFamily)
+                    .find(|f| f.name() == name)
+                    .or_else(|| registry.get(name))
+            };
+            add_curves(&mut family, curve, lookup).map_err(|reason| {
+                CustomFamiliesError::InvalidCurve {
+                    family: family.name.clone(),
+                    index,
+                    reason,
+                }
+            })?;
+        }
+        families.push(family);
+    }
+    Ok(families)
+}
+
+/// Internal helper used by [`parse_custom_families`].
+///
+/// Validates a single curve definition and adds its curves to the family,
+/// looking up any referenced family using the provided function.
+fn add_curves<'r>(
+    family: &mut CustomFamily,
+    curve: &CurveDefinition,
+    lookup: impl Fn(&str) -> Option<&'r dyn CurveFamily>,
+) -> Result<(), String> {
+    match *curve {
+        CurveDefinition::Circle { center, radius } => {
+            if !(center.is_finite() && radius > 0.0 && radius.is_finite()) {
+                return Err("circle must have a finite center and a positive radius".to_owned());
+            }
+            family
+                .curves
+                .push(curve_to_matrix(&Curve::Circle { center, radius }));
+        }
+        CurveDefinition::Line { point, slope } => {
+            if !point.is_finite() || !slope.is_finite() || slope == Complexf::ZERO {
+                return Err("line must have a finite point and a non-zero slope".to_owned());
+            }
+            family
+                .curves
+                .push(curve_to_matrix(&Curve::Line { point, slope }));
+        }
+        CurveDefinition::Pencil {
+            kind,
+            foci,
+            count,
+            ratio,
+        } => {
+            if foci[0] == foci[1] {
+                return Err("pencil must have distinct foci".to_owned());
+            }
+            if count > MAX_FAMILY_SIZE {
+                return Err(format!("pencil has more than {MAX_FAMILY_SIZE} circles"));
+            }
+            match kind {
+                PencilKind::Elliptic => family.curves.extend(elliptic_pencil(foci, count)),
+                PencilKind::Hyperbolic => {
+                    let ratio = ratio.unwrap_or(DEFAULT_PENCIL_RATIO);
+                    if !(ratio > 1.0 && ratio.is_finite()) {
+                        return Err("pencil ratio must be greater than 1".to_owned());
+                    }
+                    family.curves.extend(hyperbolic_pencil(foci, ratio, count));
+                }
+            }
+        }
+        CurveDefinition::Family {
+            ref name,
+            ref params,
+        } => {
+            let Some(referenced) = lookup(name) else {
+                return Err(format!("family \"{name}\" does not exist"));
+            };
+            let curves = referenced.generate(params);
+            let arcs = referenced.generate_arcs(params);
+            if curves.is_empty() && arcs.is_empty() {
+                return Err(format!("invalid parameters for family \"{name}\""));
+            }
+            family.curves.extend(curves);
+            family.arcs.extend(arcs);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "custom_tests.rs"]
+mod tests;
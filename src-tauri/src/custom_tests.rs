@@ -0,0 +1,210 @@
+use serde_json::json;
+
+use super::*;
+
+// parses a single family with the given curve definitions, which is expected to be valid
+fn parse_single(curves: serde_json::Value) -> CustomFamily {
+    let json = json!({ "families": [{ "name": "custom", "curves": curves }] }).to_string();
+    let mut families = parse_custom_families(&json, &CurveFamilyRegistry::default())
+        .expect("family should be valid");
+    assert_eq!(families.len(), 1);
+    families.pop().unwrap()
+}
+
+// parses a single family with the given curve definitions, which is expected to be invalid
+fn parse_single_err(curves: serde_json::Value) -> CustomFamiliesError {
+    let json = json!({ "families": [{ "name": "custom", "curves": curves }] }).to_string();
+    match parse_custom_families(&json, &CurveFamilyRegistry::default()) {
+        Ok(_) => panic!("family should be invalid"),
+        Err(err) => err,
+    }
+}
+
+#[test]
+fn parse_explicit_curves() {
+    let family = parse_single(json!([
+        { "type": "circle", "center": [1, 2], "radius": 3 },
+        { "type": "line", "point": [0, 1], "slope": [1, 0] }
+    ]));
+    let curves = family.generate(&FamilyParams::new());
+
+    assert_eq!(family.display_name(), "custom");
+    assert_eq!(
+        matrix_to_curve(&curves[0]),
+        Curve::Circle {
+            center: Complexf::new(1.0, 2.0),
+            radius: 3.0
+        }
+    );
+    match matrix_to_curve(&curves[1]) {
+        Curve::Line { point, slope } => {
+            approx::assert_abs_diff_eq!(point.im, 1.0, epsilon = 1e-9);
+            approx::assert_abs_diff_eq!(slope.im, 0.0, epsilon = 1e-9);
+        }
+        curve => panic!("expected a line, got {curve:?}"),
+    }
+}
+
+#[test]
+fn parse_pencils() {
+    let family = parse_single(json!([
+        { "type": "pencil", "kind": "elliptic", "foci": [[-1, 0], [1, 0]], "count": 4 },
+        { "type": "pencil", "kind": "hyperbolic", "foci": [[-1, 0], "inf"], "count": 2, "ratio": 3 }
+    ]));
+    let curves = family.generate(&FamilyParams::new());
+
+    assert_eq!(curves.len(), 4 + 5);
+    // the hyperbolic pencil is made up of circles centered at the finite focus
+    let (center, radius) = match matrix_to_curve(&curves[8]) {
+        Curve::Circle { center, radius } => (center, radius),
+        curve => panic!("expected a circle, got {curve:?}"),
+    };
+    approx::assert_abs_diff_eq!(center, Complexf::new(-1.0, 0.0), epsilon = 1e-9);
+    approx::assert_relative_eq!(radius, 9.0, epsilon = 1e-9);
+}
+
+#[test]
+fn parse_family_references() {
+    let json = json!({
+        "families": [
+            {
+                "name": "grid",
+                "display_name": "Coarse grid",
+                "style": { "line_width": 2 },
+                "curves": [{ "type": "family", "name": "xy", "step": 2, "extent": 4 }]
+            },
+            {
+                "name": "grid_and_tiling",
+                "curves": [
+                    { "type": "family", "name": "grid" },
+                    { "type": "family", "name": "tiling_disk", "depth": 1 }
+                ]
+            }
+        ]
+    })
+    .to_string();
+    let families = parse_custom_families(&json, &CurveFamilyRegistry::default()).unwrap();
+
+    assert_eq!(families[0].display_name(), "Coarse grid");
+    assert_eq!(families[0].default_style().line_width, 2.0);
+    assert_eq!(families[1].default_style(), CurveStyle::default());
+    assert_eq!(families[0].generate(&FamilyParams::new()).len(), 2 * 5);
+    assert_eq!(families[1].generate(&FamilyParams::new()).len(), 2 * 5);
+    assert!(!families[1].generate_arcs(&FamilyParams::new()).is_empty());
+}
+
+#[test]
+fn parse_invalid_curves() {
+    for (curves, expected_index) in [
+        (
+            json!([{ "type": "circle", "center": [0, 0], "radius": -1 }]),
+            0,
+        ),
+        (
+            json!([
+                { "type": "circle", "center": [0, 0], "radius": 1 },
+                { "type": "line", "point": [0, 0], "slope": [0, 0] }
+            ]),
+            1,
+        ),
+        (
+            json!([{ "type": "pencil", "kind": "elliptic", "foci": ["inf", "inf"], "count": 3 }]),
+            0,
+        ),
+        (
+            json!([{ "type": "pencil", "kind": "hyperbolic", "foci": [[0, 0], [1, 0]], "count": 3, "ratio": 0.5 }]),
+            0,
+        ),
+        (json!([{ "type": "family", "name": "unknown" }]), 0),
+        (json!([{ "type": "family", "name": "xy", "step": -1 }]), 0),
+        (
+            json!([
+                { "type": "family", "name": "polar" },
+                { "type": "pencil", "kind": "elliptic", "foci": [[0, 0], [1, 0]], "count": 1001 }
+            ]),
+            1,
+        ),
+    ] {
+        match parse_single_err(curves) {
+            CustomFamiliesError::InvalidCurve { family, index, .. } => {
+                assert_eq!(family, "custom");
+                assert_eq!(index, expected_index);
+            }
+            err => panic!("expected an invalid curve, got {err:?}"),
+        }
+    }
+}
+
+#[test]
+fn parse_invalid_schema() {
+    for json in [
+        "not json",
+        r#"{ "families": [{ "name": "custom" }] }"#,
+        r#"{ "families": [{ "name": "custom", "curves": [{ "type": "ellipse" }] }] }"#,
+        r#"{ "families": [], "extra": true }"#,
+    ] {
+        assert!(matches!(
+            parse_custom_families(json, &CurveFamilyRegistry::default()),
+            Err(CustomFamiliesError::Parse(_))
+        ));
+    }
+}
+
+#[test]
+fn parse_duplicate_names() {
+    let json = json!({
+        "families": [
+            { "name": "custom", "curves": [] },
+            { "name": "custom", "curves": [] }
+        ]
+    })
+    .to_string();
+
+    assert!(matches!(
+        parse_custom_families(&json, &CurveFamilyRegistry::default()),
+        Err(CustomFamiliesError::DuplicateName { name }) if name == "custom"
+    ));
+}
+
+#[test]
+fn load_custom_families_from_file() {
+    let mut registry = CurveFamilyRegistry::default();
+    let count = registry.iter().count();
+
+    // a missing file doesn't define any families
+    let path = std::env::temp_dir().join("mobius_app_load_custom_families.json");
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(load_custom_families(&path, &mut registry).unwrap(), 0);
+
+    // user-defined families are added after the built-in families, and can replace them
+    let json = json!({
+        "families": [
+            { "name": "xy", "display_name": "Unit square", "curves": [{ "type": "family", "name": "xy", "extent": 1 }] },
+            { "name": "custom", "curves": [{ "type": "circle", "center": [0, 0], "radius": 1 }] }
+        ]
+    });
+    std::fs::write(&path, json.to_string()).unwrap();
+    let result = load_custom_families(&path, &mut registry);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(result.unwrap(), 2);
+    assert_eq!(registry.iter().count(), count + 1);
+    assert_eq!(registry.get("xy").unwrap().display_name(), "Unit square");
+    assert_eq!(
+        registry
+            .get("xy")
+            .unwrap()
+            .generate(&FamilyParams::new())
+            .len(),
+        2 * 3
+    );
+    assert!(registry.get("custom").is_some());
+}
+
+#[test]
+fn parse_example_file() {
+    let json = include_str!("../../examples/curve_families.json");
+    let families = parse_custom_families(json, &CurveFamilyRegistry::default())
+        .expect("example file should be valid");
+    assert_eq!(families.len(), 2);
+}
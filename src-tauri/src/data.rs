@@ -1,20 +1,300 @@
 //! Contains definitions of the standard curve sets (i.e. gridlines) to be transformed and displayed.
 
 use nalgebra::Matrix2;
-use std::f64::consts::SQRT_2;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
 
 use super::*;
 
-pub fn lookup_curve_family(name: &str) -> Option<&'static [Matrix2<Complexf>]> {
-    CURVE_FAMILY_MAPPING.get(name).copied()
+/// Registers the built-in curve families, in the order they are listed in the frontend.
+pub fn register_builtin_families(registry: &mut CurveFamilyRegistry) {
+    registry.register(XyGridlinesFamily);
+    registry.register(PolarGridlinesFamily);
+    registry.register(ApollonianFamily);
+    registry.register(SteinerChainFamily);
+    for model in [HyperbolicModel::Disk, HyperbolicModel::HalfPlane] {
+        registry.register(GeodesicFamily { model });
+    }
+    for model in [HyperbolicModel::Disk, HyperbolicModel::HalfPlane] {
+        registry.register(TilingFamily { model });
+    }
 }
 
-/// Lookup table mapping a name to the family of curves it represents
-const CURVE_FAMILY_MAPPING: phf::Map<&'static str, &'static [Matrix2<Complexf>]> = phf::phf_map! {
-    "xy" => XY_GRIDLINES,
-    "polar" => POLAR_GRIDLINES,
-    "apollo" => APOLLONIAN_CIRCLES,
-};
+/// A family consisting of a fixed set of curves, which doesn't take any parameters.
+pub struct StaticFamily {
+    pub name: &'static str,
+    pub display_name: &'static str,
+    pub curves: &'static [Matrix2<Complexf>],
+}
+
+impl CurveFamily for StaticFamily {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn display_name(&self) -> &str {
+        self.display_name
+    }
+
+    fn generate(&self, _params: &FamilyParams) -> Vec<Matrix2<Complexf>> {
+        self.curves.to_vec()
+    }
+}
+
+/// The family of curves in a Steiner chain, generated by [`steiner_chain`].
+pub struct SteinerChainFamily;
+
+/// Parameters of the [`SteinerChainFamily`], see [`steiner_chain`] for details.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct SteinerChainParams {
+    n: usize,
+    radius: f64,
+    offset: f64,
+    rotation: f64,
+}
+
+impl Default for SteinerChainParams {
+    fn default() -> Self {
+        SteinerChainParams {
+            n: 6,
+            radius: 15.0,
+            offset: 0.4,
+            rotation: 0.0,
+        }
+    }
+}
+
+impl CurveFamily for SteinerChainFamily {
+    fn name(&self) -> &str {
+        "steiner"
+    }
+
+    fn display_name(&self) -> &str {
+        "Steiner chain"
+    }
+
+    fn description(&self) -> &str {
+        "A closed chain of circles, each tangent to its neighbors and to two non-concentric boundary circles."
+    }
+
+    fn params(&self) -> Vec<ParamSchema> {
+        param_schemas::<SteinerChainParams>(&[
+            (
+                "n",
+                ParamKind::Integer,
+                "Number of circles in the chain, at least 3",
+            ),
+            (
+                "radius",
+                ParamKind::Number,
+                "Radius of the outer boundary circle",
+            ),
+            (
+                "offset",
+                ParamKind::Number,
+                "How far to push the inner boundary circle off center, between -1 and 1",
+            ),
+            (
+                "rotation",
+                ParamKind::Number,
+                "Angle (in radians) to roll the chain around the boundary circles",
+            ),
+        ])
+    }
+
+    fn default_style(&self) -> CurveStyle {
+        CurveStyle {
+            line_width: 1.5,
+            ..Default::default()
+        }
+    }
+
+    fn generate(&self, params: &FamilyParams) -> Vec<Matrix2<Complexf>> {
+        match parse_params::<SteinerChainParams>(params) {
+            Some(p)
+                if (3..=MAX_FAMILY_SIZE).contains(&p.n)
+                    && p.radius > 0.0
+                    && p.radius.is_finite()
+                    && p.offset.abs() < 1.0
+                    && p.rotation.is_finite() =>
+            {
+                steiner_chain(p.n, p.radius, p.offset, p.rotation)
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// The family of geodesic gridlines of a hyperbolic model, generated by [`HyperbolicModel::geodesic_gridlines`].
+pub struct GeodesicFamily {
+    pub model: HyperbolicModel,
+}
+
+/// Parameters of the [`GeodesicFamily`], see [`HyperbolicModel::geodesic_gridlines`] for details.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct GeodesicParams {
+    spacing: f64,
+    count: usize,
+}
+
+impl Default for GeodesicParams {
+    fn default() -> Self {
+        GeodesicParams {
+            spacing: GEODESIC_SPACING,
+            count: GEODESIC_COUNT,
+        }
+    }
+}
+
+impl CurveFamily for GeodesicFamily {
+    fn name(&self) -> &str {
+        match self.model {
+            HyperbolicModel::Disk => "geodesic_disk",
+            HyperbolicModel::HalfPlane => "geodesic_half_plane",
+        }
+    }
+
+    fn display_name(&self) -> &str {
+        match self.model {
+            HyperbolicModel::Disk => "Geodesics (Poincaré disk)",
+            HyperbolicModel::HalfPlane => "Geodesics (upper half-plane)",
+        }
+    }
+
+    fn description(&self) -> &str {
+        match self.model {
+            HyperbolicModel::Disk => {
+                "Geodesics of the Poincaré disk, forming a grid around its center."
+            }
+            HyperbolicModel::HalfPlane => {
+                "Geodesics of the upper half-plane, forming a grid around the point i."
+            }
+        }
+    }
+
+    fn params(&self) -> Vec<ParamSchema> {
+        param_schemas::<GeodesicParams>(&[
+            (
+                "spacing",
+                ParamKind::Number,
+                "Hyperbolic distance between consecutive geodesics",
+            ),
+            (
+                "count",
+                ParamKind::Integer,
+                "Number of geodesics on each side of the center",
+            ),
+        ])
+    }
+
+    fn generate(&self, params: &FamilyParams) -> Vec<Matrix2<Complexf>> {
+        match parse_params::<GeodesicParams>(params) {
+            Some(p) if p.spacing > 0.0 && p.spacing.is_finite() && p.count <= MAX_FAMILY_SIZE => {
+                self.model.geodesic_gridlines(p.spacing, p.count)
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// The family of edges of a regular tiling of a hyperbolic model, generated by [`HyperbolicModel::regular_tiling`].
+pub struct TilingFamily {
+    pub model: HyperbolicModel,
+}
+
+/// Parameters of the [`TilingFamily`], see [`HyperbolicModel::regular_tiling`] for details.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct TilingParams {
+    p: usize,
+    q: usize,
+    depth: usize,
+}
+
+impl Default for TilingParams {
+    fn default() -> Self {
+        TilingParams {
+            p: 7,
+            q: 3,
+            depth: 4,
+        }
+    }
+}
+
+impl CurveFamily for TilingFamily {
+    fn name(&self) -> &str {
+        match self.model {
+            HyperbolicModel::Disk => "tiling_disk",
+            HyperbolicModel::HalfPlane => "tiling_half_plane",
+        }
+    }
+
+    fn display_name(&self) -> &str {
+        match self.model {
+            HyperbolicModel::Disk => "Regular tiling (Poincaré disk)",
+            HyperbolicModel::HalfPlane => "Regular tiling (upper half-plane)",
+        }
+    }
+
+    fn description(&self) -> &str {
+        "The edges of a regular tiling of the hyperbolic plane by p-gons, q of which meet at each vertex."
+    }
+
+    fn params(&self) -> Vec<ParamSchema> {
+        param_schemas::<TilingParams>(&[
+            ("p", ParamKind::Integer, "Number of sides of each polygon"),
+            (
+                "q",
+                ParamKind::Integer,
+                "Number of polygons meeting at each vertex",
+            ),
+            (
+                "depth",
+                ParamKind::Integer,
+                "Number of layers of polygons around the central polygon",
+            ),
+        ])
+    }
+
+    fn default_style(&self) -> CurveStyle {
+        CurveStyle {
+            line_width: 1.5,
+            ..Default::default()
+        }
+    }
+
+    fn generate(&self, _params: &FamilyParams) -> Vec<Matrix2<Complexf>> {
+        Vec::new()
+    }
+
+    fn generate_arcs(&self, params: &FamilyParams) -> Vec<ThreePointArc> {
+        match parse_params::<TilingParams>(params) {
+            // each polygon has p neighbors, so p^depth bounds the number of polygons in the outermost layer,
+            // while p * q bounds the size of the central polygon and its vertices regardless of the depth;
+            // tilings which aren't hyperbolic are rejected by `regular_tiling` itself
+            Some(t)
+                if t.p
+                    .checked_mul(t.q)
+                    .is_some_and(|pq| pq <= MAX_TILING_POLYGONS)
+                    && (t.p as f64).powf(t.depth as f64) <= MAX_TILING_POLYGONS as f64 =>
+            {
+                self.model
+                    .regular_tiling(t.p, t.q, t.depth)
+                    .unwrap_or_default()
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Limit on the number of curves the parameters of a family can request, to stop a single request from exhausting memory.
+pub(crate) const MAX_FAMILY_SIZE: usize = 1000;
+
+/// Limit on the number of polygons the parameters of a [`TilingFamily`] can request,
+/// which grows exponentially with the depth of the tiling. Also caps `p * q`, which grows with the size of each polygon.
+const MAX_TILING_POLYGONS: usize = 100_000;
 
 /// Generates the 2x2 matrix representation of the line equation `x = c`
 macro_rules! y_axis_gridline {
@@ -40,96 +320,83 @@ macro_rules! x_axis_gridline {
     }};
 }
 
-const XY_GRIDLINES: &[Matrix2<Complexf>] = &[
-    /* y-axis lines */
-    y_axis_gridline!(-20.0),
-    y_axis_gridline!(-19.0),
-    y_axis_gridline!(-18.0),
-    y_axis_gridline!(-17.0),
-    y_axis_gridline!(-16.0),
-    y_axis_gridline!(-15.0),
-    y_axis_gridline!(-14.0),
-    y_axis_gridline!(-13.0),
-    y_axis_gridline!(-12.0),
-    y_axis_gridline!(-11.0),
-    y_axis_gridline!(-10.0),
-    y_axis_gridline!(-9.0),
-    y_axis_gridline!(-8.0),
-    y_axis_gridline!(-7.0),
-    y_axis_gridline!(-6.0),
-    y_axis_gridline!(-5.0),
-    y_axis_gridline!(-4.0),
-    y_axis_gridline!(-3.0),
-    y_axis_gridline!(-2.0),
-    y_axis_gridline!(-1.0),
-    y_axis_gridline!(0.0),
-    y_axis_gridline!(1.0),
-    y_axis_gridline!(2.0),
-    y_axis_gridline!(3.0),
-    y_axis_gridline!(4.0),
-    y_axis_gridline!(5.0),
-    y_axis_gridline!(6.0),
-    y_axis_gridline!(7.0),
-    y_axis_gridline!(8.0),
-    y_axis_gridline!(9.0),
-    y_axis_gridline!(10.0),
-    y_axis_gridline!(11.0),
-    y_axis_gridline!(12.0),
-    y_axis_gridline!(13.0),
-    y_axis_gridline!(14.0),
-    y_axis_gridline!(15.0),
-    y_axis_gridline!(16.0),
-    y_axis_gridline!(17.0),
-    y_axis_gridline!(18.0),
-    y_axis_gridline!(19.0),
-    y_axis_gridline!(20.0),
-    /* x-axis lines */
-    x_axis_gridline!(-20.0),
-    x_axis_gridline!(-19.0),
-    x_axis_gridline!(-18.0),
-    x_axis_gridline!(-17.0),
-    x_axis_gridline!(-16.0),
-    x_axis_gridline!(-15.0),
-    x_axis_gridline!(-14.0),
-    x_axis_gridline!(-13.0),
-    x_axis_gridline!(-12.0),
-    x_axis_gridline!(-11.0),
-    x_axis_gridline!(-10.0),
-    x_axis_gridline!(-9.0),
-    x_axis_gridline!(-8.0),
-    x_axis_gridline!(-7.0),
-    x_axis_gridline!(-6.0),
-    x_axis_gridline!(-5.0),
-    x_axis_gridline!(-4.0),
-    x_axis_gridline!(-3.0),
-    x_axis_gridline!(-2.0),
-    x_axis_gridline!(-1.0),
-    x_axis_gridline!(0.0),
-    x_axis_gridline!(1.0),
-    x_axis_gridline!(2.0),
-    x_axis_gridline!(3.0),
-    x_axis_gridline!(4.0),
-    x_axis_gridline!(5.0),
-    x_axis_gridline!(6.0),
-    x_axis_gridline!(7.0),
-    x_axis_gridline!(8.0),
-    x_axis_gridline!(9.0),
-    x_axis_gridline!(10.0),
-    x_axis_gridline!(11.0),
-    x_axis_gridline!(12.0),
-    x_axis_gridline!(13.0),
-    x_axis_gridline!(14.0),
-    x_axis_gridline!(15.0),
-    x_axis_gridline!(16.0),
-    x_axis_gridline!(17.0),
-    x_axis_gridline!(18.0),
-    x_axis_gridline!(19.0),
-    x_axis_gridline!(20.0),
-];
-
-/// sqrt(3) made available as a max precision constant for use in const trigonometric expressions
-/// The constant provided in [`std::f64::consts`] is still experimental
-const SQRT_3: f64 = 1.7320508075688771931766041234368458390235900878906250f64;
+/// The family of Cartesian gridlines, i.e. the lines `x = c` and `y = c` for evenly spaced values of `c` around 0.
+pub struct XyGridlinesFamily;
+
+/// Parameters of the [`XyGridlinesFamily`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct XyGridlinesParams {
+    /// spacing between consecutive gridlines
+    step: f64,
+    /// gridlines are generated up to this distance away from the axes
+    extent: f64,
+}
+
+impl Default for XyGridlinesParams {
+    fn default() -> Self {
+        XyGridlinesParams {
+            step: 1.0,
+            extent: 20.0,
+        }
+    }
+}
+
+impl CurveFamily for XyGridlinesFamily {
+    fn name(&self) -> &str {
+        "xy"
+    }
+
+    fn display_name(&self) -> &str {
+        "Cartesian gridlines"
+    }
+
+    fn description(&self) -> &str {
+        "Evenly spaced horizontal and vertical lines."
+    }
+
+    fn params(&self) -> Vec<ParamSchema> {
+        param_schemas::<XyGridlinesParams>(&[
+            (
+                "step",
+                ParamKind::Number,
+                "Spacing between consecutive gridlines",
+            ),
+            (
+                "extent",
+                ParamKind::Number,
+                "Distance from the axes up to which gridlines are generated",
+            ),
+        ])
+    }
+
+    fn generate(&self, params: &FamilyParams) -> Vec<Matrix2<Complexf>> {
+        let Some((step, n)) = parse_params::<XyGridlinesParams>(params)
+            .and_then(|p| Some((p.step, gridline_count(p.step, p.extent)?)))
+        else {
+            return Vec::new();
+        };
+
+        let n = n as i64;
+        let coords = (-n..=n).map(|k| k as f64 * step);
+        coords
+            .clone()
+            .map(|x| y_axis_gridline!(x))
+            .chain(coords.map(|y| x_axis_gridline!(y)))
+            .collect()
+    }
+}
+
+/// Computes the number of gridlines spaced `step` apart which fit within `extent` (excluding the one at 0),
+/// or returns nothing if the parameters are invalid.
+fn gridline_count(step: f64, extent: f64) -> Option<usize> {
+    if !(step > 0.0 && extent >= 0.0 && step.is_finite() && extent.is_finite()) {
+        return None;
+    }
+    // allow for floating point error when the extent is a multiple of the step
+    let count = (extent / step + 1e-9).floor();
+    (count <= MAX_FAMILY_SIZE as f64).then_some(count as usize)
+}
 
 /// Generates the 2x2 matrix representation of the line `run * y = rise * x`
 macro_rules! radial_line {
@@ -162,135 +429,277 @@ macro_rules! centered_circle {
     };
 }
 
-const POLAR_GRIDLINES: &[Matrix2<Complexf>] = &[
-    /* radial lines */
-    radial_line!(rise = -SQRT_3, run = 1.0), // theta = -2pi/3
-    radial_line!(rise = -1.0, run = SQRT_3), // theta = -pi/3
-    radial_line!(rise = 0.0, run = 1.0),     // theta = 0
-    radial_line!(rise = 1.0, run = SQRT_3),  // theta = pi/3
-    radial_line!(rise = SQRT_3, run = 1.0),  // theta = 2pi/3
-    radial_line!(rise = 1.0, run = 0.0),     // theta = pi
-    /* circles centered at the origin */
-    centered_circle!(1.0),
-    centered_circle!(2.0),
-    centered_circle!(3.0),
-    centered_circle!(4.0),
-    centered_circle!(5.0),
-    centered_circle!(6.0),
-    centered_circle!(7.0),
-    centered_circle!(8.0),
-    centered_circle!(9.0),
-    centered_circle!(10.0),
-    centered_circle!(11.0),
-    centered_circle!(12.0),
-    centered_circle!(13.0),
-    centered_circle!(14.0),
-    centered_circle!(15.0),
-    centered_circle!(16.0),
-    centered_circle!(17.0),
-    centered_circle!(18.0),
-    centered_circle!(19.0),
-    centered_circle!(20.0),
-];
-
-/// 4th root of 2 made available as a max precision constant for use in the geometric mean to interpolate between apollonian circles
-const SQRT_SQRT_2: f64 = 1.18920711500272102689734765590401366353034973144531250f64;
-
-/// Generates the 2x2 matrix representation of the circles `|z - 5| = p|z + 5|` and `|z + 5| = p|z - 5|`
-macro_rules! apollonian_circle_pair {
-    ($p:expr, left) => {{
-        Matrix2::new(
-            Complexf::ONE,
-            Complexf::new(-5.0, 0.0),
-            Complexf::new($p, 0.0),
-            Complexf::new($p * 5.0, 0.0),
-        )
-    }};
-    ($p:expr, right) => {{
-        Matrix2::new(
-            Complexf::ONE,
-            Complexf::new(5.0, 0.0),
-            Complexf::new($p, 0.0),
-            Complexf::new($p * -5.0, 0.0),
+/// The family of polar gridlines, i.e. lines through the origin at evenly spaced angles,
+/// and circles centered at the origin with evenly spaced radii.
+pub struct PolarGridlinesFamily;
+
+/// Parameters of the [`PolarGridlinesFamily`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct PolarGridlinesParams {
+    /// spacing between the radii of consecutive circles
+    step: f64,
+    /// circles are generated up to this radius
+    extent: f64,
+    /// number of lines through the origin, which divide the plane into twice as many sectors
+    divisions: usize,
+}
+
+impl Default for PolarGridlinesParams {
+    fn default() -> Self {
+        PolarGridlinesParams {
+            step: 1.0,
+            extent: 20.0,
+            divisions: 6,
+        }
+    }
+}
+
+impl CurveFamily for PolarGridlinesFamily {
+    fn name(&self) -> &str {
+        "polar"
+    }
+
+    fn display_name(&self) -> &str {
+        "Polar gridlines"
+    }
+
+    fn description(&self) -> &str {
+        "Lines through the origin at evenly spaced angles, and circles around the origin with evenly spaced radii."
+    }
+
+    fn params(&self) -> Vec<ParamSchema> {
+        param_schemas::<PolarGridlinesParams>(&[
+            (
+                "step",
+                ParamKind::Number,
+                "Spacing between the radii of consecutive circles",
+            ),
+            (
+                "extent",
+                ParamKind::Number,
+                "Radius up to which circles are generated",
+            ),
+            (
+                "divisions",
+                ParamKind::Integer,
+                "Number of lines through the origin",
+            ),
+        ])
+    }
+
+    fn generate(&self, params: &FamilyParams) -> Vec<Matrix2<Complexf>> {
+        let Some((p, n)) = parse_params::<PolarGridlinesParams>(params).and_then(|p| {
+            let n = gridline_count(p.step, p.extent)?;
+            (p.divisions <= MAX_FAMILY_SIZE).then_some((p, n))
+        }) else {
+            return Vec::new();
+        };
+
+        let radial_lines = (0..p.divisions).map(|k| {
+            let (sin, cos) = (PI * k as f64 / p.divisions as f64).sin_cos();
+            radial_line!(rise = sin, run = cos)
+        });
+        let circles = (1..=n).map(|k| centered_circle!(k as f64 * p.step));
+        radial_lines.chain(circles).collect()
+    }
+}
+
+/// The family of circles making up the bipolar coordinate system of two foci, generated by [`apollonian_circles`].
+pub struct ApollonianFamily;
+
+/// Parameters of the [`ApollonianFamily`], see [`apollonian_circles`] for details.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct ApollonianParams {
+    foci: [ExtComplex; 2],
+    ratio: f64,
+    count: usize,
+    divisions: usize,
+}
+
+impl Default for ApollonianParams {
+    fn default() -> Self {
+        ApollonianParams {
+            foci: [ExtComplex::new(-5.0, 0.0), ExtComplex::new(5.0, 0.0)],
+            ratio: 2f64.powf(0.25),
+            count: 20,
+            divisions: 24,
+        }
+    }
+}
+
+impl CurveFamily for ApollonianFamily {
+    fn name(&self) -> &str {
+        "apollo"
+    }
+
+    fn display_name(&self) -> &str {
+        "Apollonian circles"
+    }
+
+    fn description(&self) -> &str {
+        "The bipolar coordinate system of two foci, made up of the circles separating the foci and the circles through both foci."
+    }
+
+    fn params(&self) -> Vec<ParamSchema> {
+        param_schemas::<ApollonianParams>(&[
+            ("foci", ParamKind::Points, "The two foci, e.g. the fixed points of the transformation"),
+            ("ratio", ParamKind::Number, "Ratio between the distance ratios of consecutive circles separating the foci, greater than 1"),
+            ("count", ParamKind::Integer, "Number of circles separating the foci on each side of their perpendicular bisector"),
+            ("divisions", ParamKind::Integer, "Number of circles passing through both foci"),
+        ])
+    }
+
+    fn generate(&self, params: &FamilyParams) -> Vec<Matrix2<Complexf>> {
+        match parse_params::<ApollonianParams>(params) {
+            // there are 2 * count + 1 + divisions circles, which is checked without overflowing
+            Some(p)
+                if p.foci[0] != p.foci[1]
+                    && p.ratio > 1.0
+                    && p.ratio.is_finite()
+                    && p.count
+                        .checked_mul(2)
+                        .and_then(|n| n.checked_add(p.divisions))
+                        .and_then(|n| n.checked_add(1))
+                        .is_some_and(|n| n <= MAX_FAMILY_SIZE) =>
+            {
+                apollonian_circles(p.foci, p.ratio, p.count, p.divisions)
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Generates the 2x2 matrix representations of the circles making up the bipolar coordinate system of two foci `a` and `b`.
+///
+/// The system consists of two orthogonal sets of circles:
+///  - the Apollonian circles `|z - a| = p|z - b|` separating the foci (see [`hyperbolic_pencil`])
+///  - the circles passing through both foci (see [`elliptic_pencil`])
+///
+/// Either focus may be infinite, in which case the system degenerates into polar gridlines centered at the other focus.
+///
+/// # Params
+///  - `foci`: the two foci `a` and `b`, which must be distinct
+///  - `ratio`: the ratio between consecutive values of `p`, greater than 1
+///  - `count`: number of Apollonian circles on each side of the perpendicular bisector `p = 1`
+///  - `divisions`: number of circles passing through both foci
+pub fn apollonian_circles(
+    foci: [ExtComplex; 2],
+    ratio: f64,
+    count: usize,
+    divisions: usize,
+) -> Vec<Matrix2<Complexf>> {
+    let mut curves = hyperbolic_pencil(foci, ratio, count);
+    curves.extend(elliptic_pencil(foci, divisions));
+    curves
+}
+
+/// Generates the 2x2 matrix representations of the Apollonian circles `|z - a| = p|z - b|` separating two foci `a` and `b`,
+/// where the ratios `p` are spaced geometrically, i.e. `p = ratio^k` for `k = -count, ..., count`.
+///
+/// Either focus may be infinite, in which case the circles are concentric around the other focus.
+pub fn hyperbolic_pencil(
+    foci: [ExtComplex; 2],
+    ratio: f64,
+    count: usize,
+) -> Vec<Matrix2<Complexf>> {
+    assert!(ratio > 1.0, "ratio must be greater than 1");
+
+    let tform = bipolar_tform(foci);
+    let count = count as i32;
+    (-count..=count)
+        .map(|k| {
+            let p = ratio.powi(k);
+            Matrix2::new(
+                Complexf::ONE,
+                Complexf::ZERO,
+                Complexf::ZERO,
+                Complexf::from(p),
+            ) * tform
+        })
+        .collect()
+}
+
+/// Generates the 2x2 matrix representations of `count` circles passing through two foci,
+/// which meet the foci at evenly spaced angles, starting with the line through both foci.
+///
+/// Either focus may be infinite, in which case the circles are lines through the other focus.
+pub fn elliptic_pencil(foci: [ExtComplex; 2], count: usize) -> Vec<Matrix2<Complexf>> {
+    let tform = bipolar_tform(foci);
+    (0..count)
+        .map(|k| {
+            // the line through the origin at angle t consists of the points equidistant from +/- i * e^(it)
+            let normal = Complexf::from_polar(1.0, PI * k as f64 / count as f64) * Complexf::I;
+            Matrix2::new(Complexf::ONE, -normal, Complexf::ONE, normal) * tform
+        })
+        .collect()
+}
+
+/// Internal helper used to generate pencils of circles.
+///
+/// Computes the transformation `w = (z - a) / (z - b)` mapping the foci `a` and `b` to 0 and infinity,
+/// which maps the Apollonian circles to the circles `|w| = p`, and the circles through the foci to lines through the origin.
+/// Curves are pulled back through the transformation by multiplying with it on the right (see [`generate_mobius_transformation`]).
+fn bipolar_tform(foci: [ExtComplex; 2]) -> Matrix2<Complexf> {
+    assert!(foci[0] != foci[1], "the foci must be distinct");
+
+    let row = |focus: ExtComplex| match focus {
+        ExtComplex::Val(v) => (Complexf::ONE, -v),
+        ExtComplex::Inf => (Complexf::ZERO, Complexf::ONE),
+    };
+    let (a_0, a_1) = row(foci[0]);
+    let (b_0, b_1) = row(foci[1]);
+    Matrix2::new(a_0, a_1, b_0, b_1)
+}
+
+/// Generates the 2x2 matrix representations of a Steiner chain, along with its two boundary circles.
+///
+/// A Steiner chain is a closed chain of `n` circles, each tangent to its two neighbors,
+/// which lie between two non-intersecting boundary circles and are tangent to both.
+/// The chain is first constructed between two concentric circles,
+/// then mapped through a Mobius transformation (preserving the outer boundary circle) so that the boundary circles become non-concentric.
+///
+/// # Params
+///  - `n`: number of circles in the chain, at least 3
+///  - `radius`: radius of the outer boundary circle, which is centered at the origin
+///  - `offset`: how far to push the inner boundary circle away from the center, in the range `(-1, 1)`
+///  - `rotation`: angle (in radians) to rotate the chain by in the concentric configuration, which rolls the chain around the boundary circles
+pub fn steiner_chain(n: usize, radius: f64, offset: f64, rotation: f64) -> Vec<Matrix2<Complexf>> {
+    assert!(n >= 3, "a Steiner chain requires at least 3 circles");
+    assert!(
+        offset.abs() < 1.0,
+        "offset must lie strictly between -1 and 1"
+    );
+
+    // construct the concentric configuration, where the outer boundary is the unit circle
+    let sin_half_angle = (PI / n as f64).sin();
+    let inner_radius = (1.0 - sin_half_angle) / (1.0 + sin_half_angle);
+    let chain_radius = 0.5 * (1.0 - inner_radius);
+    let chain_distance = 0.5 * (1.0 + inner_radius);
+
+    let mut curves = vec![centered_circle!(1.0), centered_circle!(inner_radius)];
+    curves.extend((0..n).map(|k| {
+        let angle = rotation + 2.0 * PI * k as f64 / n as f64;
+        let (sin, cos) = angle.sin_cos();
+        circle!(
+            c = [chain_distance * cos, chain_distance * sin],
+            r = chain_radius
         )
-    }};
+    }));
+
+    // the transformation z -> (z + offset) / (offset * z + 1) maps the unit circle to itself,
+    // and is then scaled by the radius of the outer boundary;
+    // curves are transformed by multiplying with the inverse (see `generate_mobius_transformation`), computed via the adjugate
+    let inv_tform = Matrix2::new(
+        Complexf::ONE,
+        Complexf::from(-radius * offset),
+        Complexf::from(-offset),
+        Complexf::from(radius),
+    );
+    curves.iter().map(|m| m * inv_tform).collect()
 }
 
-#[rustfmt::skip]
-const APOLLONIAN_CIRCLES: &[Matrix2<Complexf>] = &[
-    /* circle pairs running along the x-axis and converging around -5.0 and 5.0 */
-    apollonian_circle_pair!(1.0, left),
-    apollonian_circle_pair!(1.0 * SQRT_SQRT_2, left),
-    apollonian_circle_pair!(SQRT_2, left),
-    apollonian_circle_pair!(SQRT_2 * SQRT_SQRT_2, left),
-    apollonian_circle_pair!(2.0, left),
-    apollonian_circle_pair!(2.0 * SQRT_SQRT_2, left),
-    apollonian_circle_pair!(2.0 * SQRT_2, left),
-    apollonian_circle_pair!(4.0, left),
-    apollonian_circle_pair!(4.0 * SQRT_2, left),
-    apollonian_circle_pair!(8.0, left),
-    apollonian_circle_pair!(8.0 * SQRT_2, left),
-    apollonian_circle_pair!(16.0, left),
-    apollonian_circle_pair!(16.0 * SQRT_2, left),
-    apollonian_circle_pair!(32.0, left),
-    apollonian_circle_pair!(1.0 * SQRT_SQRT_2, right),
-    apollonian_circle_pair!(SQRT_2, right),
-    apollonian_circle_pair!(SQRT_2 * SQRT_SQRT_2, right),
-    apollonian_circle_pair!(2.0, right),
-    apollonian_circle_pair!(2.0 * SQRT_SQRT_2, right),
-    apollonian_circle_pair!(2.0 * SQRT_2, right),
-    apollonian_circle_pair!(4.0, right),
-    apollonian_circle_pair!(4.0 * SQRT_2, right),
-    apollonian_circle_pair!(8.0, right),
-    apollonian_circle_pair!(8.0 * SQRT_2, right),
-    apollonian_circle_pair!(16.0, right),
-    apollonian_circle_pair!(16.0 * SQRT_2, right),
-    apollonian_circle_pair!(32.0, right),
-    /*
-     Circles running along the y-axis passing through -5.0 and 5.0.
-     The radii are hard-coded and computed based on `sqrt(c^2 + r^2)` (where r = 5),
-     since const sqrt is not available to do this computation at compile time.
-     TODO: may be worth investigating initializing this lazily at runtime to avoid needing to hardcode this
-    */
-    circle!(c = [0.0, 0.0], r = 5.0f64),
-    circle!(c = [0.0, 1.0], r = 5.09901951359278449160683521768078207969665527343750f64),
-    circle!(c = [0.0, 2.0], r = 5.385164807134503739405317901400849223136901855468750f64),
-    circle!(c = [0.0, 3.0], r = 5.83095189484530074253143538953736424446105957031250f64),
-    circle!(c = [0.0, 4.0], r = 6.4031242374328485311707481741905212402343750f64),
-    circle!(c = [0.0, 5.0], r = 7.071067811865475505328504368662834167480468750f64),
-    circle!(c = [0.0, 6.0], r = 7.810249675906653976653615245595574378967285156250f64),
-    circle!(c = [0.0, 7.0], r = 8.6023252670426266774938994785770773887634277343750f64),
-    circle!(c = [0.0, 8.0], r = 9.43398113205660315827572048874571919441223144531250f64),
-    circle!(c = [0.0, 9.0], r = 10.29563014098700080012349644675850868225097656250f64),
-    circle!(c = [0.0, 10.0], r = 11.1803398874989490252573887119069695472717285156250f64),
-    circle!(c = [0.0, 11.0], r = 12.083045973594572330966911977156996726989746093750f64),
-    circle!(c = [0.0, 12.0], r = 13.0f64),
-    circle!(c = [0.0, 13.0], r = 13.928388277184119203866430325433611869812011718750f64),
-    circle!(c = [0.0, 14.0], r = 14.86606874731850602699978480814024806022644042968750f64),
-    circle!(c = [0.0, 15.0], r = 15.81138830084189628166768670780584216117858886718750f64),
-    circle!(c = [0.0, 16.0], r = 16.763054614240211037667904747650027275085449218750f64),
-    circle!(c = [0.0, 17.0], r = 17.72004514666934937849873676896095275878906250f64),
-    circle!(c = [0.0, 18.0], r = 18.6815416922694055301690241321921348571777343750f64),
-    circle!(c = [0.0, 19.0], r = 19.6468827043884992633593356003984808921813964843750f64),
-    circle!(c = [0.0, 20.0], r = 20.6155281280883038164120080182328820228576660156250f64),
-    circle!(c = [0.0, -1.0], r = 5.09901951359278449160683521768078207969665527343750f64),
-    circle!(c = [0.0, -2.0], r = 5.385164807134503739405317901400849223136901855468750f64),
-    circle!(c = [0.0, -3.0], r = 5.83095189484530074253143538953736424446105957031250f64),
-    circle!(c = [0.0, -4.0], r = 6.4031242374328485311707481741905212402343750f64),
-    circle!(c = [0.0, -5.0], r = 7.071067811865475505328504368662834167480468750f64),
-    circle!(c = [0.0, -6.0], r = 7.810249675906653976653615245595574378967285156250f64),
-    circle!(c = [0.0, -7.0], r = 8.6023252670426266774938994785770773887634277343750f64),
-    circle!(c = [0.0, -8.0], r = 9.43398113205660315827572048874571919441223144531250f64),
-    circle!(c = [0.0, -9.0], r = 10.29563014098700080012349644675850868225097656250f64),
-    circle!(c = [0.0, -10.0], r = 11.1803398874989490252573887119069695472717285156250f64),
-    circle!(c = [0.0, -11.0], r = 12.083045973594572330966911977156996726989746093750f64),
-    circle!(c = [0.0, -12.0], r = 13.0f64),
-    circle!(c = [0.0, -13.0], r = 13.928388277184119203866430325433611869812011718750f64),
-    circle!(c = [0.0, -14.0], r = 14.86606874731850602699978480814024806022644042968750f64),
-    circle!(c = [0.0, -15.0], r = 15.81138830084189628166768670780584216117858886718750f64),
-    circle!(c = [0.0, -16.0], r = 16.763054614240211037667904747650027275085449218750f64),
-    circle!(c = [0.0, -17.0], r = 17.72004514666934937849873676896095275878906250f64),
-    circle!(c = [0.0, -18.0], r = 18.6815416922694055301690241321921348571777343750f64),
-    circle!(c = [0.0, -19.0], r = 19.6468827043884992633593356003984808921813964843750f64),
-    circle!(c = [0.0, -20.0], r = 20.6155281280883038164120080182328820228576660156250f64),
-];
+#[cfg(test)]
+#[path = "data_tests.rs"]
+mod tests;
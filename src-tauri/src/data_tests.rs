@@ -0,0 +1,323 @@
+use super::*;
+
+/* steiner_chain tests */
+
+// extracts the center and radius of a curve expected to be a circle
+fn as_circle(m: &Matrix2<Complexf>) -> (Complexf, f64) {
+    match matrix_to_curve(m) {
+        Curve::Circle { center, radius } => (center, radius),
+        curve => panic!("expected a circle, got {curve:?}"),
+    }
+}
+
+#[test]
+fn steiner_chain_tangency() {
+    let n = 7;
+    let curves = steiner_chain(n, 10.0, 0.6, 0.3);
+    assert_eq!(curves.len(), n + 2);
+
+    let (outer_center, outer_radius) = as_circle(&curves[0]);
+    let (inner_center, inner_radius) = as_circle(&curves[1]);
+    let chain = curves[2..].iter().map(as_circle).collect::<Vec<_>>();
+
+    // outer boundary is preserved, but the inner boundary is no longer concentric with it
+    approx::assert_abs_diff_eq!(outer_center, Complexf::ZERO, epsilon = 1e-9);
+    approx::assert_relative_eq!(outer_radius, 10.0, epsilon = 1e-9);
+    assert!((inner_center - outer_center).norm() > 1.0);
+
+    for (i, &(center, radius)) in chain.iter().enumerate() {
+        // internally tangent to the outer boundary, externally tangent to the inner boundary
+        approx::assert_relative_eq!(
+            (center - outer_center).norm(),
+            outer_radius - radius,
+            epsilon = 1e-9
+        );
+        approx::assert_relative_eq!(
+            (center - inner_center).norm(),
+            inner_radius + radius,
+            epsilon = 1e-9
+        );
+
+        // externally tangent to the next circle in the chain, which wraps around at the end
+        let (next_center, next_radius) = chain[(i + 1) % n];
+        approx::assert_relative_eq!(
+            (center - next_center).norm(),
+            radius + next_radius,
+            epsilon = 1e-9
+        );
+    }
+}
+
+#[test]
+fn steiner_chain_family() {
+    let family = SteinerChainFamily;
+    let curves = family.generate(&FamilyParams::new());
+    assert_eq!(curves.len(), 8);
+
+    let params = serde_json::json!({ "n": 9, "offset": -0.2 });
+    let curves = family.generate(params.as_object().unwrap());
+    assert_eq!(curves.len(), 11);
+}
+
+#[test]
+fn steiner_chain_family_invalid_params() {
+    let family = SteinerChainFamily;
+    for params in [
+        serde_json::json!({ "n": 2 }),
+        serde_json::json!({ "offset": 1.0 }),
+        serde_json::json!({ "radius": "large" }),
+    ] {
+        assert!(family.generate(params.as_object().unwrap()).is_empty());
+    }
+}
+
+/* gridline family tests */
+
+#[test]
+fn xy_gridlines_default() {
+    let curves = XyGridlinesFamily.generate(&FamilyParams::new());
+
+    // lines x = -20, ..., 20 and y = -20, ..., 20
+    assert_eq!(curves.len(), 82);
+    assert_eq!(curves[0], y_axis_gridline!(-20.0));
+    assert_eq!(curves[81], x_axis_gridline!(20.0));
+}
+
+#[test]
+fn xy_gridlines_params() {
+    let params = serde_json::json!({ "step": 0.25, "extent": 5 });
+    let curves = XyGridlinesFamily.generate(params.as_object().unwrap());
+
+    assert_eq!(curves.len(), 2 * 41);
+    for (k, m) in curves[..41].iter().enumerate() {
+        match matrix_to_curve(m) {
+            Curve::Line { point, slope } => {
+                approx::assert_relative_eq!(point.re, -5.0 + 0.25 * k as f64, epsilon = 1e-9);
+                approx::assert_abs_diff_eq!(slope.re, 0.0, epsilon = 1e-9);
+            }
+            curve => panic!("expected a line, got {curve:?}"),
+        }
+    }
+}
+
+#[test]
+fn xy_gridlines_inexact_extent() {
+    // 0.9 / 0.3 is slightly less than 3 in floating point, but the line at 0.9 should still be generated
+    let params = serde_json::json!({ "step": 0.3, "extent": 0.9 });
+
+    assert_eq!(
+        XyGridlinesFamily
+            .generate(params.as_object().unwrap())
+            .len(),
+        2 * 7
+    );
+}
+
+#[test]
+fn polar_gridlines_params() {
+    let params = serde_json::json!({ "step": 2.5, "extent": 10, "divisions": 4 });
+    let curves = PolarGridlinesFamily.generate(params.as_object().unwrap());
+    assert_eq!(curves.len(), 4 + 4);
+
+    // radial lines are spaced pi/4 apart
+    for (k, m) in curves[..4].iter().enumerate() {
+        match matrix_to_curve(m) {
+            Curve::Line { point, slope } => {
+                let angle = std::f64::consts::FRAC_PI_4 * k as f64;
+                approx::assert_abs_diff_eq!(point, Complexf::ZERO, epsilon = 1e-9);
+                approx::assert_abs_diff_eq!(
+                    (slope * Complexf::from_polar(1.0, -angle)).im,
+                    0.0,
+                    epsilon = 1e-9
+                );
+            }
+            curve => panic!("expected a line, got {curve:?}"),
+        }
+    }
+    for (k, m) in curves[4..].iter().enumerate() {
+        let (center, radius) = as_circle(m);
+        approx::assert_abs_diff_eq!(center, Complexf::ZERO, epsilon = 1e-9);
+        approx::assert_relative_eq!(radius, 2.5 * (k + 1) as f64, epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn gridlines_invalid_params() {
+    for params in [
+        serde_json::json!({ "step": 0 }),
+        serde_json::json!({ "step": -1 }),
+        serde_json::json!({ "extent": -1 }),
+        serde_json::json!({ "step": 1e-9, "extent": 1e9 }),
+    ] {
+        assert!(XyGridlinesFamily
+            .generate(params.as_object().unwrap())
+            .is_empty());
+        assert!(PolarGridlinesFamily
+            .generate(params.as_object().unwrap())
+            .is_empty());
+    }
+}
+
+/* builtin family tests */
+
+#[test]
+fn builtin_families_registered() {
+    let registry = CurveFamilyRegistry::default();
+    let names = registry.iter().map(|f| f.name()).collect::<Vec<_>>();
+
+    assert_eq!(
+        names,
+        [
+            "xy",
+            "polar",
+            "apollo",
+            "steiner",
+            "geodesic_disk",
+            "geodesic_half_plane",
+            "tiling_disk",
+            "tiling_half_plane"
+        ]
+    );
+}
+
+#[test]
+fn builtin_families_param_defaults() {
+    // the advertised defaults should generate the same curves as omitting the parameters
+    let registry = CurveFamilyRegistry::default();
+    for family in registry.iter() {
+        let params = family
+            .params()
+            .into_iter()
+            .map(|p| (p.name.to_owned(), p.default))
+            .collect::<FamilyParams>();
+
+        assert!(params.values().all(|v| !v.is_null()));
+        assert_eq!(
+            family.generate(&params),
+            family.generate(&FamilyParams::new())
+        );
+        assert_eq!(
+            family.generate_arcs(&params),
+            family.generate_arcs(&FamilyParams::new())
+        );
+    }
+}
+
+#[test]
+fn tiling_family_generates_arcs() {
+    let family = TilingFamily {
+        model: HyperbolicModel::Disk,
+    };
+    let params = serde_json::json!({ "p": 5, "q": 4, "depth": 1 });
+
+    assert!(family.generate(&FamilyParams::new()).is_empty());
+    assert_eq!(family.generate_arcs(params.as_object().unwrap()).len(), 25);
+    // {4, 4} is a euclidean tiling
+    let params = serde_json::json!({ "p": 4, "q": 4 });
+    assert!(family.generate_arcs(params.as_object().unwrap()).is_empty());
+}
+
+#[test]
+fn tiling_family_rejects_huge_polygons() {
+    let family = TilingFamily {
+        model: HyperbolicModel::Disk,
+    };
+
+    for params in [
+        serde_json::json!({ "p": usize::MAX, "q": usize::MAX }),
+        serde_json::json!({ "p": 1_000_000_000, "q": 3, "depth": 0 }),
+    ] {
+        assert!(family.generate_arcs(params.as_object().unwrap()).is_empty());
+    }
+}
+
+/* apollonian family tests */
+
+#[test]
+fn apollonian_circles_bipolar() {
+    let (a, b) = (Complexf::new(-1.0, 2.0), Complexf::new(3.0, 0.5));
+    let curves = apollonian_circles([ExtComplex::Val(a), ExtComplex::Val(b)], 1.5, 2, 3);
+    assert_eq!(curves.len(), 5 + 3);
+
+    // the foci are inverse to each other with respect to each Apollonian circle,
+    // except for the perpendicular bisector p = 1
+    for (k, m) in curves[..5].iter().enumerate() {
+        let p = 1.5f64.powi(k as i32 - 2);
+        match matrix_to_curve(m) {
+            Curve::Circle { center, radius } => {
+                approx::assert_relative_eq!(
+                    (a - center).norm() * (b - center).norm(),
+                    radius * radius,
+                    epsilon = 1e-9
+                );
+                approx::assert_relative_eq!(
+                    (a - center).norm() + radius,
+                    p * ((b - center).norm() + radius),
+                    epsilon = 1e-9
+                );
+            }
+            Curve::Line { point, .. } => {
+                assert_eq!(k, 2);
+                approx::assert_relative_eq!((a - point).norm(), (b - point).norm(), epsilon = 1e-9);
+            }
+            curve => panic!("unexpected curve {curve:?}"),
+        }
+    }
+
+    // the remaining curves pass through both foci, starting with the line through them
+    assert!(matches!(matrix_to_curve(&curves[5]), Curve::Line { .. }));
+    for m in &curves[6..] {
+        let (center, radius) = as_circle(m);
+        approx::assert_relative_eq!((a - center).norm(), radius, epsilon = 1e-9);
+        approx::assert_relative_eq!((b - center).norm(), radius, epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn apollonian_circles_infinite_focus() {
+    // degenerates into polar gridlines centered at the finite focus
+    let curves = apollonian_circles([ExtComplex::new(1.0, 1.0), ExtComplex::Inf], 2.0, 1, 4);
+    assert_eq!(curves.len(), 3 + 4);
+
+    for (k, m) in curves[..3].iter().enumerate() {
+        let (center, radius) = as_circle(m);
+        approx::assert_abs_diff_eq!(center, Complexf::new(1.0, 1.0), epsilon = 1e-9);
+        approx::assert_relative_eq!(radius, 2f64.powi(k as i32 - 1), epsilon = 1e-9);
+    }
+    for m in &curves[3..] {
+        match matrix_to_curve(m) {
+            Curve::Line { point, slope } => {
+                // the focus lies on the line
+                let offset = Complexf::new(1.0, 1.0) - point;
+                approx::assert_abs_diff_eq!((offset * slope.conj()).im, 0.0, epsilon = 1e-9);
+            }
+            curve => panic!("expected a line, got {curve:?}"),
+        }
+    }
+}
+
+#[test]
+fn apollonian_family_params() {
+    let family = ApollonianFamily;
+    assert_eq!(family.generate(&FamilyParams::new()).len(), 41 + 24);
+
+    let params = serde_json::json!({ "foci": [[0, 0], "inf"], "count": 2, "divisions": 0 });
+    assert_eq!(family.generate(params.as_object().unwrap()).len(), 5);
+    // the largest family allowed
+    let params = serde_json::json!({ "count": 499, "divisions": 1 });
+    assert_eq!(
+        family.generate(params.as_object().unwrap()).len(),
+        MAX_FAMILY_SIZE
+    );
+
+    for params in [
+        serde_json::json!({ "foci": ["inf", "inf"] }),
+        serde_json::json!({ "foci": [[1, 1], [1, 1]] }),
+        serde_json::json!({ "ratio": 1 }),
+        serde_json::json!({ "count": 1000 }),
+        serde_json::json!({ "count": usize::MAX }),
+        serde_json::json!({ "count": usize::MAX / 2, "divisions": usize::MAX }),
+    ] {
+        assert!(family.generate(params.as_object().unwrap()).is_empty());
+    }
+}
@@ -0,0 +1,212 @@
+//! Contains the [`CurveFamily`] abstraction, and the registry through which curve families are looked up at runtime.
+
+use nalgebra::Matrix2;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+/// Parameters passed to a [`CurveFamily`] to control how its curves are generated,
+/// e.g. the spacing between gridlines.
+///
+/// Each family interprets its own parameters, and falls back to a default for any parameter not provided.
+pub type FamilyParams = serde_json::Map<String, serde_json::Value>;
+
+/// A named family of curves (e.g. a set of gridlines) which can be transformed and displayed.
+///
+/// Curves are generated in their 2x2 matrix representation (see [`matrix_to_curve`]).
+/// Families made up of pieces of circles/lines (i.e. arcs) generate [`ThreePointArcs`][ThreePointArc] instead.
+pub trait CurveFamily: Send + Sync {
+    /// The key used to look up this family, which must be unique within a [`CurveFamilyRegistry`].
+    fn name(&self) -> &str;
+
+    /// A human-readable name for this family.
+    fn display_name(&self) -> &str;
+
+    /// A short human-readable description of the curves in this family.
+    fn description(&self) -> &str {
+        ""
+    }
+
+    /// The parameters this family accepts, along with their default values.
+    fn params(&self) -> Vec<ParamSchema> {
+        Vec::new()
+    }
+
+    /// The style the curves in this family are drawn with, unless overridden by the user.
+    fn default_style(&self) -> CurveStyle {
+        CurveStyle::default()
+    }
+
+    /// Generate the 2x2 matrix representations of the curves in this family.
+    ///
+    /// If the parameters are invalid, no curves are generated.
+    fn generate(&self, params: &FamilyParams) -> Vec<Matrix2<Complexf>>;
+
+    /// Generate the arcs in this family.
+    ///
+    /// If the parameters are invalid, no arcs are generated.
+    /// Most families are made up of whole circles/lines, so by default no arcs are generated.
+    fn generate_arcs(&self, _params: &FamilyParams) -> Vec<ThreePointArc> {
+        Vec::new()
+    }
+}
+
+/// Parse the parameters passed to a [`CurveFamily`] into a struct.
+///
+/// The struct should implement [`Deserialize`] with `#[serde(default)]`, so that missing parameters take their default values.
+/// Returns nothing if any of the parameters are invalid (e.g. have the wrong type).
+pub fn parse_params<T: DeserializeOwned>(params: &FamilyParams) -> Option<T> {
+    T::deserialize(serde_json::Value::Object(params.clone())).ok()
+}
+
+/// Describes one of the parameters accepted by a [`CurveFamily`], so that the frontend can offer controls for it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParamSchema {
+    /// The key of the parameter in the [`FamilyParams`].
+    pub name: &'static str,
+    pub description: &'static str,
+    #[serde(rename = "type")]
+    pub kind: ParamKind,
+    /// The value used when the parameter is not provided.
+    pub default: serde_json::Value,
+}
+
+/// The type of value a parameter of a [`CurveFamily`] takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParamKind {
+    Number,
+    Integer,
+    /// A list of points in the extended complex plane (see [`ExtComplex`]).
+    Points,
+}
+
+/// Build the schemas of the parameters of a [`CurveFamily`] from their names, types and descriptions,
+/// taking the default values from the [`Default`] implementation of the struct they are parsed into (see [`parse_params`]).
+pub fn param_schemas<T: Serialize + Default>(
+    params: &[(&'static str, ParamKind, &'static str)],
+) -> Vec<ParamSchema> {
+    let defaults = serde_json::to_value(T::default()).unwrap_or_default();
+    params
+        .iter()
+        .map(|&(name, kind, description)| ParamSchema {
+            name,
+            description,
+            kind,
+            default: defaults.get(name).cloned().unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// How the curves of a [`CurveFamily`] are drawn.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CurveStyle {
+    /// A CSS color to draw the curves with, or `None` to use the color of the current theme.
+    pub color: Option<String>,
+    /// Width of the curves, in pixels.
+    pub line_width: f64,
+    pub dashed: bool,
+}
+
+impl Default for CurveStyle {
+    fn default() -> Self {
+        CurveStyle {
+            color: None,
+            line_width: 1.0,
+            dashed: false,
+        }
+    }
+}
+
+/// A collection of [`CurveFamilies`][CurveFamily] which can be looked up by name.
+///
+/// The [default][CurveFamilyRegistry::default] registry contains the built-in families,
+/// and is held in Tauri's managed state so that it is available to the commands invoked by the frontend.
+pub struct CurveFamilyRegistry {
+    /// families in the order they were registered
+    families: Vec<Box<dyn CurveFamily>>,
+}
+
+impl CurveFamilyRegistry {
+    /// Create a registry without any families.
+    pub fn empty() -> Self {
+        CurveFamilyRegistry {
+            families: Vec::new(),
+        }
+    }
+
+    /// Add a family to the registry, replacing any existing family with the same name.
+    pub fn register(&mut self, family: impl CurveFamily + 'static) {
+        let family: Box<dyn CurveFamily> = Box::new(family);
+        match self.families.iter_mut().find(|f| f.name() == family.name()) {
+            Some(existing) => *existing = family,
+            None => self.families.push(family),
+        }
+    }
+
+    /// Look up a family by name.
+    pub fn get(&self, name: &str) -> Option<&dyn CurveFamily> {
+        self.families
+            .iter()
+            .find(|f| f.name() == name)
+            .map(|f| f.as_ref())
+    }
+
+    /// Iterate over the families in the order they were registered.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn CurveFamily> {
+        self.families.iter().map(|f| f.as_ref())
+    }
+}
+
+impl Default for CurveFamilyRegistry {
+    /// Create a registry containing the built-in families (see [`data`]).
+    fn default() -> Self {
+        let mut registry = CurveFamilyRegistry::empty();
+        data::register_builtin_families(&mut registry);
+        registry
+    }
+}
+
+/// A request for the curves of a family, as passed in by the frontend.
+///
+/// Can either be just the name of the family (in which case the default parameters are used),
+/// or an object containing the name along with the parameters, e.g. `{"name": "xy", "step": 0.25, "extent": 5}`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum CurveFamilyRequest<'a> {
+    Name(&'a str),
+    WithParams {
+        name: &'a str,
+        #[serde(flatten)]
+        params: FamilyParams,
+    },
+}
+
+impl<'a> CurveFamilyRequest<'a> {
+    /// The name of the requested family.
+    pub fn name(&self) -> &'a str {
+        match *self {
+            Self::Name(name) | Self::WithParams { name, .. } => name,
+        }
+    }
+
+    /// The parameters the family should be generated with.
+    pub fn params(&self) -> FamilyParams {
+        match self {
+            Self::Name(_) => FamilyParams::new(),
+            Self::WithParams { params, .. } => params.clone(),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for CurveFamilyRequest<'a> {
+    fn from(name: &'a str) -> Self {
+        Self::Name(name)
+    }
+}
+
+#[cfg(test)]
+#[path = "family_tests.rs"]
+mod tests;
@@ -0,0 +1,110 @@
+use serde_json::json;
+
+use super::*;
+
+// family with a single circle, whose radius is controlled by a parameter
+struct CircleFamily;
+
+#[derive(Deserialize)]
+#[serde(default)]
+struct CircleParams {
+    radius: f64,
+}
+
+impl Default for CircleParams {
+    fn default() -> Self {
+        CircleParams { radius: 1.0 }
+    }
+}
+
+impl CurveFamily for CircleFamily {
+    fn name(&self) -> &str {
+        "circle"
+    }
+
+    fn display_name(&self) -> &str {
+        "Circle"
+    }
+
+    fn generate(&self, params: &FamilyParams) -> Vec<Matrix2<Complexf>> {
+        match parse_params::<CircleParams>(params) {
+            Some(p) => vec![math::curve_to_matrix(&Curve::Circle {
+                center: Complexf::ZERO,
+                radius: p.radius,
+            })],
+            None => Vec::new(),
+        }
+    }
+}
+
+#[test]
+fn registry_register_and_get() {
+    let mut registry = CurveFamilyRegistry::empty();
+    assert!(registry.get("circle").is_none());
+
+    registry.register(CircleFamily);
+    let family = registry.get("circle").expect("family should be registered");
+    assert_eq!(family.display_name(), "Circle");
+    assert!(family.generate_arcs(&FamilyParams::new()).is_empty());
+}
+
+#[test]
+fn registry_register_replaces() {
+    let mut registry = CurveFamilyRegistry::default();
+    let count = registry.iter().count();
+    registry.register(StaticFamily {
+        name: "xy",
+        display_name: "Replaced",
+        curves: &[],
+    });
+
+    // the replaced family keeps its position
+    assert_eq!(registry.iter().count(), count);
+    assert_eq!(registry.iter().next().unwrap().display_name(), "Replaced");
+}
+
+#[test]
+fn parse_params_defaults() {
+    let family = CircleFamily;
+    let curves = family.generate(&FamilyParams::new());
+    let params = json!({ "radius": 3.0 });
+    let scaled_curves = family.generate(params.as_object().unwrap());
+
+    assert_eq!(
+        matrix_to_curve(&curves[0]),
+        Curve::Circle {
+            center: Complexf::ZERO,
+            radius: 1.0
+        }
+    );
+    assert_eq!(
+        matrix_to_curve(&scaled_curves[0]),
+        Curve::Circle {
+            center: Complexf::ZERO,
+            radius: 3.0
+        }
+    );
+}
+
+#[test]
+fn parse_params_invalid() {
+    let params = json!({ "radius": "big" });
+
+    assert!(parse_params::<CircleParams>(params.as_object().unwrap()).is_none());
+}
+
+#[test]
+fn deserialize_request() {
+    let requests: Vec<CurveFamilyRequest> =
+        serde_json::from_str(r#"["xy", { "name": "steiner", "n": 5 }, { "name": "polar" }]"#)
+            .expect("deserialization should succeed");
+
+    assert_eq!(requests[0], CurveFamilyRequest::Name("xy"));
+    assert_eq!(requests[1].name(), "steiner");
+    assert_eq!(
+        requests[1].params(),
+        *json!({ "n": 5 }).as_object().unwrap()
+    );
+    assert_eq!(requests[2].name(), "polar");
+    assert!(requests[2].params().is_empty());
+}
@@ -0,0 +1,467 @@
+//! Contains a C ABI for the math core, so that it can be called from C/C++ through the `cdylib` built with the `ffi` feature
+//! (see `ffi/Makefile`).
+//!
+//! The API only uses plain `#[repr(C)]` types, and every function reports failure through a [`MobiusStatus`]
+//! rather than an `Option`, writing its result through an out-pointer.
+//! The corresponding C header is `ffi/mobius.h`, generated with `cbindgen --output ffi/mobius.h` (see `cbindgen.toml`).
+//!
+//! Matrices are used both for Mobius transformations and for circles/lines (see [`matrix_to_curve`]),
+//! in the same way as in the rest of the crate.
+
+use std::slice;
+
+use nalgebra::Matrix2;
+
+use super::*;
+
+/// A finite complex number.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MobiusComplex {
+    pub re: f64,
+    pub im: f64,
+}
+
+/// An element of the extended complex plane, i.e. an [`ExtComplex`].
+///
+/// The point is infinite when `is_infinite` is non-zero, in which case `value` is ignored.
+/// It is a plain `u32` rather than a `bool` for the same reason as the `kind` of a [`MobiusCurve`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MobiusPoint {
+    pub is_infinite: u32,
+    pub value: MobiusComplex,
+}
+
+/// A 2x2 complex matrix `[[m11, m12], [m21, m22]]`, representing either a Mobius transformation
+/// `(m11 z + m12) / (m21 z + m22)` or a circle/line.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MobiusMatrix {
+    pub m11: MobiusComplex,
+    pub m12: MobiusComplex,
+    pub m21: MobiusComplex,
+    pub m22: MobiusComplex,
+}
+
+/// The kinds of [`MobiusCurve`], matching the variants of [`Curve`].
+///
+/// A [`MobiusCurve`] stores its `kind` as a plain `u32` rather than as this enum,
+/// since C code can pass in any integer, and reading one which isn't a variant would be undefined behavior.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MobiusCurveKind {
+    Line = 0,
+    Circle = 1,
+    Arc = 2,
+    Segment = 3,
+    Ray = 4,
+}
+
+/// A line, circle or piece of one, i.e. a [`Curve`].
+///
+/// Which fields are used depends on the `kind` (one of the values of [`MobiusCurveKind`]),
+/// and the unused fields are set to 0:
+///  - `Line`: the points `point + direction * t`
+///  - `Circle`: the points `|z - point| = radius`
+///  - `Arc`: the part of the circle `|z - point| = radius` traced counterclockwise from `start` to `end`
+///  - `Segment`: the points `start + (end - start) * t` for `0 <= t <= 1`
+///  - `Ray`: the points `point + direction * t` for `t >= 0`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MobiusCurve {
+    pub kind: u32,
+    pub point: MobiusComplex,
+    pub direction: MobiusComplex,
+    pub radius: f64,
+    pub start: MobiusComplex,
+    pub end: MobiusComplex,
+}
+
+/// The result of calling a function of the C API.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MobiusStatus {
+    /// The function succeeded, and its result was written to the out-pointer.
+    Ok = 0,
+    /// One of the pointers passed in was null.
+    NullPointer = 1,
+    /// One of the numbers passed in was nan.
+    InvalidNumber = 2,
+    /// Two of the inputs are (nearly) the same point, so no transformation exists.
+    DuplicateInputs = 3,
+    /// Two of the outputs are (nearly) the same point, so no transformation exists.
+    DuplicateOutputs = 4,
+    /// The matrix doesn't describe an actual circle or line, e.g. because it has a radius of 0.
+    DegenerateCurve = 5,
+    /// The `kind` of a curve passed in is not one of the values of `MobiusCurveKind`.
+    InvalidCurveKind = 6,
+}
+
+impl From<Complexf> for MobiusComplex {
+    fn from(c: Complexf) -> Self {
+        MobiusComplex { re: c.re, im: c.im }
+    }
+}
+
+impl From<MobiusComplex> for Complexf {
+    fn from(c: MobiusComplex) -> Self {
+        Complexf::new(c.re, c.im)
+    }
+}
+
+impl From<ExtComplex> for MobiusPoint {
+    fn from(p: ExtComplex) -> Self {
+        match p {
+            ExtComplex::Inf => MobiusPoint {
+                is_infinite: 1,
+                value: Complexf::ZERO.into(),
+            },
+            ExtComplex::Val(v) => MobiusPoint {
+                is_infinite: 0,
+                value: v.into(),
+            },
+        }
+    }
+}
+
+impl MobiusPoint {
+    /// Convert to an [`ExtComplex`], returning `None` if either component is nan.
+    fn to_ext_complex(self) -> Option<ExtComplex> {
+        if self.is_infinite != 0 {
+            Some(ExtComplex::Inf)
+        } else {
+            ExtComplex::try_new(self.value.re, self.value.im)
+        }
+    }
+}
+
+impl From<Matrix2<Complexf>> for MobiusMatrix {
+    fn from(m: Matrix2<Complexf>) -> Self {
+        MobiusMatrix {
+            m11: m.m11.into(),
+            m12: m.m12.into(),
+            m21: m.m21.into(),
+            m22: m.m22.into(),
+        }
+    }
+}
+
+impl From<MobiusMatrix> for Matrix2<Complexf> {
+    fn from(m: MobiusMatrix) -> Self {
+        Matrix2::new(m.m11.into(), m.m12.into(), m.m21.into(), m.m22.into())
+    }
+}
+
+impl From<Curve> for MobiusCurve {
+    fn from(curve: Curve) -> Self {
+        let zero = Complexf::ZERO.into();
+        let empty = MobiusCurve {
+            kind: MobiusCurveKind::Line as u32,
+            point: zero,
+            direction: zero,
+            radius: 0.0,
+            start: zero,
+            end: zero,
+        };
+        match curve {
+            Curve::Line { point, slope } => MobiusCurve {
+                point: point.into(),
+                direction: slope.into(),
+                ..empty
+            },
+            Curve::Circle { center, radius } => MobiusCurve {
+                kind: MobiusCurveKind::Circle as u32,
+                point: center.into(),
+                radius,
+                ..empty
+            },
+            Curve::Arc {
+                center,
+                radius,
+                start,
+                end,
+            } => MobiusCurve {
+                kind: MobiusCurveKind::Arc as u32,
+                point: center.into(),
+                radius,
+                start: start.into(),
+                end: end.into(),
+                ..empty
+            },
+            Curve::Segment { start, end } => MobiusCurve {
+                kind: MobiusCurveKind::Segment as u32,
+                start: start.into(),
+                end: end.into(),
+                ..empty
+            },
+            Curve::Ray { point, direction } => MobiusCurve {
+                kind: MobiusCurveKind::Ray as u32,
+                point: point.into(),
+                direction: direction.into(),
+                ..empty
+            },
+        }
+    }
+}
+
+impl TryFrom<u32> for MobiusCurveKind {
+    type Error = MobiusStatus;
+
+    fn try_from(kind: u32) -> Result<Self, Self::Error> {
+        [
+            MobiusCurveKind::Line,
+            MobiusCurveKind::Circle,
+            MobiusCurveKind::Arc,
+            MobiusCurveKind::Segment,
+            MobiusCurveKind::Ray,
+        ]
+        .into_iter()
+        .find(|&k| k as u32 == kind)
+        .ok_or(MobiusStatus::InvalidCurveKind)
+    }
+}
+
+impl TryFrom<MobiusCurve> for Curve {
+    type Error = MobiusStatus;
+
+    fn try_from(curve: MobiusCurve) -> Result<Self, Self::Error> {
+        Ok(match MobiusCurveKind::try_from(curve.kind)? {
+            MobiusCurveKind::Line => Curve::Line {
+                point: curve.point.into(),
+                slope: curve.direction.into(),
+            },
+            MobiusCurveKind::Circle => Curve::Circle {
+                center: curve.point.into(),
+                radius: curve.radius,
+            },
+            MobiusCurveKind::Arc => Curve::Arc {
+                center: curve.point.into(),
+                radius: curve.radius,
+                start: curve.start.into(),
+                end: curve.end.into(),
+            },
+            MobiusCurveKind::Segment => Curve::Segment {
+                start: curve.start.into(),
+                end: curve.end.into(),
+            },
+            MobiusCurveKind::Ray => Curve::Ray {
+                point: curve.point.into(),
+                direction: curve.direction.into(),
+            },
+        })
+    }
+}
+
+/// Internal helper which reads three points, e.g. the control points of a transformation.
+///
+/// # Safety
+///
+/// `points` must be null or point to 3 valid `MobiusPoint`s.
+unsafe fn read_points(points: *const MobiusPoint) -> Result<[ExtComplex; 3], MobiusStatus> {
+    if points.is_null() {
+        return Err(MobiusStatus::NullPointer);
+    }
+    let points = slice::from_raw_parts(points, 3);
+    let mut result = [ExtComplex::Inf; 3];
+    for (p, q) in result.iter_mut().zip(points) {
+        *p = q.to_ext_complex().ok_or(MobiusStatus::InvalidNumber)?;
+    }
+    Ok(result)
+}
+
+/// Internal helper which writes the result of a function to its out-pointer, converting it to a status.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes.
+unsafe fn write_result<T>(result: Result<T, MobiusStatus>, out: *mut T) -> MobiusStatus {
+    match result {
+        _ if out.is_null() => MobiusStatus::NullPointer,
+        Ok(value) => {
+            out.write(value);
+            MobiusStatus::Ok
+        }
+        Err(status) => status,
+    }
+}
+
+/// Computes the Mobius transformation mapping each of the three `inputs` onto the corresponding `outputs`
+/// (see [`compute_mobius_tform`]), and writes its matrix to `out`.
+///
+/// Fails if any of the points are nan, or if two of the inputs or two of the outputs are (nearly) the same point.
+///
+/// # Safety
+///
+/// `inputs` and `outputs` must each point to 3 valid points, and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn mobius_compute_tform(
+    inputs: *const MobiusPoint,
+    outputs: *const MobiusPoint,
+    out: *mut MobiusMatrix,
+) -> MobiusStatus {
+    let result = (|| {
+        let inputs = read_points(inputs)?;
+        let outputs = read_points(outputs)?;
+        math::compute_mobius_tform(&inputs, &outputs)
+            .map(MobiusMatrix::from)
+            .ok_or_else(|| match diagnose_singular_tform(&inputs, &outputs) {
+                Error::DuplicateInputs { .. } => MobiusStatus::DuplicateInputs,
+                _ => MobiusStatus::DuplicateOutputs,
+            })
+    })();
+    write_result(result, out)
+}
+
+/// Computes the Mobius transformation mapping the three `points` onto 0, infinity and 1 respectively,
+/// and writes its matrix to `out`.
+///
+/// This is the building block of [`mobius_compute_tform`], which handles any of the points being infinite.
+/// The matrix is singular if any two of the points are the same, so no error is reported in that case.
+///
+/// # Safety
+///
+/// `points` must point to 3 valid points, and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn mobius_compute_partial_tform(
+    points: *const MobiusPoint,
+    out: *mut MobiusMatrix,
+) -> MobiusStatus {
+    let result =
+        read_points(points).map(|points| math::compute_partial_mobius_tform(&points).into());
+    write_result(result, out)
+}
+
+/// Computes the inverse of a Mobius transformation (see [`invert_mobius_tform`]), and writes its matrix to `out`.
+///
+/// # Safety
+///
+/// `tform` must point to a valid matrix, and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn mobius_invert_tform(
+    tform: *const MobiusMatrix,
+    out: *mut MobiusMatrix,
+) -> MobiusStatus {
+    let result = match tform.as_ref() {
+        Some(&tform) => Ok(math::invert_mobius_tform(&tform.into()).into()),
+        None => Err(MobiusStatus::NullPointer),
+    };
+    write_result(result, out)
+}
+
+/// Maps `count` points under a Mobius transformation (see [`apply_mobius_tform`]), writing the images to `out`.
+///
+/// `points` and `out` may be the same array, in which case the points are mapped in place.
+/// If any of the points are nan, nothing is written.
+///
+/// # Safety
+///
+/// `tform` must point to a valid matrix, and `points` and `out` must each point to `count` points.
+#[no_mangle]
+pub unsafe extern "C" fn mobius_apply_tform(
+    tform: *const MobiusMatrix,
+    points: *const MobiusPoint,
+    count: usize,
+    out: *mut MobiusPoint,
+) -> MobiusStatus {
+    let Some(&tform) = tform.as_ref() else {
+        return MobiusStatus::NullPointer;
+    };
+    if points.is_null() || out.is_null() {
+        return MobiusStatus::NullPointer;
+    }
+    // copied first, since the output may overlap with the input
+    let Some(points) = slice::from_raw_parts(points, count)
+        .iter()
+        .map(|p| p.to_ext_complex())
+        .collect::<Option<Vec<_>>>()
+    else {
+        return MobiusStatus::InvalidNumber;
+    };
+    let tform = tform.into();
+    for (i, p) in points.iter().enumerate() {
+        out.add(i).write(math::apply_mobius_tform(&tform, p).into());
+    }
+    MobiusStatus::Ok
+}
+
+/// Transforms the circle/line represented by `curve` under a Mobius transformation, and writes the matrix
+/// representing the image to `out`.
+///
+/// Since curve matrices are transformed contravariantly, this multiplies `curve` by the inverse of `tform`.
+///
+/// # Safety
+///
+/// `tform` and `curve` must point to valid matrices, and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn mobius_transform_curve(
+    tform: *const MobiusMatrix,
+    curve: *const MobiusMatrix,
+    out: *mut MobiusMatrix,
+) -> MobiusStatus {
+    let result = match (tform.as_ref(), curve.as_ref()) {
+        (Some(&tform), Some(&curve)) => {
+            let inv_tform = math::invert_mobius_tform(&tform.into());
+            Ok((Matrix2::from(curve) * inv_tform).into())
+        }
+        _ => Err(MobiusStatus::NullPointer),
+    };
+    write_result(result, out)
+}
+
+/// Converts the matrix of a circle/line into the curve itself (see [`matrix_to_curve`]), and writes it to `out`.
+///
+/// Fails if the matrix doesn't describe an actual circle or line.
+///
+/// # Safety
+///
+/// `matrix` must point to a valid matrix, and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn mobius_matrix_to_curve(
+    matrix: *const MobiusMatrix,
+    out: *mut MobiusCurve,
+) -> MobiusStatus {
+    let result = match matrix.as_ref() {
+        Some(&matrix) => {
+            let curve = math::matrix_to_curve(&matrix.into());
+            if curve.is_degenerate() {
+                Err(MobiusStatus::DegenerateCurve)
+            } else {
+                Ok(curve.into())
+            }
+        }
+        None => Err(MobiusStatus::NullPointer),
+    };
+    write_result(result, out)
+}
+
+/// Converts a circle or line into its matrix representation (see [`curve_to_matrix`]), and writes it to `out`.
+///
+/// Pieces of circles and lines (i.e. arcs, segments and rays) are converted as the whole circle/line containing them.
+/// Fails if the `kind` of the curve is not one of the values of [`MobiusCurveKind`],
+/// or if the curve is degenerate, e.g. a circle with a radius of 0.
+///
+/// # Safety
+///
+/// `curve` must point to a valid curve, and `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn mobius_curve_to_matrix(
+    curve: *const MobiusCurve,
+    out: *mut MobiusMatrix,
+) -> MobiusStatus {
+    let result = match curve.as_ref() {
+        Some(&curve) => Curve::try_from(curve).and_then(|curve| {
+            if curve.is_degenerate() {
+                Err(MobiusStatus::DegenerateCurve)
+            } else {
+                Ok(math::curve_to_matrix(&curve).into())
+            }
+        }),
+        None => Err(MobiusStatus::NullPointer),
+    };
+    write_result(result, out)
+}
+
+#[cfg(test)]
+#[path = "ffi_tests.rs"]
+mod tests;
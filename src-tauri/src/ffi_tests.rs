@@ -0,0 +1,183 @@
+use std::mem::MaybeUninit;
+use std::ptr;
+
+use super::*;
+
+fn points(points: [ExtComplex; 3]) -> [MobiusPoint; 3] {
+    points.map(MobiusPoint::from)
+}
+
+fn unit_circle() -> MobiusMatrix {
+    math::curve_to_matrix(&Curve::Circle {
+        center: Complexf::ZERO,
+        radius: 1.0,
+    })
+    .into()
+}
+
+#[test]
+fn ffi_compute_and_apply_tform() {
+    // z -> 1/z
+    let inputs = points([
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::Inf,
+    ]);
+    let outputs = points([
+        ExtComplex::Inf,
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::new(0.0, 0.0),
+    ]);
+    let mut tform = MaybeUninit::<MobiusMatrix>::uninit();
+    let status =
+        unsafe { mobius_compute_tform(inputs.as_ptr(), outputs.as_ptr(), tform.as_mut_ptr()) };
+    assert_eq!(status, MobiusStatus::Ok);
+
+    let mut mapped = points([
+        ExtComplex::new(2.0, 0.0),
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::Inf,
+    ]);
+    // mapped in place
+    let status =
+        unsafe { mobius_apply_tform(tform.as_ptr(), mapped.as_ptr(), 3, mapped.as_mut_ptr()) };
+    assert_eq!(status, MobiusStatus::Ok);
+    let mapped = mapped.map(|p| p.to_ext_complex().unwrap());
+    assert_eq!(mapped[1], ExtComplex::Inf);
+    assert_eq!(mapped[2], ExtComplex::new(0.0, 0.0));
+    match mapped[0] {
+        ExtComplex::Val(v) => {
+            approx::assert_abs_diff_eq!(v, Complexf::new(0.5, 0.0), epsilon = 1e-12)
+        }
+        ExtComplex::Inf => panic!("expected a finite point"),
+    }
+}
+
+#[test]
+fn ffi_compute_tform_errors() {
+    let distinct = points([
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::Inf,
+    ]);
+    let duplicate = points([
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::Inf,
+    ]);
+    let mut invalid = distinct;
+    invalid[1].value.re = f64::NAN;
+    let mut tform = MaybeUninit::<MobiusMatrix>::uninit();
+
+    for (inputs, outputs, expected) in [
+        (&duplicate, &distinct, MobiusStatus::DuplicateInputs),
+        (&distinct, &duplicate, MobiusStatus::DuplicateOutputs),
+        (&distinct, &invalid, MobiusStatus::InvalidNumber),
+    ] {
+        let status =
+            unsafe { mobius_compute_tform(inputs.as_ptr(), outputs.as_ptr(), tform.as_mut_ptr()) };
+        assert_eq!(status, expected);
+    }
+    let status =
+        unsafe { mobius_compute_tform(ptr::null(), distinct.as_ptr(), tform.as_mut_ptr()) };
+    assert_eq!(status, MobiusStatus::NullPointer);
+    let status =
+        unsafe { mobius_compute_tform(distinct.as_ptr(), distinct.as_ptr(), ptr::null_mut()) };
+    assert_eq!(status, MobiusStatus::NullPointer);
+}
+
+#[test]
+fn ffi_partial_tform_matches_math() {
+    let inputs = [
+        ExtComplex::Inf,
+        ExtComplex::new(1.0, 2.0),
+        ExtComplex::new(-3.0, 0.5),
+    ];
+    let mut partial = MaybeUninit::<MobiusMatrix>::uninit();
+    let status =
+        unsafe { mobius_compute_partial_tform(points(inputs).as_ptr(), partial.as_mut_ptr()) };
+
+    assert_eq!(status, MobiusStatus::Ok);
+    assert_eq!(
+        Matrix2::from(unsafe { partial.assume_init() }),
+        math::compute_partial_mobius_tform(&inputs)
+    );
+}
+
+#[test]
+fn ffi_transform_curve() {
+    // translating the unit circle by 2 gives the circle of radius 1 around 2
+    let tform = MobiusMatrix::from(Matrix2::new(
+        Complexf::ONE,
+        Complexf::new(2.0, 0.0),
+        Complexf::ZERO,
+        Complexf::ONE,
+    ));
+    let mut matrix = MaybeUninit::<MobiusMatrix>::uninit();
+    let status = unsafe { mobius_transform_curve(&tform, &unit_circle(), matrix.as_mut_ptr()) };
+    assert_eq!(status, MobiusStatus::Ok);
+
+    let mut curve = MaybeUninit::<MobiusCurve>::uninit();
+    let status = unsafe { mobius_matrix_to_curve(matrix.as_ptr(), curve.as_mut_ptr()) };
+    assert_eq!(status, MobiusStatus::Ok);
+    let curve = unsafe { curve.assume_init() };
+    assert_eq!(curve.kind, MobiusCurveKind::Circle as u32);
+    approx::assert_abs_diff_eq!(
+        Complexf::from(curve.point),
+        Complexf::new(2.0, 0.0),
+        epsilon = 1e-12
+    );
+    approx::assert_relative_eq!(curve.radius, 1.0);
+}
+
+#[test]
+fn ffi_curve_roundtrip() {
+    let line = MobiusCurve::from(Curve::Line {
+        point: Complexf::new(0.0, 1.0),
+        slope: Complexf::ONE,
+    });
+    let mut matrix = MaybeUninit::<MobiusMatrix>::uninit();
+    let status = unsafe { mobius_curve_to_matrix(&line, matrix.as_mut_ptr()) };
+    assert_eq!(status, MobiusStatus::Ok);
+
+    let mut curve = MaybeUninit::<MobiusCurve>::uninit();
+    let status = unsafe { mobius_matrix_to_curve(matrix.as_ptr(), curve.as_mut_ptr()) };
+    assert_eq!(status, MobiusStatus::Ok);
+    let curve = unsafe { curve.assume_init() };
+    assert_eq!(curve.kind, MobiusCurveKind::Line as u32);
+    approx::assert_abs_diff_eq!(Complexf::from(curve.point).im, 1.0, epsilon = 1e-12);
+    approx::assert_abs_diff_eq!(Complexf::from(curve.direction).im, 0.0, epsilon = 1e-12);
+
+    let point = MobiusCurve::from(Curve::Circle {
+        center: Complexf::ZERO,
+        radius: 0.0,
+    });
+    let status = unsafe { mobius_curve_to_matrix(&point, matrix.as_mut_ptr()) };
+    assert_eq!(status, MobiusStatus::DegenerateCurve);
+}
+
+#[test]
+fn ffi_invalid_curve_kind() {
+    let mut curve = MobiusCurve::from(Curve::Circle {
+        center: Complexf::ZERO,
+        radius: 1.0,
+    });
+    let mut matrix = MaybeUninit::<MobiusMatrix>::uninit();
+    for kind in [MobiusCurveKind::Ray as u32 + 1, u32::MAX] {
+        curve.kind = kind;
+        let status = unsafe { mobius_curve_to_matrix(&curve, matrix.as_mut_ptr()) };
+        assert_eq!(status, MobiusStatus::InvalidCurveKind);
+    }
+}
+
+#[test]
+fn point_any_nonzero_flag_is_infinite() {
+    let point = MobiusPoint {
+        is_infinite: 2,
+        value: MobiusComplex {
+            re: f64::NAN,
+            im: 0.0,
+        },
+    };
+    assert_eq!(point.to_ext_complex(), Some(ExtComplex::Inf));
+}
@@ -0,0 +1,201 @@
+//! Contains the functionality for exporting a still figure of [`Curves`][Curve] as an SVG or PNG image,
+//! e.g. from the command line without launching the app.
+
+use std::f64::consts::PI;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+/// Settings controlling how a figure is drawn.
+///
+/// Any setting not provided when deserializing falls back to its [default][FigureOptions::default].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FigureOptions {
+    /// width of the image in pixels
+    pub width: u32,
+    /// height of the image in pixels
+    pub height: u32,
+    /// the region of the complex plane to draw, which is scaled to fit inside the image
+    pub viewport: Viewport,
+    /// width of the curves in pixels
+    pub line_width: f64,
+    pub background: Rgba,
+    pub foreground: Rgba,
+}
+
+impl Default for FigureOptions {
+    fn default() -> Self {
+        // figures look the same as the frames of an exported animation
+        let animation = AnimationOptions::default();
+        FigureOptions {
+            width: animation.width,
+            height: animation.height,
+            viewport: animation.viewport,
+            line_width: animation.line_width,
+            background: animation.background,
+            foreground: animation.foreground,
+        }
+    }
+}
+
+impl FigureOptions {
+    /// Check that the options describe a figure that can actually be drawn.
+    pub fn validate(&self) -> io::Result<()> {
+        let invalid = |msg: &str| Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+        if self.width == 0 || self.height == 0 {
+            return invalid("image dimensions must be non-zero");
+        }
+        // written to also reject nan values
+        let is_positive = |x: f64| x > 0.0;
+        if !is_positive(self.viewport.width()) || !is_positive(self.viewport.height()) {
+            return invalid("viewport must have a positive width and height");
+        }
+        if !is_positive(self.line_width) {
+            return invalid("line width must be positive");
+        }
+        Ok(())
+    }
+
+    /// The region of the complex plane which is actually visible in the image.
+    ///
+    /// This contains the requested viewport, extended along one axis if its aspect ratio doesn't match the image
+    /// (in the same way as a [`Canvas`]).
+    pub fn visible_viewport(&self) -> Viewport {
+        let scale = (self.width as f64 / self.viewport.width())
+            .min(self.height as f64 / self.viewport.height());
+        let half_extent = 0.5 * Complexf::new(self.width as f64, self.height as f64) / scale;
+        let center = self.viewport.center();
+        Viewport {
+            min: center - half_extent,
+            max: center + half_extent,
+        }
+    }
+}
+
+/// Write the curves as an SVG image, clipping them to the visible region of the complex plane.
+///
+/// Fails without writing anything if the options are invalid (see [`FigureOptions::validate`]).
+pub fn write_svg<W: Write>(
+    mut writer: W,
+    curves: &[Curve],
+    options: &FigureOptions,
+) -> io::Result<()> {
+    options.validate()?;
+    let visible = options.visible_viewport();
+    // the y-axis points downwards in SVG, hence imaginary parts are negated
+    let mut path = String::new();
+    for curve in curves.iter().flat_map(|curve| clip_curve(curve, &visible)) {
+        append_svg_path(&mut path, &curve);
+    }
+
+    writeln!(
+        writer,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{} {} {} {}">"#,
+        options.width,
+        options.height,
+        visible.min.re,
+        -visible.max.im,
+        visible.width(),
+        visible.height(),
+    )?;
+    writeln!(
+        writer,
+        r#"  <rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+        visible.min.re,
+        -visible.max.im,
+        visible.width(),
+        visible.height(),
+        svg_color(options.background),
+    )?;
+    writeln!(
+        writer,
+        r#"  <path d="{}" fill="none" stroke="{}" stroke-width="{}" vector-effect="non-scaling-stroke"/>"#,
+        path.trim_end(),
+        svg_color(options.foreground),
+        options.line_width,
+    )?;
+    writeln!(writer, "</svg>")
+}
+
+/// Write the curves as a PNG image, drawn with a [`Canvas`].
+///
+/// Fails without writing anything if the options are invalid (see [`FigureOptions::validate`])
+/// or the image would have more than [`MAX_CANVAS_PIXELS`] pixels.
+pub fn write_png<W: Write>(writer: W, curves: &[Curve], options: &FigureOptions) -> io::Result<()> {
+    options.validate()?;
+    if canvas_pixels(options.width, options.height).is_none() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "image dimensions are too large",
+        ));
+    }
+    let mut canvas = Canvas::new(
+        options.width,
+        options.height,
+        &options.viewport,
+        options.background,
+    );
+    for curve in curves {
+        canvas.draw_curve(curve, options.foreground, options.line_width);
+    }
+
+    let mut encoder = png::Encoder::new(writer, options.width, options.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer
+        .write_image_data(canvas.pixels())
+        .map_err(io::Error::other)?;
+    writer.finish().map_err(io::Error::other)
+}
+
+/// Internal helper used by [`write_svg`], which appends a (clipped) curve to the data of an SVG path.
+fn append_svg_path(path: &mut String, curve: &Curve) {
+    let point = |c: Complexf| format!("{} {}", c.re, -c.im);
+    // writing to a string never fails
+    let _ = match *curve {
+        Curve::Segment { start, end } => writeln!(path, "M {} L {}", point(start), point(end)),
+        Curve::Circle { center, radius } => {
+            // a full circle can't be drawn as a single arc, so it is split into two halves
+            let left = center - radius;
+            let right = center + radius;
+            writeln!(
+                path,
+                "M {} A {radius} {radius} 0 1 1 {} A {radius} {radius} 0 1 1 {}",
+                point(right),
+                point(left),
+                point(right)
+            )
+        }
+        Curve::Arc {
+            center,
+            radius,
+            start,
+            end,
+        } => {
+            // arcs are traced counterclockwise, which is clockwise (i.e. a positive sweep) once the y-axis is flipped
+            let angle = ((end - center).arg() - (start - center).arg()).rem_euclid(2.0 * PI);
+            let large = u8::from(angle > PI);
+            writeln!(
+                path,
+                "M {} A {radius} {radius} 0 {large} 1 {}",
+                point(start),
+                point(end)
+            )
+        }
+        // lines and rays are clipped into segments
+        Curve::Line { .. } | Curve::Ray { .. } => Ok(()),
+    };
+}
+
+fn svg_color([r, g, b, a]: Rgba) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+}
+
+#[cfg(test)]
+#[path = "figure_tests.rs"]
+mod tests;
@@ -0,0 +1,126 @@
+use super::*;
+
+fn small_options() -> FigureOptions {
+    FigureOptions {
+        width: 40,
+        height: 20,
+        viewport: Viewport {
+            min: Complexf::new(-2.0, -2.0),
+            max: Complexf::new(2.0, 2.0),
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn visible_viewport_matches_aspect_ratio() {
+    let visible = small_options().visible_viewport();
+
+    assert_eq!(visible.min, Complexf::new(-4.0, -2.0));
+    assert_eq!(visible.max, Complexf::new(4.0, 2.0));
+}
+
+#[test]
+fn write_svg_curves() {
+    let curves = [
+        Curve::Circle {
+            center: Complexf::new(0.0, 1.0),
+            radius: 0.5,
+        },
+        // clipped to the visible region
+        Curve::Line {
+            point: Complexf::ZERO,
+            slope: Complexf::ONE,
+        },
+        // not visible at all
+        Curve::Circle {
+            center: Complexf::new(10.0, 0.0),
+            radius: 1.0,
+        },
+    ];
+    let mut buffer = Vec::new();
+    write_svg(&mut buffer, &curves, &small_options()).expect("writing should succeed");
+    let svg = String::from_utf8(buffer).unwrap();
+
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains(r#"viewBox="-4 -2 8 4""#));
+    assert!(svg.contains("M 0.5 -1 A 0.5 0.5 0 1 1 -0.5 -1 A 0.5 0.5 0 1 1 0.5 -1\n"));
+    assert!(svg.contains("M -4 -0 L 4 -0"));
+    assert_eq!(svg.matches('M').count(), 2);
+}
+
+#[test]
+fn write_svg_arc_direction() {
+    // the upper half of the unit circle, which is drawn above the real axis once the y-axis is flipped
+    let arc = Curve::Arc {
+        center: Complexf::ZERO,
+        radius: 1.0,
+        start: Complexf::new(1.0, 0.0),
+        end: Complexf::new(-1.0, 0.0),
+    };
+    let mut buffer = Vec::new();
+    write_svg(&mut buffer, &[arc], &small_options()).expect("writing should succeed");
+    let svg = String::from_utf8(buffer).unwrap();
+
+    assert!(svg.contains("M 1 -0 A 1 1 0 0 1 -1 -0"));
+}
+
+#[test]
+fn write_png_header() {
+    let curves = [Curve::Circle {
+        center: Complexf::ZERO,
+        radius: 1.0,
+    }];
+    let mut buffer = Vec::new();
+    write_png(&mut buffer, &curves, &small_options()).expect("writing should succeed");
+
+    let decoder = png::Decoder::new(buffer.as_slice());
+    let info = decoder
+        .read_info()
+        .expect("image should be a valid PNG")
+        .info()
+        .clone();
+    assert_eq!((info.width, info.height), (40, 20));
+}
+
+#[test]
+fn write_png_too_large() {
+    let options = FigureOptions {
+        width: 65536,
+        height: 65536,
+        ..small_options()
+    };
+    let mut buffer = Vec::new();
+    let err = write_png(&mut buffer, &[], &options).expect_err("writing should fail");
+
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn write_svg_invalid_options() {
+    let inverted = Viewport {
+        min: Complexf::new(2.0, 2.0),
+        max: Complexf::new(-2.0, -2.0),
+    };
+    for options in [
+        FigureOptions {
+            viewport: inverted,
+            ..small_options()
+        },
+        FigureOptions {
+            line_width: -1.0,
+            ..small_options()
+        },
+        FigureOptions {
+            line_width: f64::NAN,
+            ..small_options()
+        },
+    ] {
+        let mut buffer = Vec::new();
+        let err = write_svg(&mut buffer, &[], &options).expect_err("writing should fail");
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(buffer.is_empty());
+    }
+}
@@ -0,0 +1,119 @@
+//! Contains the undo/redo [`History`] of the edits made to a [`Session`].
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+/// The maximum number of edits which can be undone; older edits are forgotten.
+pub const MAX_HISTORY_LENGTH: usize = 100;
+
+/// Edits made within this long of the previous edit of the same kind are merged into it,
+/// so that the many intermediate positions of a continuous drag are undone in one go.
+pub const COALESCE_WINDOW: Duration = Duration::from_millis(500);
+
+/// A bounded history of edits, each of which stores the state from before it was made.
+///
+/// The state is stored as a whole rather than as a diff, since it is small.
+#[derive(Debug, Clone)]
+pub struct History<T> {
+    undo: VecDeque<HistoryEntry<T>>,
+    /// Most recently undone edit last.
+    redo: Vec<HistoryEntry<T>>,
+    /// The description and time of the last edit, if it may be merged with the next one.
+    last_edit: Option<(String, Instant)>,
+}
+
+#[derive(Debug, Clone)]
+struct HistoryEntry<T> {
+    description: String,
+    state: T,
+}
+
+/// The edits which can be undone and redone, as returned by [`History::summary`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistorySummary {
+    /// Descriptions of the edits which can be undone, with the next one to be undone last.
+    pub undo: Vec<String>,
+    /// Descriptions of the edits which can be redone, with the next one to be redone last.
+    pub redo: Vec<String>,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            last_edit: None,
+        }
+    }
+}
+
+impl<T> History<T> {
+    /// Records an edit, given the state from before it was made. Any undone edits can no longer be redone.
+    ///
+    /// If `coalesce` is set, the edit is merged into the previous edit when they have the same description
+    /// and were made within [`COALESCE_WINDOW`] of each other, in which case `before` is discarded.
+    pub fn record(&mut self, description: String, before: T, coalesce: bool, now: Instant) {
+        self.redo.clear();
+        let merge = matches!(
+            &self.last_edit,
+            Some((last, time)) if coalesce && *last == description && now.duration_since(*time) <= COALESCE_WINDOW
+        );
+        self.last_edit = coalesce.then(|| (description.clone(), now));
+        if merge {
+            return;
+        }
+
+        self.undo.push_back(HistoryEntry {
+            description,
+            state: before,
+        });
+        if self.undo.len() > MAX_HISTORY_LENGTH {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Steps back to the state from before the last edit, given the current state so that it can be redone.
+    ///
+    /// Returns nothing if there are no edits to undo.
+    pub fn undo(&mut self, current: T) -> Option<T> {
+        let entry = self.undo.pop_back()?;
+        self.last_edit = None;
+        self.redo.push(HistoryEntry {
+            description: entry.description,
+            state: current,
+        });
+        Some(entry.state)
+    }
+
+    /// Steps forward to the state from after the last undone edit, given the current state so that it can be undone again.
+    ///
+    /// Returns nothing if there are no edits to redo.
+    pub fn redo(&mut self, current: T) -> Option<T> {
+        let entry = self.redo.pop()?;
+        self.last_edit = None;
+        self.undo.push_back(HistoryEntry {
+            description: entry.description,
+            state: current,
+        });
+        Some(entry.state)
+    }
+
+    /// Forgets every edit.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Describes the edits which can be undone and redone.
+    pub fn summary(&self) -> HistorySummary {
+        HistorySummary {
+            undo: self.undo.iter().map(|e| e.description.clone()).collect(),
+            redo: self.redo.iter().map(|e| e.description.clone()).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "history_tests.rs"]
+mod tests;
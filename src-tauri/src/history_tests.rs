@@ -0,0 +1,85 @@
+use super::*;
+
+#[test]
+fn history_undo_redo() {
+    let mut history = History::default();
+    let now = Instant::now();
+    history.record("first".to_owned(), 0, false, now);
+    history.record("second".to_owned(), 1, false, now);
+
+    assert_eq!(history.undo(2), Some(1));
+    assert_eq!(history.undo(1), Some(0));
+    assert_eq!(history.undo(0), None);
+    assert_eq!(
+        history.summary(),
+        HistorySummary {
+            undo: vec![],
+            redo: vec!["second".to_owned(), "first".to_owned()]
+        }
+    );
+
+    assert_eq!(history.redo(0), Some(1));
+    assert_eq!(history.redo(1), Some(2));
+    assert_eq!(history.redo(2), None);
+}
+
+#[test]
+fn history_edit_clears_redo() {
+    let mut history = History::default();
+    let now = Instant::now();
+    history.record("first".to_owned(), 0, false, now);
+    history.undo(1);
+    history.record("second".to_owned(), 0, false, now);
+
+    assert_eq!(history.redo(3), None);
+    assert_eq!(history.summary().undo, ["second"]);
+}
+
+#[test]
+fn history_coalesces_continuous_edits() {
+    let mut history = History::default();
+    let start = Instant::now();
+    let step = COALESCE_WINDOW / 2;
+
+    // a drag made up of many edits is undone in one go
+    for i in 0..5 {
+        history.record("drag".to_owned(), i, true, start + step * i);
+    }
+    // but not merged with an edit of another kind, nor one after a pause
+    history.record("other".to_owned(), 5, true, start + step * 5);
+    history.record("other".to_owned(), 6, true, start + step * 8);
+
+    assert_eq!(history.summary().undo, ["drag", "other", "other"]);
+    assert_eq!(history.undo(7), Some(6));
+    assert_eq!(history.undo(6), Some(5));
+    assert_eq!(history.undo(5), Some(0));
+}
+
+#[test]
+fn history_undo_stops_coalescing() {
+    let mut history = History::default();
+    let now = Instant::now();
+    history.record("drag".to_owned(), 0, true, now);
+    history.undo(1);
+    history.record("drag".to_owned(), 0, true, now);
+    history.record("drag".to_owned(), 1, true, now);
+
+    assert_eq!(history.summary().undo.len(), 1);
+    assert_eq!(history.undo(2), Some(0));
+}
+
+#[test]
+fn history_is_bounded() {
+    let mut history = History::default();
+    let now = Instant::now();
+    for i in 0..MAX_HISTORY_LENGTH + 10 {
+        history.record(i.to_string(), i, false, now);
+    }
+
+    assert_eq!(history.summary().undo.len(), MAX_HISTORY_LENGTH);
+    let mut state = MAX_HISTORY_LENGTH + 10;
+    while let Some(previous) = history.undo(state) {
+        state = previous;
+    }
+    assert_eq!(state, 10);
+}
@@ -0,0 +1,287 @@
+//! Contains the functionality needed to restrict Mobius transformations to the isometries of the hyperbolic plane.
+//!
+//! The isometries of the hyperbolic plane are exactly the Mobius transformations preserving a model of it,
+//! such as the Poincaré disk or the upper half-plane.
+//! All computations are done by conjugating the model onto the standard (unit) Poincaré disk,
+//! whose isometries are the matrices in SU(1, 1) (up to a scalar multiple).
+
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use nalgebra::Matrix2;
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+/// Scale of the hyperbolic models in the complex plane.
+///
+/// The Poincaré disk is the disk of this radius centered at the origin,
+/// and the upper half-plane is scaled so that the point `MODEL_SCALE * i` corresponds to the center of the disk.
+/// The standard (unit) models would be too small to be seen at the frontend's zoom level.
+pub const MODEL_SCALE: f64 = 10.0;
+
+/// The models of the hyperbolic plane which transformations can be restricted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HyperbolicModel {
+    /// The Poincaré disk `|z| < MODEL_SCALE`, whose isometries are the automorphisms of the disk.
+    Disk,
+    /// The upper half-plane `Im(z) > 0`, whose isometries are the transformations in PSL(2, R).
+    HalfPlane,
+}
+
+impl HyperbolicModel {
+    /// The Mobius transformation mapping this model onto the unit disk.
+    fn to_unit_disk(self) -> Matrix2<Complexf> {
+        let scale = Complexf::from(MODEL_SCALE);
+        match self {
+            // z -> z / s
+            Self::Disk => Matrix2::new(Complexf::ONE, Complexf::ZERO, Complexf::ZERO, scale),
+            // the Cayley transform z -> (z - si) / (z + si)
+            Self::HalfPlane => Matrix2::new(
+                Complexf::ONE,
+                -scale * Complexf::I,
+                Complexf::ONE,
+                scale * Complexf::I,
+            ),
+        }
+    }
+
+    /// Project a Mobius transformation onto the nearest isometry of this model.
+    ///
+    /// The transformation is conjugated onto the unit disk and normalized to have determinant 1,
+    /// then its entries are replaced with the closest ones having the form of an element of SU(1, 1).
+    /// Isometries are left unchanged by this projection.
+    ///
+    /// Returns nothing if the transformation is too far from an isometry for the projection to be well-defined,
+    /// e.g. if it maps the interior of the model mostly onto its exterior.
+    pub fn project_isometry(self, tform: &Matrix2<Complexf>) -> Option<Matrix2<Complexf>> {
+        let to_disk = self.to_unit_disk();
+        let from_disk = math::invert_mobius_tform(&to_disk);
+        let m = to_disk * tform * from_disk;
+        let m = m / m.determinant().sqrt();
+
+        // elements of SU(1, 1) have the form [[a, b], [conj(b), conj(a)]], with determinant |a|^2 - |b|^2 = 1
+        let a = 0.5 * (m.m11 + m.m22.conj());
+        let b = 0.5 * (m.m12 + m.m21.conj());
+        let det = a.norm_sqr() - b.norm_sqr();
+        if det <= f64::EPSILON * 100.0 {
+            return None;
+        }
+
+        let isometry = Matrix2::new(a, b, b.conj(), a.conj()).unscale(det.sqrt());
+        Some(from_disk * isometry * to_disk)
+    }
+
+    /// Check whether a point lies strictly inside this model.
+    pub fn contains(self, p: &ExtComplex) -> bool {
+        matches!(math::apply_mobius_tform(&self.to_unit_disk(), p), ExtComplex::Val(z) if z.norm_sqr() < 1.0)
+    }
+
+    /// Compute the hyperbolic distance between two points.
+    ///
+    /// Returns nothing if either of the points does not lie inside this model.
+    pub fn distance(self, p: &ExtComplex, q: &ExtComplex) -> Option<f64> {
+        let to_disk = self.to_unit_disk();
+        match (
+            math::apply_mobius_tform(&to_disk, p),
+            math::apply_mobius_tform(&to_disk, q),
+        ) {
+            (ExtComplex::Val(z), ExtComplex::Val(w))
+                if z.norm_sqr() < 1.0 && w.norm_sqr() < 1.0 =>
+            {
+                // distance in the unit disk is 2 * artanh(|z - w| / |1 - conj(z) * w|)
+                let ratio = (z - w).norm() / (Complexf::ONE - z.conj() * w).norm();
+                Some(2.0 * ratio.atanh())
+            }
+            _ => None,
+        }
+    }
+
+    /// Generates the 2x2 matrix representations of a grid of geodesics in this model.
+    ///
+    /// In the unit disk, the grid consists of the two diameters along the real and imaginary axes,
+    /// along with the geodesics perpendicular to either diameter spaced at regular hyperbolic distances.
+    /// The same grid is mapped onto the other models.
+    /// All the geodesics are circles/lines orthogonal to the boundary of the model.
+    ///
+    /// # Params
+    ///  - `spacing`: hyperbolic distance between consecutive parallel geodesics
+    ///  - `count`: number of geodesics on each side of either diameter
+    pub fn geodesic_gridlines(self, spacing: f64, count: usize) -> Vec<Matrix2<Complexf>> {
+        let mut curves = Vec::with_capacity(4 * count + 2);
+        for axis in [Complexf::ONE, Complexf::I] {
+            // the diameter along the axis
+            curves.push(math::curve_to_matrix(&Curve::Line {
+                point: Complexf::ZERO,
+                slope: axis,
+            }));
+
+            // a geodesic crossing the diameter perpendicularly at the point t (for 0 < |t| < 1)
+            // is the circle centered at (1 + t^2) / 2t with radius (1 - t^2) / 2|t|
+            for i in (1..=count).flat_map(|i| [-(i as f64), i as f64]) {
+                let t = (0.5 * spacing * i).tanh();
+                curves.push(math::curve_to_matrix(&Curve::Circle {
+                    center: axis * ((1.0 + t * t) / (2.0 * t)),
+                    radius: (1.0 - t * t) / (2.0 * t.abs()),
+                }));
+            }
+        }
+
+        // curves are mapped onto the model by multiplying with the inverse (see `generate_mobius_transformation`)
+        let to_disk = self.to_unit_disk();
+        curves.iter().map(|m| m * to_disk).collect()
+    }
+
+    /// Generates the edges of the regular tiling of this model by `p`-gons, `q` of which meet at each vertex
+    /// (i.e. the tiling with Schläfli symbol `{p, q}`).
+    ///
+    /// In the unit disk, the central polygon of the tiling is centered at the origin with a vertex on the positive real axis.
+    /// The tiling is built outwards by repeatedly applying the half-turns about the midpoints of the edges,
+    /// each of which maps a polygon onto its neighbor across that edge.
+    /// Only the polygons which can be reached from the central polygon by crossing at most `depth` edges are included,
+    /// and edges shared by two polygons are only returned once.
+    /// The same tiling is mapped onto the other models.
+    ///
+    /// Returns nothing unless `p, q >= 3` and `(p - 2)(q - 2) > 4`, since the tiling is otherwise spherical or Euclidean
+    /// rather than hyperbolic.
+    pub fn regular_tiling(self, p: usize, q: usize, depth: usize) -> Option<Vec<ThreePointArc>> {
+        if p < 3 || q < 3 || (p - 2).checked_mul(q - 2).is_some_and(|n| n <= 4) {
+            return None;
+        }
+
+        // hyperbolic distances from the center of the central polygon to its vertices and to the midpoints of its edges,
+        // which correspond to the euclidean distances tanh(d / 2) in the unit disk
+        let half_angle = PI / p as f64;
+        let vertex_dist = (1.0 / (half_angle.tan() * (PI / q as f64).tan())).acosh();
+        let midpoint_dist = ((PI / q as f64).cos() / half_angle.sin()).acosh();
+        let vertex = |k: usize| {
+            Complexf::from_polar((0.5 * vertex_dist).tanh(), 2.0 * half_angle * k as f64)
+        };
+        let midpoint = |k: usize| {
+            Complexf::from_polar(
+                (0.5 * midpoint_dist).tanh(),
+                half_angle * (2 * k + 1) as f64,
+            )
+        };
+
+        let edges = (0..p)
+            .map(|k| (vertex(k), midpoint(k), vertex((k + 1) % p)))
+            .collect::<Vec<_>>();
+        let half_turns = (0..p)
+            .map(|k| {
+                // conjugate the half-turn z -> -z by the automorphism of the disk mapping the midpoint to the origin
+                let m = midpoint(k);
+                let to_origin = Matrix2::new(Complexf::ONE, -m, -m.conj(), Complexf::ONE);
+                let half_turn = Matrix2::new(
+                    -Complexf::ONE,
+                    Complexf::ZERO,
+                    Complexf::ZERO,
+                    Complexf::ONE,
+                );
+                math::invert_mobius_tform(&to_origin) * half_turn * to_origin
+            })
+            .collect::<Vec<_>>();
+
+        // the image of a point w under an isometry g is identified by its coordinates in the hyperboloid model (see [`PointSet`]);
+        // since g has determinant 1, they can be worked out without the cancellation in 1 - |g(w)|^2 near the boundary
+        let hyperboloid = |g: &Matrix2<Complexf>, w: Complexf| {
+            let (num, den) = (g.m11 * w + g.m12, g.m21 * w + g.m22);
+            let s = 1.0 - w.norm_sqr();
+            let x = 2.0 * num * den.conj() / s;
+            [(num.norm_sqr() + den.norm_sqr()) / s, x.re, x.im]
+        };
+        let apply = |g: &Matrix2<Complexf>, z: Complexf| (g.m11 * z + g.m12) / (g.m21 * z + g.m22);
+        let point = ExtComplex::from_complex;
+
+        // breadth-first search over the polygons, each of which is represented by the isometry mapping the central polygon onto it;
+        // edges are identified by their midpoints and polygons by their centers
+        let mut seen_edges = PointSet::default();
+        let mut seen_polygons = PointSet::default();
+        seen_polygons.insert(hyperboloid(&Matrix2::identity(), Complexf::ZERO));
+        let mut layer = vec![Matrix2::identity()];
+        let mut arcs = Vec::new();
+        for level in 0..=depth {
+            let mut next_layer = Vec::new();
+            for g in &layer {
+                for (&(start, via, end), half_turn) in edges.iter().zip(&half_turns) {
+                    if seen_edges.insert(hyperboloid(g, via)) {
+                        // edges so close to the boundary that their points can't be computed are left out
+                        if let (Some(start), Some(via), Some(end)) = (
+                            point(apply(g, start)),
+                            point(apply(g, via)),
+                            point(apply(g, end)),
+                        ) {
+                            arcs.push(ThreePointArc { start, via, end });
+                        }
+                    }
+
+                    if level < depth {
+                        // normalize the determinant to stop the entries from growing as isometries are composed
+                        let neighbor = g * half_turn;
+                        let neighbor = neighbor / neighbor.determinant().sqrt();
+                        if seen_polygons.insert(hyperboloid(&neighbor, Complexf::ZERO)) {
+                            next_layer.push(neighbor);
+                        }
+                    }
+                }
+            }
+            layer = next_layer;
+        }
+
+        let from_disk = math::invert_mobius_tform(&self.to_unit_disk());
+        Some(
+            arcs.iter()
+                .map(|arc| math::apply_mobius_tform_arc(&from_disk, arc))
+                .collect(),
+        )
+    }
+}
+
+/// A set of points in the hyperboloid model of the hyperbolic plane, which treats points within [`PointSet::TOLERANCE`]
+/// of each other as the same point, used by [`HyperbolicModel::regular_tiling`] to find the edges and polygons it has seen.
+///
+/// Points a hyperbolic distance `d` apart are at least `d` apart in the hyperboloid model,
+/// rather than crowding together towards the boundary like in the Poincaré disk, so a fixed tolerance works everywhere.
+/// The points are bucketed into cubes whose side is the tolerance, so only the neighboring cubes have to be searched.
+#[derive(Debug, Default)]
+struct PointSet {
+    cells: HashMap<[i64; 3], Vec<[f64; 3]>>,
+}
+
+impl PointSet {
+    /// Distinct edge midpoints and polygon centers of a regular tiling are always more than 0.1 apart,
+    /// whereas rounding errors are many orders of magnitude smaller.
+    const TOLERANCE: f64 = 0.01;
+
+    /// Add a point to the set, returning whether it was not already in the set.
+    fn insert(&mut self, x: [f64; 3]) -> bool {
+        let cell = x.map(|x| (x / Self::TOLERANCE).floor() as i64);
+        let neighbors = (0..27).map(|i| [i % 3, i / 3 % 3, i / 9].map(|offset| offset - 1));
+        let is_near = |y: &[f64; 3]| {
+            (0..3).map(|i| (x[i] - y[i]).powi(2)).sum::<f64>() < Self::TOLERANCE.powi(2)
+        };
+        for offset in neighbors {
+            let neighbor = [0, 1, 2].map(|i| cell[i] + offset[i]);
+            if self
+                .cells
+                .get(&neighbor)
+                .is_some_and(|points| points.iter().any(is_near))
+            {
+                return false;
+            }
+        }
+        self.cells.entry(cell).or_default().push(x);
+        true
+    }
+}
+
+/// Default spacing between geodesics in the geodesic gridline families.
+pub const GEODESIC_SPACING: f64 = 0.4;
+
+/// Default number of geodesics on each side of the axes in the geodesic gridline families.
+pub const GEODESIC_COUNT: usize = 10;
+
+#[cfg(test)]
+#[path = "hyperbolic_tests.rs"]
+mod tests;
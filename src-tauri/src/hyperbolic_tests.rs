@@ -0,0 +1,289 @@
+use paste::paste;
+
+use super::*;
+
+// compares two points, allowing for floating point error
+fn assert_point_eq(p: ExtComplex, q: ExtComplex) {
+    match (p, q) {
+        (ExtComplex::Val(z), ExtComplex::Val(w)) => {
+            approx::assert_abs_diff_eq!(z, w, epsilon = 1e-9)
+        }
+        (p, q) => assert_eq!(p, q),
+    }
+}
+
+/* project_isometry tests */
+
+// test that projecting an isometry leaves it unchanged
+macro_rules! project_isometry_fixed {
+    ($case:ident, $model:expr, $inputs:expr, $outputs:expr) => {
+        paste! {
+            #[test]
+            fn [<project_isometry_fixed_ $case>]() {
+                let inputs = $inputs;
+                let outputs = $outputs;
+                let tform = math::compute_mobius_tform(&inputs, &outputs).unwrap();
+                let isometry = $model.project_isometry(&tform).expect("projection should succeed");
+
+                for (p, q) in inputs.iter().zip(outputs) {
+                    assert_point_eq(math::apply_mobius_tform(&isometry, p), q);
+                }
+            }
+        }
+    };
+}
+
+// rotation about the origin
+project_isometry_fixed!(
+    disk_rotation,
+    HyperbolicModel::Disk,
+    [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(2.0, 0.0),
+        ExtComplex::Inf
+    ],
+    [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(0.0, 2.0),
+        ExtComplex::Inf
+    ]
+);
+// scaling about the origin
+project_isometry_fixed!(
+    half_plane_dilation,
+    HyperbolicModel::HalfPlane,
+    [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(1.0, 1.0),
+        ExtComplex::Inf
+    ],
+    [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(3.0, 3.0),
+        ExtComplex::Inf
+    ]
+);
+// translation along the real axis
+project_isometry_fixed!(
+    half_plane_translation,
+    HyperbolicModel::HalfPlane,
+    [
+        ExtComplex::new(0.0, 1.0),
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::Inf
+    ],
+    [
+        ExtComplex::new(5.0, 1.0),
+        ExtComplex::new(6.0, 0.0),
+        ExtComplex::Inf
+    ]
+);
+
+#[test]
+fn project_isometry_preserves_disk() {
+    // a translation does not preserve the disk, but its projection should
+    let tform = Matrix2::new(
+        Complexf::ONE,
+        Complexf::new(3.0, -1.0),
+        Complexf::ZERO,
+        Complexf::ONE,
+    );
+    let isometry = HyperbolicModel::Disk
+        .project_isometry(&tform)
+        .expect("projection should succeed");
+
+    for i in 0..8 {
+        let angle = i as f64 * std::f64::consts::FRAC_PI_4;
+        let p = ExtComplex::Val(Complexf::from_polar(MODEL_SCALE, angle));
+        match math::apply_mobius_tform(&isometry, &p) {
+            ExtComplex::Val(z) => {
+                approx::assert_relative_eq!(z.norm(), MODEL_SCALE, epsilon = 1e-9)
+            }
+            ExtComplex::Inf => panic!("boundary should not be mapped to infinity"),
+        }
+    }
+}
+
+#[test]
+fn project_isometry_failure() {
+    // inversion z -> s^2 / z swaps the interior and exterior of the disk
+    let tform = Matrix2::new(
+        Complexf::ZERO,
+        Complexf::from(MODEL_SCALE * MODEL_SCALE),
+        Complexf::ONE,
+        Complexf::ZERO,
+    );
+
+    assert!(HyperbolicModel::Disk.project_isometry(&tform).is_none());
+}
+
+/* distance tests */
+
+#[test]
+fn distance_disk() {
+    let p = ExtComplex::new(0.0, 0.0);
+    let q = ExtComplex::new(MODEL_SCALE * 0.5f64.tanh(), 0.0);
+    let distance = HyperbolicModel::Disk.distance(&p, &q).unwrap();
+
+    approx::assert_relative_eq!(distance, 1.0, epsilon = 1e-12);
+}
+
+#[test]
+fn distance_half_plane() {
+    // distance between points on the imaginary axis is the log of the ratio of their heights
+    let p = ExtComplex::new(0.0, 2.0);
+    let q = ExtComplex::new(0.0, 2.0 * std::f64::consts::E);
+    let distance = HyperbolicModel::HalfPlane.distance(&p, &q).unwrap();
+
+    approx::assert_relative_eq!(distance, 1.0, epsilon = 1e-12);
+}
+
+#[test]
+fn distance_outside_model() {
+    let p = ExtComplex::new(0.0, 1.0);
+    let q = ExtComplex::new(0.0, -1.0);
+
+    assert!(HyperbolicModel::HalfPlane.distance(&p, &q).is_none());
+    assert!(HyperbolicModel::HalfPlane
+        .distance(&p, &ExtComplex::Inf)
+        .is_none());
+    assert!(HyperbolicModel::Disk
+        .distance(&p, &ExtComplex::Inf)
+        .is_none());
+}
+
+/* geodesic_gridlines tests */
+
+// checks that a curve is a geodesic of the disk, i.e. that it is orthogonal to the boundary
+fn assert_disk_geodesic(curve: &Curve) {
+    match *curve {
+        // circles orthogonal to the boundary satisfy |c|^2 = r^2 + s^2
+        Curve::Circle { center, radius } | Curve::Arc { center, radius, .. } => {
+            approx::assert_relative_eq!(
+                center.norm_sqr(),
+                radius * radius + MODEL_SCALE * MODEL_SCALE,
+                max_relative = 1e-9
+            )
+        }
+        // lines orthogonal to the boundary pass through the origin
+        Curve::Line { point, slope } => {
+            approx::assert_abs_diff_eq!((point * slope.conj()).im, 0.0, epsilon = 1e-9)
+        }
+        Curve::Segment { start, end } => {
+            approx::assert_abs_diff_eq!((start * end.conj()).im, 0.0, epsilon = 1e-9)
+        }
+        curve => panic!("expected a geodesic, got {curve:?}"),
+    }
+}
+
+#[test]
+fn geodesic_gridlines_orthogonal_disk() {
+    let curves = HyperbolicModel::Disk.geodesic_gridlines(0.5, 4);
+    assert_eq!(curves.len(), 18);
+
+    for m in &curves {
+        assert_disk_geodesic(&math::matrix_to_curve(m));
+    }
+}
+
+#[test]
+fn geodesic_gridlines_orthogonal_half_plane() {
+    let curves = HyperbolicModel::HalfPlane.geodesic_gridlines(0.5, 4);
+
+    for m in &curves {
+        match math::matrix_to_curve(m) {
+            // circles orthogonal to the boundary are centered on the real axis
+            Curve::Circle { center, .. } => {
+                approx::assert_abs_diff_eq!(center.im, 0.0, epsilon = 1e-9)
+            }
+            // lines orthogonal to the boundary are vertical
+            Curve::Line { slope, .. } => approx::assert_abs_diff_eq!(slope.re, 0.0, epsilon = 1e-9),
+            curve => panic!("expected a circle or line, got {curve:?}"),
+        }
+    }
+}
+
+/* regular_tiling tests */
+
+#[test]
+fn regular_tiling_edge_count() {
+    // the central heptagon, plus the 7 heptagons around it which share an edge with the center and with both of their neighbors
+    assert_eq!(
+        HyperbolicModel::Disk.regular_tiling(7, 3, 0).unwrap().len(),
+        7
+    );
+    assert_eq!(
+        HyperbolicModel::Disk.regular_tiling(7, 3, 1).unwrap().len(),
+        7 + 7 * 6 - 7
+    );
+    // 4 squares meet at each of the vertices of the central pentagon, so the neighbors only share an edge with the center
+    assert_eq!(
+        HyperbolicModel::Disk.regular_tiling(5, 4, 1).unwrap().len(),
+        5 + 5 * 4
+    );
+}
+
+#[test]
+fn regular_tiling_geodesic_edges() {
+    for arc in HyperbolicModel::Disk.regular_tiling(7, 3, 3).unwrap() {
+        for p in [arc.start, arc.via, arc.end] {
+            assert!(
+                HyperbolicModel::Disk.contains(&p),
+                "{p:?} should lie inside the disk"
+            );
+        }
+        for curve in math::arc_to_curves(&arc) {
+            assert_disk_geodesic(&curve);
+        }
+    }
+}
+
+#[test]
+fn regular_tiling_edge_length() {
+    // all edges of a regular tiling have the same hyperbolic length
+    let arcs = HyperbolicModel::HalfPlane.regular_tiling(4, 5, 2).unwrap();
+    let model = HyperbolicModel::HalfPlane;
+    let length = model.distance(&arcs[0].start, &arcs[0].end).unwrap();
+
+    for arc in &arcs {
+        approx::assert_relative_eq!(
+            model.distance(&arc.start, &arc.end).unwrap(),
+            length,
+            max_relative = 1e-6
+        );
+        // the via point is the midpoint of the edge
+        approx::assert_relative_eq!(
+            model.distance(&arc.start, &arc.via).unwrap(),
+            0.5 * length,
+            max_relative = 1e-6
+        );
+    }
+}
+
+#[test]
+fn regular_tiling_deep() {
+    // points are crowded against the boundary of the disk after a few layers, but edges must still be told apart
+    // (the counts come from the same search done with 40 significant digits)
+    assert_eq!(
+        HyperbolicModel::Disk.regular_tiling(7, 3, 7).unwrap().len(),
+        19698
+    );
+    assert_eq!(
+        HyperbolicModel::Disk.regular_tiling(5, 4, 8).unwrap().len(),
+        28880
+    );
+    assert_eq!(
+        HyperbolicModel::HalfPlane
+            .regular_tiling(3, 7, 8)
+            .unwrap()
+            .len(),
+        537
+    );
+}
+
+#[test]
+fn regular_tiling_not_hyperbolic() {
+    for (p, q) in [(4, 4), (3, 6), (5, 3), (2, 100), (100, 0)] {
+        assert_eq!(HyperbolicModel::Disk.regular_tiling(p, q, 1), None);
+    }
+}
@@ -6,8 +6,62 @@ pub use model::*;
 mod math;
 pub use math::*;
 
+mod family;
+pub use family::*;
+
 mod data;
 pub use data::*;
 
+mod custom;
+pub use custom::*;
+
+mod hyperbolic;
+pub use hyperbolic::*;
+
+mod clip;
+pub use clip::*;
+
+mod batch;
+pub use batch::*;
+
+#[cfg(feature = "render")]
+mod render;
+#[cfg(feature = "render")]
+pub use render::*;
+
+#[cfg(feature = "render")]
+mod animation;
+#[cfg(feature = "render")]
+pub use animation::*;
+
+#[cfg(feature = "render")]
+mod figure;
+#[cfg(feature = "render")]
+pub use figure::*;
+
 mod api;
 pub use api::*;
+
+mod history;
+pub use history::*;
+
+mod session;
+pub use session::*;
+
+mod scene;
+pub use scene::*;
+
+mod share;
+pub use share::*;
+
+mod presets;
+pub use presets::*;
+
+mod rpc;
+pub use rpc::*;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+#[cfg(feature = "tauri")]
+pub mod commands;
@@ -1,11 +1,47 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+use std::sync::Mutex;
+
 use mobius_app as mb;
+use tauri::Manager;
 
 fn main() {
     tauri::Builder::default()
-        .invoke_handler(tauri::generate_handler![mb::generate_mobius_transformation])
+        .setup(|app| {
+            // user-defined families are optional, so fall back to just the built-in families if they can't be loaded
+            let mut registry = mb::CurveFamilyRegistry::default();
+            if let Some(config_dir) = app.path_resolver().app_config_dir() {
+                let path = config_dir.join(mb::CUSTOM_FAMILIES_FILE);
+                if let Err(err) = mb::load_custom_families(&path, &mut registry) {
+                    eprintln!("{err}");
+                }
+            }
+            app.manage(registry);
+            app.manage(mb::RequestSequence::default());
+            app.manage(Mutex::new(mb::Session::default()));
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            mb::commands::generate_mobius_transformation,
+            mb::commands::generate_hyperbolic_transformation,
+            mb::commands::export_mobius_animation,
+            mb::commands::map_points,
+            mb::commands::list_curve_families,
+            mb::commands::start_session,
+            mb::commands::set_control_point,
+            mb::commands::toggle_family,
+            mb::commands::set_tolerances,
+            mb::commands::undo,
+            mb::commands::redo,
+            mb::commands::history,
+            mb::commands::save_scene,
+            mb::commands::load_scene,
+            mb::commands::share_scene,
+            mb::commands::load_share_code,
+            mb::commands::list_presets,
+            mb::commands::load_preset
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
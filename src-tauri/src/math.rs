@@ -13,46 +13,41 @@ use super::*;
 ///
 /// It is assumed that the input matrix is not singular (i.e., the corresponding curve is not degenerate).
 pub fn matrix_to_curve(m: &Matrix2<Complexf>) -> Curve {
-    // de-structure matrix entries for convenience
-    // the number indicates the column number of the entry
-    let min0;
-    let min1;
-    let maj0;
-    let maj1;
-
     // squared magnitudes of the leading entries in each row
-    let mut min_norm_sqr = m.m11.norm_sqr();
-    let mut maj_norm_sqr = m.m21.norm_sqr();
+    let (norm_sqr1, norm_sqr2) = (m.m11.norm_sqr(), m.m21.norm_sqr());
 
     // store the row with the smaller leading coefficient with prefix "min", and the other with prefix "maj";
     // by knowing which leading entry is smaller,
-    // can potentially control for floating point error in any future algorithm optimizations
-    if min_norm_sqr <= maj_norm_sqr {
-        min0 = m.m11;
-        min1 = m.m12;
-        maj0 = m.m21;
-        maj1 = m.m22;
+    // can potentially control for floating point error in any future algorithm optimizations;
+    // the number indicates the column number of the entry
+    let swap = norm_sqr1 > norm_sqr2;
+    let pick = |a: Complexf, b: Complexf| if swap { b } else { a };
+    let (min0, min1) = (pick(m.m11, m.m21), pick(m.m12, m.m22));
+    let (maj0, maj1) = (pick(m.m21, m.m11), pick(m.m22, m.m12));
+    let (min_norm_sqr, maj_norm_sqr) = if swap {
+        (norm_sqr2, norm_sqr1)
     } else {
-        std::mem::swap(&mut min_norm_sqr, &mut maj_norm_sqr);
-        maj0 = m.m11;
-        maj1 = m.m12;
-        min0 = m.m21;
-        min1 = m.m22;
-    }
+        (norm_sqr1, norm_sqr2)
+    };
+
+    // every candidate curve is computed up front, so that the only branch is the choice between them at the end,
+    // which keeps the loop converting a whole batch of curves (see `CurveMatrixBatch`) from branching any further
+    let maj_pt = -maj1 / maj0;
+    let min_pt = -min1 / min0;
+    let ratio_sqr = min_norm_sqr / maj_norm_sqr; // note that ratio_sqr < 1
+    let affine_coeff = (1.0 - ratio_sqr).recip(); // used in the affine combination to compute the center
+
+    // `Complexf::norm` calls out to `hypot`, which can't be vectorized
+    let distance = (maj_pt - min_pt).norm_sqr().sqrt();
 
     // if one of the leading coefficients is zero, the matrix describes a circle
     if approx::abs_diff_eq!(0.0, min_norm_sqr) {
         return Curve::Circle {
-            center: -maj1 / maj0,
+            center: maj_pt,
             radius: (min1.norm_sqr() / maj_norm_sqr).sqrt(),
         };
     }
 
-    // otherwise, neither of the leading coefficients is zero
-    let maj_pt = -maj1 / maj0;
-    let min_pt = -min1 / min0;
-    let ratio_sqr = min_norm_sqr / maj_norm_sqr; // note that ratio_sqr < 1
-
     // if leading coefficients have (approximately) the same norm, the matrix describes a line
     // NOTE: can fine-tune this threshold to prevent circles with excessively large radii from being generated
     if approx::relative_eq!(1.0, ratio_sqr, max_relative = 0.001) {
@@ -63,14 +58,140 @@ pub fn matrix_to_curve(m: &Matrix2<Complexf>) -> Curve {
     }
 
     // otherwise, the matrix describes a circle
-    let ratio = ratio_sqr.sqrt();
-    let affine_coeff = (1.0 - ratio_sqr).recip(); // used in the affine combination to compute the center
     return Curve::Circle {
         center: affine_coeff * maj_pt + (1.0 - affine_coeff) * min_pt,
-        radius: ratio * affine_coeff * (maj_pt - min_pt).norm(),
+        radius: ratio_sqr.sqrt() * affine_coeff * distance,
     };
 }
 
+/// Converts a [`Curve`] into its 2x2 matrix representation.
+///
+/// This is the inverse of [`matrix_to_curve`], up to a scalar multiple of the matrix.
+/// Pieces of circles/lines (i.e. arcs, segments and rays) are converted into the matrix of the whole circle/line.
+/// It is assumed that the curve is not degenerate (i.e. circles have a non-zero radius and lines have a non-zero slope).
+pub fn curve_to_matrix(curve: &Curve) -> Matrix2<Complexf> {
+    match *curve {
+        // equation |(z - center) / radius| = 1
+        Curve::Circle { center, radius } | Curve::Arc { center, radius, .. } => Matrix2::new(
+            Complexf::ONE,
+            -center,
+            Complexf::ZERO,
+            Complexf::from(radius),
+        ),
+        // a line is the set of points equidistant from two points reflected across it,
+        // i.e. the equation |(z - (point + normal)) / (z - (point - normal))| = 1
+        Curve::Line { point, slope } => {
+            let normal = slope * Complexf::I;
+            Matrix2::new(
+                Complexf::ONE,
+                -(point + normal),
+                Complexf::ONE,
+                -(point - normal),
+            )
+        }
+        Curve::Segment { start, end } => curve_to_matrix(&Curve::Line {
+            point: start,
+            slope: end - start,
+        }),
+        Curve::Ray { point, direction } => curve_to_matrix(&Curve::Line {
+            point,
+            slope: direction,
+        }),
+    }
+}
+
+/// Converts a [`ThreePointArc`] into the [`Curves`][Curve] making it up.
+///
+/// An arc of a circle is converted into a [`Curve::Arc`], and an arc of a line is converted into either
+/// a [`Curve::Segment`], or a pair of [`Curve::Ray`] if it passes through infinity.
+/// If one of the endpoints is infinite, the arc is converted into a single [`Curve::Ray`].
+///
+/// Degenerate arcs (e.g. those with more than one infinite point) are converted into nothing.
+pub fn arc_to_curves(arc: &ThreePointArc) -> Vec<Curve> {
+    use ExtComplex::{Inf, Val};
+
+    match (arc.start, arc.via, arc.end) {
+        (Val(start), Val(via), Val(end)) => {
+            let u = via - start;
+            let v = end - start;
+            // twice the signed area of the triangle (start, via, end), which is positive when it is oriented counterclockwise
+            let cross = (u.conj() * v).im;
+
+            // NOTE: can fine-tune this threshold to prevent arcs with excessively large radii from being generated
+            if cross.abs() <= 1e-9 * u.norm() * v.norm() {
+                let dot = ((start - via).conj() * (end - via)).re;
+                return if start == end {
+                    vec![]
+                } else if dot <= 0.0 {
+                    // via lies between the endpoints
+                    vec![Curve::Segment { start, end }]
+                } else {
+                    // via lies outside the endpoints, so the arc passes through infinity
+                    vec![
+                        Curve::Ray {
+                            point: start,
+                            direction: start - end,
+                        },
+                        Curve::Ray {
+                            point: end,
+                            direction: end - start,
+                        },
+                    ]
+                };
+            }
+
+            // circumcenter of the triangle, relative to start
+            let offset = (u.norm_sqr() * v - v.norm_sqr() * u) / Complexf::new(0.0, 2.0 * cross);
+            let center = start + offset;
+            let radius = offset.norm();
+            // points on a circle are ordered counterclockwise exactly when the triangle they form is
+            if cross > 0.0 {
+                vec![Curve::Arc {
+                    center,
+                    radius,
+                    start,
+                    end,
+                }]
+            } else {
+                vec![Curve::Arc {
+                    center,
+                    radius,
+                    start: end,
+                    end: start,
+                }]
+            }
+        }
+        (Val(start), Inf, Val(end)) if start != end => vec![
+            Curve::Ray {
+                point: start,
+                direction: start - end,
+            },
+            Curve::Ray {
+                point: end,
+                direction: end - start,
+            },
+        ],
+        (Inf, Val(via), Val(end)) if via != end => vec![Curve::Ray {
+            point: end,
+            direction: via - end,
+        }],
+        (Val(start), Val(via), Inf) if via != start => vec![Curve::Ray {
+            point: start,
+            direction: via - start,
+        }],
+        _ => vec![],
+    }
+}
+
+/// Apply a Mobius transformation to each of the points defining a [`ThreePointArc`].
+pub fn apply_mobius_tform_arc(tform: &Matrix2<Complexf>, arc: &ThreePointArc) -> ThreePointArc {
+    ThreePointArc {
+        start: apply_mobius_tform(tform, &arc.start),
+        via: apply_mobius_tform(tform, &arc.via),
+        end: apply_mobius_tform(tform, &arc.end),
+    }
+}
+
 /// Apply a Mobius transformation to a point in the extended complex plane.
 pub fn apply_mobius_tform(tform: &Matrix2<Complexf>, p: &ExtComplex) -> ExtComplex {
     let mapped_pt = match *p {
@@ -81,12 +202,113 @@ pub fn apply_mobius_tform(tform: &Matrix2<Complexf>, p: &ExtComplex) -> ExtCompl
         }
     };
 
-    if mapped_pt.is_finite() {
-        ExtComplex::Val(mapped_pt)
+    // treat nan as infinity too, as complex division by 0 generally results in nan since it results in 0/0 under the std formula
+    ExtComplex::from_complex(mapped_pt).unwrap_or(ExtComplex::Inf)
+}
+
+/// Compute the chordal distance between two points in the extended complex plane,
+/// i.e. the straight-line distance between the corresponding points on the Riemann sphere (which has diameter 1).
+///
+/// Unlike the usual distance, this treats the point at infinity like any other point, and is at most 1.
+pub fn chordal_distance(p: &ExtComplex, q: &ExtComplex) -> f64 {
+    match (*p, *q) {
+        (ExtComplex::Inf, ExtComplex::Inf) => 0.0,
+        (ExtComplex::Val(v), ExtComplex::Inf) | (ExtComplex::Inf, ExtComplex::Val(v)) => {
+            1.0 / (1.0 + v.norm_sqr()).sqrt()
+        }
+        (ExtComplex::Val(v), ExtComplex::Val(w)) => {
+            (v - w).norm() / ((1.0 + v.norm_sqr()) * (1.0 + w.norm_sqr())).sqrt()
+        }
+    }
+}
+
+/// Compute the inverse of a Mobius transformation.
+///
+/// Since Mobius transformations are only defined up to a scalar multiple,
+/// the adjugate of the matrix is returned, which avoids dividing by the determinant.
+pub fn invert_mobius_tform(tform: &Matrix2<Complexf>) -> Matrix2<Complexf> {
+    Matrix2::new(tform.m22, -tform.m12, -tform.m21, tform.m11)
+}
+
+/// Scale a Mobius transformation `(az + b) / (cz + d)` into a canonical form suitable for displaying.
+///
+/// The matrix is scaled so that `c = 1`, or so that `d = 1` if the transformation is affine (i.e. `c = 0`).
+/// Components of entries which are negligible compared to the rest of the matrix (e.g. due to floating point error)
+/// are set to exactly 0.
+///
+/// It is assumed that the input matrix is not singular.
+pub fn normalize_mobius_tform(tform: &Matrix2<Complexf>) -> Matrix2<Complexf> {
+    let scale = tform.iter().map(|v| v.norm()).fold(0.0, f64::max);
+    let snap = |x: f64| if x.abs() <= scale * 1e-12 { 0.0 } else { x };
+    let m = tform.map(|v| Complexf::new(snap(v.re), snap(v.im)));
+    if m.m21 != Complexf::ZERO {
+        m / m.m21
     } else {
-        // treat nan under this case too, as complex division by 0 generally results in nan since it results in 0/0 under the std formula
-        ExtComplex::Inf
+        m / m.m22
+    }
+}
+
+/// Compute the fixed points of a Mobius transformation.
+///
+/// Every transformation other than the identity has either two distinct fixed points,
+/// or a single fixed point (if it is parabolic), in which case the same point is returned twice.
+/// Returns nothing for the identity, since it fixes every point.
+pub fn compute_fixed_points(tform: &Matrix2<Complexf>) -> Option<[ExtComplex; 2]> {
+    // normalize to determinant 1 so that the tolerances below are independent of the scale of the matrix
+    let m = tform / tform.determinant().sqrt();
+    let (a, b, c, d) = (m.m11, m.m12, m.m21, m.m22);
+
+    // fixed points satisfy z = (az + b) / (cz + d), i.e. cz^2 + (d - a)z - b = 0
+    if approx::abs_diff_eq!(Complexf::ZERO, c, epsilon = 1e-9) {
+        // infinity is fixed, and the equation degenerates to (d - a)z = b
+        return if !approx::abs_diff_eq!(Complexf::ZERO, d - a, epsilon = 1e-9) {
+            Some([ExtComplex::from_complex(b / (d - a))?, ExtComplex::Inf])
+        } else if !approx::abs_diff_eq!(Complexf::ZERO, b, epsilon = 1e-9) {
+            Some([ExtComplex::Inf, ExtComplex::Inf])
+        } else {
+            None
+        };
     }
+    let discriminant = ((a - d) * (a - d) + b * c * 4.0).sqrt();
+    Some([
+        ExtComplex::from_complex((a - d + discriminant) / (c * 2.0))?,
+        ExtComplex::from_complex((a - d - discriminant) / (c * 2.0))?,
+    ])
+}
+
+/// Compute a fractional power `tform^t` of a Mobius transformation.
+///
+/// As `t` runs from 0 to 1, the result runs continuously from the identity to `tform`
+/// along the one-parameter subgroup generated by `tform`, which makes it suitable for animating a transformation.
+/// The path is chosen so that elliptic transformations rotate by the smallest angle possible.
+///
+/// The returned matrix is normalized to have determinant 1.
+pub fn interpolate_mobius_tform(tform: &Matrix2<Complexf>, t: f64) -> Matrix2<Complexf> {
+    // normalize to SL(2, C), picking the sign of the representative that is closest to the identity
+    let mut m = tform / tform.determinant().sqrt();
+    if m.trace().re < 0.0 {
+        m = -m;
+    }
+
+    // eigenvalues of a determinant 1 matrix are the roots of x^2 - trace * x + 1
+    let trace = m.trace();
+    let discriminant = (trace * trace - 4.0).sqrt();
+    let identity = Matrix2::<Complexf>::identity();
+
+    // parabolic transformations (and the identity) are not diagonalizable, but m - I is nilpotent,
+    // so the power series for m^t truncates to I + t(m - I)
+    if approx::abs_diff_eq!(Complexf::ZERO, discriminant, epsilon = 1e-9) {
+        return identity + (m - identity) * Complexf::from(t);
+    }
+
+    // otherwise m^t can be computed from the eigenvalues using Lagrange interpolation;
+    // the log of the second eigenvalue is taken to be the negation of the first so that the determinant stays 1
+    let lambda_1 = (trace + discriminant) * 0.5;
+    let lambda_2 = (trace - discriminant) * 0.5;
+    let log_lambda = lambda_1.ln();
+    let power_1 = (log_lambda * t).exp();
+    let power_2 = (-log_lambda * t).exp();
+    ((m - identity * lambda_2) * power_1 - (m - identity * lambda_1) * power_2) / discriminant
 }
 
 /// Generate a Mobius transformation from a set of sample points.
@@ -104,18 +326,36 @@ pub fn compute_mobius_tform(
     inputs: &[ExtComplex; 3],
     outputs: &[ExtComplex; 3],
 ) -> Option<Matrix2<Complexf>> {
-    let map_from_inputs = compute_partial_mobius_tform(inputs);
-    let map_to_outputs = compute_partial_mobius_tform(outputs).try_inverse()?;
+    compose_partial_mobius_tforms(
+        &compute_partial_mobius_tform(inputs),
+        &compute_partial_mobius_tform(outputs),
+        SINGULAR_TOLERANCE,
+    )
+}
+
+/// The default threshold on the determinant of a transformation below which it counts as singular,
+/// see [`compute_mobius_tform`].
+pub const SINGULAR_TOLERANCE: f64 = f64::EPSILON * 100.0;
+
+/// Internal helper method used by [compute_mobius_tform].
+///
+/// Combines the [partial transformations][compute_partial_mobius_tform] of the inputs and of the outputs
+/// into the transformation mapping the inputs onto the outputs,
+/// which counts as singular if its determinant is within `tolerance` of 0.
+///
+/// This is separate so that the partial transformations can be cached, e.g. by a [`Session`].
+pub(crate) fn compose_partial_mobius_tforms(
+    map_from_inputs: &Matrix2<Complexf>,
+    map_from_outputs: &Matrix2<Complexf>,
+    tolerance: f64,
+) -> Option<Matrix2<Complexf>> {
+    let map_to_outputs = map_from_outputs.try_inverse()?;
     let transform = map_to_outputs * map_from_inputs;
 
     // NOTE: can fine-tune this threshold to determine when a transformation counts as "singular".
     // higher thresholds will prevent curves from becoming visibly inaccurate as the input/output pairs approach each other,
     // but will reduce the range of transformations that can be computed.
-    if approx::abs_diff_eq!(
-        Complexf::ZERO,
-        transform.determinant(),
-        epsilon = f64::EPSILON * 100.0
-    ) {
+    if approx::abs_diff_eq!(Complexf::ZERO, transform.determinant(), epsilon = tolerance) {
         None
     } else {
         Some(transform)
@@ -132,7 +372,7 @@ pub fn compute_mobius_tform(
 /// Does not check that the inputs are distinct (in which case a Mobius transformation does not exist).
 /// In this case, the returned matrix will be singular.
 #[inline(always)]
-fn compute_partial_mobius_tform(inputs: &[ExtComplex; 3]) -> Matrix2<Complexf> {
+pub(crate) fn compute_partial_mobius_tform(inputs: &[ExtComplex; 3]) -> Matrix2<Complexf> {
     // Mobius transformation as described above is given by
     // (z - z_1) * (z_3 - z_2)
     // -----------------------
@@ -100,3 +100,392 @@ partial_mob_tform_eval!(
     ExtComplex::new(1.0, 0.0),
     ExtComplex::new(0.0, 1.0)
 );
+
+/* interpolate_mobius_tform tests */
+
+// compares two Mobius transformations by their action on a few sample points
+fn assert_same_action(a: &Matrix2<Complexf>, b: &Matrix2<Complexf>) {
+    let samples = [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(1.0, -2.0),
+        ExtComplex::new(-3.0, 0.5),
+    ];
+    for p in samples {
+        match (apply_mobius_tform(a, &p), apply_mobius_tform(b, &p)) {
+            (ExtComplex::Val(x), ExtComplex::Val(y)) => {
+                approx::assert_relative_eq!(x, y, epsilon = 1e-9, max_relative = 1e-9)
+            }
+            (x, y) => assert_eq!(x, y),
+        }
+    }
+}
+
+// test that interpolating starts at the identity and ends at the original transformation
+macro_rules! interpolate_mob_tform_endpoints {
+    ($case:ident, $tform:expr) => {
+        paste! {
+            #[test]
+            fn [<interpolate_mob_tform_endpoints_ $case>]() {
+                let tform: Matrix2<Complexf> = $tform;
+                assert_same_action(&interpolate_mobius_tform(&tform, 0.0), &Matrix2::identity());
+                assert_same_action(&interpolate_mobius_tform(&tform, 1.0), &tform);
+            }
+        }
+    };
+}
+
+interpolate_mob_tform_endpoints!(
+    elliptic,
+    Matrix2::new(
+        Complexf::new(0.0, 1.0),
+        Complexf::ZERO,
+        Complexf::ZERO,
+        Complexf::ONE
+    )
+);
+interpolate_mob_tform_endpoints!(
+    parabolic,
+    Matrix2::new(
+        Complexf::ONE,
+        Complexf::new(3.0, 1.0),
+        Complexf::ZERO,
+        Complexf::ONE
+    )
+);
+interpolate_mob_tform_endpoints!(
+    loxodromic,
+    Matrix2::new(
+        Complexf::new(2.0, 1.0),
+        Complexf::new(-1.0, 0.0),
+        Complexf::new(0.5, 0.0),
+        Complexf::new(0.0, 3.0)
+    )
+);
+interpolate_mob_tform_endpoints!(
+    negative_trace,
+    Matrix2::new(
+        Complexf::new(-2.0, 0.0),
+        Complexf::ZERO,
+        Complexf::ZERO,
+        Complexf::new(-0.5, 0.0)
+    )
+);
+
+#[test]
+fn interpolate_mob_tform_halfway() {
+    // applying the halfway transformation twice should be the same as applying the full transformation
+    let tform = Matrix2::new(
+        Complexf::new(2.0, 1.0),
+        Complexf::new(-1.0, 0.0),
+        Complexf::new(0.5, 0.0),
+        Complexf::new(0.0, 3.0),
+    );
+    let half = interpolate_mobius_tform(&tform, 0.5);
+
+    assert_same_action(&(half * half), &tform);
+}
+
+/* curve_to_matrix tests */
+
+#[test]
+fn curve_to_matrix_roundtrip_circle() {
+    let curve = Curve::Circle {
+        center: Complexf::new(1.0, -2.0),
+        radius: 3.0,
+    };
+
+    assert_eq!(matrix_to_curve(&curve_to_matrix(&curve)), curve);
+}
+
+#[test]
+fn curve_to_matrix_roundtrip_line() {
+    let curve = Curve::Line {
+        point: Complexf::new(1.0, -2.0),
+        slope: Complexf::new(3.0, 4.0),
+    };
+
+    match matrix_to_curve(&curve_to_matrix(&curve)) {
+        Curve::Line { point, slope } => {
+            approx::assert_abs_diff_eq!(point, Complexf::new(1.0, -2.0), epsilon = 1e-12);
+            // slope should be parallel to the original
+            approx::assert_abs_diff_eq!(
+                (slope * Complexf::new(3.0, -4.0)).im,
+                0.0,
+                epsilon = 1e-12
+            );
+        }
+        curve => panic!("expected a line, got {curve:?}"),
+    }
+}
+
+/* arc_to_curves tests */
+
+#[test]
+fn arc_to_curves_counterclockwise() {
+    let arc = ThreePointArc {
+        start: ExtComplex::new(1.0, 0.0),
+        via: ExtComplex::new(0.0, 1.0),
+        end: ExtComplex::new(-1.0, 0.0),
+    };
+
+    match arc_to_curves(&arc)[..] {
+        [Curve::Arc {
+            center,
+            radius,
+            start,
+            end,
+        }] => {
+            approx::assert_abs_diff_eq!(center, Complexf::ZERO, epsilon = 1e-12);
+            approx::assert_relative_eq!(radius, 1.0);
+            assert_eq!(start, Complexf::new(1.0, 0.0));
+            assert_eq!(end, Complexf::new(-1.0, 0.0));
+        }
+        ref curves => panic!("expected a single arc, got {curves:?}"),
+    }
+}
+
+#[test]
+fn arc_to_curves_clockwise() {
+    // the arc runs clockwise, so the endpoints are swapped
+    let arc = ThreePointArc {
+        start: ExtComplex::new(1.0, 0.0),
+        via: ExtComplex::new(0.0, -1.0),
+        end: ExtComplex::new(-1.0, 0.0),
+    };
+
+    match arc_to_curves(&arc)[..] {
+        [Curve::Arc { start, end, .. }] => {
+            assert_eq!(start, Complexf::new(-1.0, 0.0));
+            assert_eq!(end, Complexf::new(1.0, 0.0));
+        }
+        ref curves => panic!("expected a single arc, got {curves:?}"),
+    }
+}
+
+#[test]
+fn arc_to_curves_segment() {
+    let arc = ThreePointArc {
+        start: ExtComplex::new(1.0, 1.0),
+        via: ExtComplex::new(2.0, 2.0),
+        end: ExtComplex::new(3.0, 3.0),
+    };
+
+    assert_eq!(
+        arc_to_curves(&arc),
+        vec![Curve::Segment {
+            start: Complexf::new(1.0, 1.0),
+            end: Complexf::new(3.0, 3.0),
+        }]
+    );
+}
+
+// arcs of lines passing through infinity are split into two rays
+macro_rules! arc_to_curves_through_infinity {
+    ($case:ident, $via:expr) => {
+        paste! {
+            #[test]
+            fn [<arc_to_curves_through_infinity_ $case>]() {
+                let arc = ThreePointArc {
+                    start: ExtComplex::new(1.0, 0.0),
+                    via: $via,
+                    end: ExtComplex::new(3.0, 0.0),
+                };
+
+                assert_eq!(
+                    arc_to_curves(&arc),
+                    vec![
+                        Curve::Ray {
+                            point: Complexf::new(1.0, 0.0),
+                            direction: Complexf::new(-2.0, 0.0),
+                        },
+                        Curve::Ray {
+                            point: Complexf::new(3.0, 0.0),
+                            direction: Complexf::new(2.0, 0.0),
+                        },
+                    ]
+                );
+            }
+        }
+    };
+}
+
+arc_to_curves_through_infinity!(inf, ExtComplex::Inf);
+arc_to_curves_through_infinity!(finite, ExtComplex::new(5.0, 0.0));
+
+#[test]
+fn arc_to_curves_infinite_endpoint() {
+    let arc = ThreePointArc {
+        start: ExtComplex::Inf,
+        via: ExtComplex::new(1.0, 1.0),
+        end: ExtComplex::new(0.0, 1.0),
+    };
+
+    assert_eq!(
+        arc_to_curves(&arc),
+        vec![Curve::Ray {
+            point: Complexf::new(0.0, 1.0),
+            direction: Complexf::new(1.0, 0.0),
+        }]
+    );
+}
+
+#[test]
+fn arc_to_curves_degenerate() {
+    let arc = ThreePointArc {
+        start: ExtComplex::Inf,
+        via: ExtComplex::new(1.0, 1.0),
+        end: ExtComplex::Inf,
+    };
+
+    assert!(arc_to_curves(&arc).is_empty());
+}
+
+#[test]
+fn apply_mob_tform_arc_preserves_order() {
+    // z -> 1 / z maps the upper half of the unit circle onto the lower half
+    let tform = Matrix2::new(Complexf::ZERO, Complexf::ONE, Complexf::ONE, Complexf::ZERO);
+    let arc = ThreePointArc {
+        start: ExtComplex::new(1.0, 0.0),
+        via: ExtComplex::new(0.0, 1.0),
+        end: ExtComplex::new(-1.0, 0.0),
+    };
+
+    match arc_to_curves(&apply_mobius_tform_arc(&tform, &arc))[..] {
+        // the image runs clockwise from 1 to -1, hence the endpoints are swapped
+        [Curve::Arc { start, end, .. }] => {
+            approx::assert_abs_diff_eq!(start, Complexf::new(-1.0, 0.0), epsilon = 1e-12);
+            approx::assert_abs_diff_eq!(end, Complexf::new(1.0, 0.0), epsilon = 1e-12);
+        }
+        ref curves => panic!("expected a single arc, got {curves:?}"),
+    }
+}
+
+/* compute_fixed_points tests */
+
+#[test]
+fn fixed_points_loxodromic() {
+    let inputs = [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(1.0, 0.0),
+        ExtComplex::Inf,
+    ];
+    let outputs = [
+        ExtComplex::new(1.0, 2.0),
+        ExtComplex::new(-3.0, 0.5),
+        ExtComplex::new(2.0, -1.0),
+    ];
+    let tform = compute_mobius_tform(&inputs, &outputs).unwrap();
+    let fixed_points = compute_fixed_points(&tform).expect("tform is not the identity");
+
+    assert_ne!(fixed_points[0], fixed_points[1]);
+    for p in fixed_points {
+        match (p, apply_mobius_tform(&tform, &p)) {
+            (ExtComplex::Val(p), ExtComplex::Val(q)) => {
+                approx::assert_abs_diff_eq!(p, q, epsilon = 1e-9)
+            }
+            pair => panic!("expected finite fixed points, got {pair:?}"),
+        }
+    }
+}
+
+#[test]
+fn fixed_points_affine() {
+    // z -> 2z + 2 fixes -2 and infinity
+    let tform = Matrix2::new(
+        Complexf::from(2.0),
+        Complexf::from(2.0),
+        Complexf::ZERO,
+        Complexf::ONE,
+    );
+
+    assert_eq!(
+        compute_fixed_points(&tform),
+        Some([ExtComplex::new(-2.0, 0.0), ExtComplex::Inf])
+    );
+}
+
+#[test]
+fn fixed_points_parabolic() {
+    // z -> z + 1 only fixes infinity, while z -> z / (z + 1) only fixes 0
+    let translation = Matrix2::new(Complexf::ONE, Complexf::ONE, Complexf::ZERO, Complexf::ONE);
+    let conjugate = Matrix2::new(Complexf::ONE, Complexf::ZERO, Complexf::ONE, Complexf::ONE);
+
+    assert_eq!(
+        compute_fixed_points(&translation),
+        Some([ExtComplex::Inf, ExtComplex::Inf])
+    );
+    assert_eq!(
+        compute_fixed_points(&conjugate),
+        Some([ExtComplex::new(0.0, 0.0), ExtComplex::new(0.0, 0.0)])
+    );
+}
+
+#[test]
+fn fixed_points_identity() {
+    let tform = Matrix2::identity() * Complexf::new(0.0, 3.0);
+    assert_eq!(compute_fixed_points(&tform), None);
+}
+
+/* normalize_mobius_tform tests */
+
+#[test]
+fn normalize_mob_tform_scales_c() {
+    let tform = Matrix2::new(
+        Complexf::new(0.0, 2.0),
+        Complexf::from(4.0),
+        Complexf::from(2.0),
+        Complexf::new(1e-20, 2.0),
+    );
+    let expected = Matrix2::new(
+        Complexf::new(0.0, 1.0),
+        Complexf::from(2.0),
+        Complexf::ONE,
+        Complexf::new(0.0, 1.0),
+    );
+
+    assert_eq!(normalize_mobius_tform(&tform), expected);
+}
+
+#[test]
+fn normalize_mob_tform_affine() {
+    // c is only nonzero due to floating point error, so the transformation is treated as affine
+    let tform = Matrix2::new(
+        Complexf::from(6.0),
+        Complexf::from(-3.0),
+        Complexf::from(1e-16),
+        Complexf::from(3.0),
+    );
+    let expected = Matrix2::new(
+        Complexf::from(2.0),
+        Complexf::from(-1.0),
+        Complexf::ZERO,
+        Complexf::ONE,
+    );
+
+    assert_eq!(normalize_mobius_tform(&tform), expected);
+}
+
+/* chordal_distance tests */
+
+#[test]
+fn chordal_distance_antipodal() {
+    // 0 and infinity are the poles of the Riemann sphere, as are 1 and -1 on the equator
+    let zero = ExtComplex::new(0.0, 0.0);
+    approx::assert_relative_eq!(chordal_distance(&zero, &ExtComplex::Inf), 1.0);
+    approx::assert_relative_eq!(
+        chordal_distance(&ExtComplex::new(1.0, 0.0), &ExtComplex::new(-1.0, 0.0)),
+        1.0
+    );
+    assert_eq!(chordal_distance(&ExtComplex::Inf, &ExtComplex::Inf), 0.0);
+}
+
+#[test]
+fn chordal_distance_inversion_invariant() {
+    // z -> 1 / z is a rotation of the Riemann sphere, so it preserves chordal distances
+    let (p, q) = (Complexf::new(0.5, 2.0), Complexf::new(-3.0, 0.25));
+    approx::assert_relative_eq!(
+        chordal_distance(&ExtComplex::Val(p), &ExtComplex::Val(q)),
+        chordal_distance(&ExtComplex::Val(p.inv()), &ExtComplex::Val(q.inv())),
+        epsilon = 1e-12
+    );
+}
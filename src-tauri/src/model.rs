@@ -1,5 +1,8 @@
 //! Contains the definitions of the math objects/concepts we will be using.
 
+use std::fmt;
+use std::str::FromStr;
+
 use nalgebra as na;
 use serde::{de, ser, Deserialize, Serialize};
 
@@ -9,9 +12,10 @@ use serde::{de, ser, Deserialize, Serialize};
 /// to match the Javascript Number type.
 pub type Complexf = na::Complex<f64>;
 
-/// The curves we're interested in are lines and circles.
+/// The curves we're interested in are lines and circles, along with pieces of them.
 ///
 /// A Mobius transformation will map a circle/line to another circle/line.
+/// Pieces of circles/lines are produced by transforming a [`ThreePointArc`].
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum Curve {
@@ -21,6 +25,95 @@ pub enum Curve {
     /// A circle represents the set of points `|z - center| = radius`
     #[serde(rename = "circle")]
     Circle { center: Complexf, radius: f64 },
+    /// An arc represents the part of the circle `|z - center| = radius`
+    /// traced counterclockwise from the point `start` to the point `end`
+    #[serde(rename = "arc")]
+    Arc {
+        center: Complexf,
+        radius: f64,
+        start: Complexf,
+        end: Complexf,
+    },
+    /// A segment represents the set of points `start + (end - start) * t` for `0 <= t <= 1`
+    #[serde(rename = "segment")]
+    Segment { start: Complexf, end: Complexf },
+    /// A ray represents the set of points `point + direction * t` for `t >= 0`
+    #[serde(rename = "ray")]
+    Ray {
+        point: Complexf,
+        direction: Complexf,
+    },
+}
+
+impl Curve {
+    /// Check whether the curve is degenerate, i.e. it has non-finite components,
+    /// or has collapsed to a single point (e.g. a circle with radius 0 or a line with slope 0).
+    pub fn is_degenerate(&self) -> bool {
+        let is_valid = match *self {
+            Curve::Line { point, slope } => {
+                point.is_finite() && slope.is_finite() && slope != Complexf::ZERO
+            }
+            Curve::Circle { center, radius } => {
+                center.is_finite() && radius.is_finite() && radius > 0.0
+            }
+            Curve::Arc {
+                center,
+                radius,
+                start,
+                end,
+            } => {
+                center.is_finite()
+                    && radius.is_finite()
+                    && radius > 0.0
+                    && start.is_finite()
+                    && end.is_finite()
+            }
+            Curve::Segment { start, end } => start.is_finite() && end.is_finite() && start != end,
+            Curve::Ray { point, direction } => {
+                point.is_finite() && direction.is_finite() && direction != Complexf::ZERO
+            }
+        };
+        !is_valid
+    }
+}
+
+/// An arc of a circle/line, given by its two endpoints and a third point lying on the arc between them.
+///
+/// Unlike [`Curve`], this representation is closed under Mobius transformations:
+/// since they map circles/lines to circles/lines and preserve the order of points along them,
+/// an arc can be transformed by transforming each of the three points.
+/// The arc may pass through the point at infinity, in which case it is (a piece of) a line.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ThreePointArc {
+    pub start: ExtComplex,
+    pub via: ExtComplex,
+    pub end: ExtComplex,
+}
+
+/// An axis-aligned rectangle of the complex plane, e.g. the region which is visible on screen.
+///
+/// `min` is the bottom-left corner and `max` is the top-right corner of the rectangle.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Viewport {
+    pub min: Complexf,
+    pub max: Complexf,
+}
+
+impl Viewport {
+    /// The center of the rectangle.
+    pub fn center(&self) -> Complexf {
+        0.5 * (self.min + self.max)
+    }
+
+    /// The width of the rectangle, i.e. its extent along the real axis.
+    pub fn width(&self) -> f64 {
+        self.max.re - self.min.re
+    }
+
+    /// The height of the rectangle, i.e. its extent along the imaginary axis.
+    pub fn height(&self) -> f64 {
+        self.max.im - self.min.im
+    }
 }
 
 /// An element of the extended complex plane (i.e. the Riemann sphere).
@@ -37,12 +130,83 @@ pub enum ExtComplex {
 impl ExtComplex {
     /// Initialize a regular (finite) complex value from real and imaginary components.
     ///
-    /// It is recommended not to pass nan or infinite values into this method.
+    /// # Panics
+    ///
+    /// Panics if either component is nan or infinite; use [`ExtComplex::try_new`] for values which may not be finite.
     pub fn new(re: f64, im: f64) -> Self {
+        assert!(
+            re.is_finite() && im.is_finite(),
+            "components of a finite complex value must be finite, got {re} + {im}i"
+        );
         ExtComplex::Val(Complexf::new(re, im))
     }
+
+    /// Initialize a complex value from real and imaginary components, upholding the invariant on [`ExtComplex::Val`].
+    ///
+    /// Values with an infinite component are mapped to [`ExtComplex::Inf`].
+    /// Returns `None` if either component is nan.
+    pub fn try_new(re: f64, im: f64) -> Option<Self> {
+        Self::from_complex(Complexf::new(re, im))
+    }
+
+    /// Initialize a complex value from its modulus and argument (in radians).
+    ///
+    /// An infinite modulus gives [`ExtComplex::Inf`] regardless of the argument, as long as the argument is finite.
+    /// Returns `None` if the modulus is negative or nan, or if the argument is not finite.
+    pub fn from_polar(r: f64, theta: f64) -> Option<Self> {
+        if r.is_nan() || r < 0.0 || !theta.is_finite() {
+            None
+        } else if r.is_infinite() {
+            Some(ExtComplex::Inf)
+        } else {
+            Self::from_complex(Complexf::from_polar(r, theta))
+        }
+    }
+
+    /// Convert a complex number to an element of the extended complex plane, see [`ExtComplex::try_new`].
+    pub fn from_complex(c: Complexf) -> Option<Self> {
+        if c.is_nan() {
+            None
+        } else if c.is_infinite() {
+            Some(ExtComplex::Inf)
+        } else {
+            Some(ExtComplex::Val(c))
+        }
+    }
+}
+
+/// Parses either the string `inf` or a complex number written like `1.5-2i`, `-5` or `i`.
+impl FromStr for ExtComplex {
+    type Err = ParseExtComplexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s == "inf" {
+            return Ok(ExtComplex::Inf);
+        }
+        Complexf::from_str(s)
+            .ok()
+            .and_then(ExtComplex::from_complex)
+            .ok_or_else(|| ParseExtComplexError(s.to_owned()))
+    }
 }
 
+/// An error returned when a string can't be [parsed][FromStr] as an [`ExtComplex`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseExtComplexError(String);
+
+impl fmt::Display for ParseExtComplexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "\"{}\" is not \"inf\" or a complex number like 1.5-2i",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseExtComplexError {}
+
 // need custom Serialize implementation to serialize to either string or struct
 impl Serialize for ExtComplex {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -69,7 +233,7 @@ impl<'de> Deserialize<'de> for ExtComplex {
         impl<'de> de::Visitor<'de> for ExtComplexVisitor {
             type Value = ExtComplex;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
                 formatter.write_str("the string \"inf\" or a tuple of 2 numbers")
             }
 
@@ -89,22 +253,15 @@ impl<'de> Deserialize<'de> for ExtComplex {
             where
                 A: de::SeqAccess<'de>,
             {
-                Complexf::deserialize(de::value::SeqAccessDeserializer::new(seq))
-                    .map(|value| {
-                        if value.is_infinite() {
-                            Self::Value::Inf
-                        } else {
-                            Self::Value::Val(value)
-                        }
-                    })
-                    .or_else(|err| {
-                        // treat numbers that can't fit inside the internal floating point type as Inf
-                        if err.to_string().contains("number out of range") {
-                            Ok(Self::Value::Inf)
-                        } else {
-                            Err(err)
-                        }
-                    })
+                let value = Complexf::deserialize(de::value::SeqAccessDeserializer::new(seq))?;
+                // infinite components are mapped to Inf, whereas JSON numbers which can't fit inside the internal
+                // floating point type are rejected by the parser before they ever reach this visitor
+                ExtComplex::from_complex(value).ok_or_else(|| {
+                    de::Error::invalid_value(
+                        de::Unexpected::Other("a complex number containing nan"),
+                        &self,
+                    )
+                })
             }
         }
 
@@ -14,7 +14,7 @@ fn serialize_extcomplex_inf_variant() {
 
 #[test]
 fn serialize_extcomplex_inf_value() {
-    let c = ExtComplex::new(0.0, f64::INFINITY);
+    let c = ExtComplex::Val(Complexf::new(0.0, f64::INFINITY));
     let json = serde_json::to_string(&c).expect("serialization should succeed");
 
     assert_eq!(json, "\"inf\"");
@@ -30,7 +30,7 @@ fn serialize_extcomplex_finite_value() {
 
 #[test]
 fn serialize_extcomplex_nan_failure() {
-    let c = ExtComplex::new(f64::NAN, 0.0);
+    let c = ExtComplex::Val(Complexf::new(f64::NAN, 0.0));
     let err = serde_json::to_string(&c).expect_err("serialization should fail");
 
     assert!(err.to_string().contains("value contains nan"));
@@ -44,10 +44,19 @@ fn deserialize_extcomplex_inf_variant() {
 }
 
 #[test]
-fn deserialize_extcomplex_inf_value() {
-    // should deserialize to Inf variant when numbers are too large to fit inside the internal floating point type
-    let c =
-        serde_json::from_str::<ExtComplex>("[1, 1e1000]").expect("deserialization should succeed");
+fn deserialize_extcomplex_out_of_range() {
+    // JSON numbers too large to fit inside the internal floating point type are rejected rather than rounded to Inf
+    let c = serde_json::from_str::<ExtComplex>("[1, 1e1000]");
+
+    assert!(c.is_err());
+}
+
+#[test]
+fn deserialize_extcomplex_infinite_component() {
+    // JSON can't represent infinite numbers, so use a deserializer which can
+    let seq =
+        de::value::SeqDeserializer::<_, de::value::Error>::new([1.0, f64::INFINITY].into_iter());
+    let c = ExtComplex::deserialize(seq).expect("deserialization should succeed");
 
     assert_eq!(c, ExtComplex::Inf);
 }
@@ -77,6 +86,68 @@ fn deserialize_extcomplex_nan_failure() {
     assert!(err.is_data());
 }
 
+#[test]
+fn deserialize_extcomplex_nan_component_failure() {
+    let seq = de::value::SeqDeserializer::<_, de::value::Error>::new([f64::NAN, 1.0].into_iter());
+    let err = ExtComplex::deserialize(seq).expect_err("deserialization should fail");
+
+    assert!(err.to_string().contains("nan"));
+}
+
+/* ExtComplex construction tests */
+
+#[test]
+fn extcomplex_try_new() {
+    assert_eq!(
+        ExtComplex::try_new(1.0, 2.0),
+        Some(ExtComplex::new(1.0, 2.0))
+    );
+    assert_eq!(
+        ExtComplex::try_new(f64::NEG_INFINITY, 0.0),
+        Some(ExtComplex::Inf)
+    );
+    assert_eq!(ExtComplex::try_new(0.0, f64::NAN), None);
+    assert_eq!(ExtComplex::try_new(f64::INFINITY, f64::NAN), None);
+}
+
+#[test]
+fn extcomplex_from_polar() {
+    match ExtComplex::from_polar(2.0, std::f64::consts::FRAC_PI_2) {
+        Some(ExtComplex::Val(v)) => {
+            approx::assert_abs_diff_eq!(v, Complexf::new(0.0, 2.0), epsilon = 1e-12)
+        }
+        c => panic!("expected a finite value, got {c:?}"),
+    }
+    assert_eq!(
+        ExtComplex::from_polar(f64::INFINITY, 1.0),
+        Some(ExtComplex::Inf)
+    );
+    assert_eq!(ExtComplex::from_polar(-1.0, 0.0), None);
+    assert_eq!(ExtComplex::from_polar(f64::NAN, 0.0), None);
+    assert_eq!(ExtComplex::from_polar(1.0, f64::INFINITY), None);
+}
+
+#[test]
+#[should_panic]
+fn extcomplex_new_nan_panics() {
+    ExtComplex::new(f64::NAN, 0.0);
+}
+
+#[test]
+fn extcomplex_from_str() {
+    for (s, expected) in [
+        ("inf", ExtComplex::Inf),
+        (" -5 ", ExtComplex::new(-5.0, 0.0)),
+        ("i", ExtComplex::new(0.0, 1.0)),
+        ("1.5-2i", ExtComplex::new(1.5, -2.0)),
+    ] {
+        assert_eq!(s.parse(), Ok(expected));
+    }
+    for s in ["", "1+", "nan", "z"] {
+        assert!(s.parse::<ExtComplex>().is_err(), "{s:?} should not parse");
+    }
+}
+
 /* Curve (de)-serialization tests */
 
 #[test]
@@ -148,3 +219,38 @@ fn deserialize_curve_circle() {
 
     assert_eq!(curve, expected);
 }
+
+/* Curve tests */
+
+#[test]
+fn curve_is_degenerate() {
+    let z = Complexf::new(1.0, 2.0);
+    for curve in [
+        Curve::Circle {
+            center: z,
+            radius: 0.0,
+        },
+        Curve::Circle {
+            center: Complexf::new(f64::NAN, 0.0),
+            radius: 1.0,
+        },
+        Curve::Line {
+            point: z,
+            slope: Complexf::ZERO,
+        },
+        Curve::Segment { start: z, end: z },
+        Curve::Ray {
+            point: Complexf::new(f64::INFINITY, 0.0),
+            direction: z,
+        },
+    ] {
+        assert!(curve.is_degenerate(), "{curve:?} should be degenerate");
+    }
+
+    assert!(!Curve::Circle {
+        center: z,
+        radius: 1.0
+    }
+    .is_degenerate());
+    assert!(!Curve::Line { point: z, slope: z }.is_degenerate());
+}
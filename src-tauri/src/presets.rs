@@ -0,0 +1,179 @@
+//! Contains a catalogue of named [`Preset`]s, i.e. well-known Mobius transformations which can be loaded as a starting point.
+//!
+//! Each preset is defined by the matrix of its transformation, from which the outputs of its control points are computed,
+//! so that the control points always describe the intended transformation exactly (up to floating point error).
+
+use std::f64::consts::PI;
+
+use nalgebra::Matrix2;
+use serde::Serialize;
+
+use super::*;
+
+/// A named Mobius transformation, expressed as three control points along with curve families which show it off.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Preset {
+    /// The name used to load the preset, e.g. in [`load_preset`].
+    pub key: &'static str,
+    pub display_name: &'static str,
+    pub description: &'static str,
+    pub inputs: [ExtComplex; 3],
+    pub outputs: [ExtComplex; 3],
+    /// The curve families enabled when the preset is loaded, with their default parameters.
+    pub families: &'static [&'static str],
+}
+
+impl Preset {
+    /// The scene loaded for the preset, which has no viewport.
+    pub fn to_scene(&self) -> Scene {
+        Scene {
+            version: SCENE_VERSION,
+            points: [0, 1, 2].map(|i| ScenePoint {
+                input: self.inputs[i],
+                output: self.outputs[i],
+            }),
+            families: self
+                .families
+                .iter()
+                .map(|&name| SceneFamily {
+                    name: name.to_owned(),
+                    params: FamilyParams::new(),
+                })
+                .collect(),
+            viewport: None,
+        }
+    }
+}
+
+/// The built-in presets, in the order they should be listed.
+pub fn presets() -> Vec<Preset> {
+    let c = Complexf::new;
+    let one = c(1.0, 0.0);
+    let zero = c(0.0, 0.0);
+    let i = c(0.0, 1.0);
+    // the hyperbolic models are scaled up in the plane, so the presets acting on them are conjugated by the same scale
+    let s = c(MODEL_SCALE, 0.0);
+    let default_inputs = [0.0, -5.0, 5.0].map(|re| ExtComplex::new(re, 0.0));
+
+    vec![
+        preset(
+            "identity",
+            "Identity",
+            "Leaves every point where it is.",
+            default_inputs,
+            Matrix2::identity(),
+            &["xy"],
+        ),
+        preset(
+            "cayley",
+            "Cayley transform",
+            "Maps the upper half-plane onto the Poincare disk via 10(z - 10i) / (z + 10i), sending the real line to the circle |z| = 10.",
+            [0.0, -MODEL_SCALE, MODEL_SCALE].map(|re| ExtComplex::new(re, 0.0)),
+            Matrix2::new(s, -s * s * i, one, s * i),
+            &["tiling_half_plane"],
+        ),
+        preset(
+            "inversion",
+            "Inversion",
+            "The map 1/z, which swaps 0 and infinity and turns the unit circle over onto itself.",
+            [1.0, -5.0, 5.0].map(|re| ExtComplex::new(re, 0.0)),
+            Matrix2::new(zero, one, one, zero),
+            &["polar"],
+        ),
+        preset(
+            "rotation",
+            "Rotation",
+            "Rotates the plane by 45 degrees about the origin, an elliptic map fixing 0 and infinity.",
+            default_inputs,
+            Matrix2::new(Complexf::from_polar(1.0, PI / 4.0), zero, zero, one),
+            &["polar"],
+        ),
+        preset(
+            "translation",
+            "Translation",
+            "Shifts the plane by 2 to the right, a parabolic map whose only fixed point is infinity.",
+            default_inputs,
+            Matrix2::new(one, c(2.0, 0.0), zero, one),
+            &["xy"],
+        ),
+        preset(
+            "dilation",
+            "Dilation",
+            "Scales the plane by a factor of 2 about the origin, a hyperbolic map fixing 0 and infinity.",
+            default_inputs,
+            Matrix2::new(c(2.0, 0.0), zero, zero, one),
+            &["polar"],
+        ),
+        preset(
+            "hyperbolic",
+            "Hyperbolic flow",
+            "Pushes points along the circles through -5 and 5, away from -5 and towards 5.",
+            default_inputs,
+            with_fixed_points(c(-5.0, 0.0), c(5.0, 0.0), c(2.0, 0.0)),
+            &["apollo"],
+        ),
+        preset(
+            "elliptic_order_3",
+            "Elliptic map of order 3",
+            "Rotates the plane by a third of a turn about -5 and 5, so that applying it three times gives the identity.",
+            default_inputs,
+            with_fixed_points(c(-5.0, 0.0), c(5.0, 0.0), Complexf::from_polar(1.0, 2.0 * PI / 3.0)),
+            &["apollo"],
+        ),
+        preset(
+            "loxodromic",
+            "Loxodromic spiral",
+            "Spirals points out of -5 and into 5, combining the hyperbolic flow with a rotation.",
+            default_inputs,
+            with_fixed_points(c(-5.0, 0.0), c(5.0, 0.0), Complexf::from_polar(2.0, PI / 4.0)),
+            &["apollo"],
+        ),
+        preset(
+            "disk_isometry",
+            "Disk isometry",
+            "Translates the Poincare disk |z| < 10 so that 5 moves to the centre, an isometry of the hyperbolic plane.",
+            [0.0, 0.5, -0.5].map(|re| ExtComplex::new(re * MODEL_SCALE, 0.0)),
+            Matrix2::new(one, -0.5 * s, -0.5 / s, one),
+            &["tiling_disk"],
+        ),
+    ]
+}
+
+/// Find the built-in preset with the given key, if there is one.
+pub fn find_preset(key: &str) -> Option<Preset> {
+    presets().into_iter().find(|preset| preset.key == key)
+}
+
+/// Internal helper used by [`presets`], which computes the outputs of the control points under the transformation.
+fn preset(
+    key: &'static str,
+    display_name: &'static str,
+    description: &'static str,
+    inputs: [ExtComplex; 3],
+    tform: Matrix2<Complexf>,
+    families: &'static [&'static str],
+) -> Preset {
+    Preset {
+        key,
+        display_name,
+        description,
+        inputs,
+        outputs: inputs.map(|p| math::apply_mobius_tform(&tform, &p)),
+        families,
+    }
+}
+
+/// Internal helper used by [`presets`].
+///
+/// Computes the transformation with the (distinct, finite) fixed points `p` and `q` and multiplier `k`,
+/// i.e. the conjugate of `z -> kz` by the map sending `p` to 0 and `q` to infinity.
+fn with_fixed_points(p: Complexf, q: Complexf, k: Complexf) -> Matrix2<Complexf> {
+    let one = Complexf::new(1.0, 0.0);
+    let zero = Complexf::new(0.0, 0.0);
+    let to_standard = Matrix2::new(one, -p, one, -q);
+    math::invert_mobius_tform(&to_standard) * Matrix2::new(k, zero, zero, one) * to_standard
+}
+
+#[cfg(test)]
+#[path = "presets_tests.rs"]
+mod tests;
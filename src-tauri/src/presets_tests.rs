@@ -0,0 +1,142 @@
+use std::collections::HashSet;
+
+use super::*;
+
+fn assert_close(actual: ExtComplex, expected: ExtComplex) {
+    assert!(
+        math::chordal_distance(&actual, &expected) < 1e-9,
+        "expected {expected:?}, got {actual:?}"
+    );
+}
+
+fn preset_tform(key: &str) -> Matrix2<Complexf> {
+    let preset = find_preset(key).expect("preset should exist");
+    math::compute_mobius_tform(&preset.inputs, &preset.outputs)
+        .expect("preset should define a transformation")
+}
+
+#[test]
+fn presets_are_valid() {
+    let registry = CurveFamilyRegistry::default();
+    let presets = presets();
+    let keys: HashSet<_> = presets.iter().map(|preset| preset.key).collect();
+    assert_eq!(keys.len(), presets.len(), "preset keys should be unique");
+
+    for preset in presets {
+        let scene = load_preset(preset.key).unwrap();
+        let mut session = Session::default();
+        let update = session.start(
+            &registry,
+            scene.inputs(),
+            scene.outputs(),
+            scene.family_requests(),
+            None,
+        );
+        assert!(
+            update.is_ok(),
+            "preset {} failed to start: {update:?}",
+            preset.key
+        );
+    }
+}
+
+#[test]
+fn preset_inversion() {
+    let tform = preset_tform("inversion");
+    assert_close(
+        math::apply_mobius_tform(&tform, &ExtComplex::new(2.0, 0.0)),
+        ExtComplex::new(0.5, 0.0),
+    );
+    assert_close(
+        math::apply_mobius_tform(&tform, &ExtComplex::new(0.0, 0.0)),
+        ExtComplex::Inf,
+    );
+}
+
+#[test]
+fn preset_cayley() {
+    let tform = preset_tform("cayley");
+    assert_close(
+        math::apply_mobius_tform(&tform, &ExtComplex::new(0.0, MODEL_SCALE)),
+        ExtComplex::new(0.0, 0.0),
+    );
+    assert_close(
+        math::apply_mobius_tform(&tform, &ExtComplex::Inf),
+        ExtComplex::new(MODEL_SCALE, 0.0),
+    );
+    // the upper half-plane lands inside the disk
+    let p = math::apply_mobius_tform(&tform, &ExtComplex::new(3.0, 0.5));
+    assert!(HyperbolicModel::Disk.contains(&p));
+}
+
+#[test]
+fn presets_map_model_boundaries() {
+    // presets showing off a tiling map the boundary of its model onto the boundary of the (scaled) Poincare disk,
+    // which for the disk means leaving its boundary invariant
+    let boundary = |model| -> Vec<ExtComplex> {
+        match model {
+            HyperbolicModel::Disk => (-4..=4)
+                .map(|k| ExtComplex::from_polar(MODEL_SCALE, k as f64).unwrap())
+                .collect(),
+            HyperbolicModel::HalfPlane => (-4..=4)
+                .map(|k| ExtComplex::new(MODEL_SCALE * k as f64, 0.0))
+                .chain([ExtComplex::Inf])
+                .collect(),
+        }
+    };
+    for preset in presets() {
+        for (family, model) in [
+            ("tiling_disk", HyperbolicModel::Disk),
+            ("tiling_half_plane", HyperbolicModel::HalfPlane),
+        ] {
+            if !preset.families.contains(&family) {
+                continue;
+            }
+            let tform = preset_tform(preset.key);
+            for p in boundary(model) {
+                let q = math::apply_mobius_tform(&tform, &p);
+                assert!(
+                    matches!(q, ExtComplex::Val(q) if (q.norm() - MODEL_SCALE).abs() < 1e-9),
+                    "{}: {p:?} maps to {q:?}, off the boundary of the disk",
+                    preset.key
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn preset_elliptic_has_order_3() {
+    let tform = preset_tform("elliptic_order_3");
+    let p = ExtComplex::new(1.0, 2.0);
+    let mut q = p;
+    for _ in 0..3 {
+        q = math::apply_mobius_tform(&tform, &q);
+    }
+    assert_close(q, p);
+    assert_ne!(math::apply_mobius_tform(&tform, &p), p);
+}
+
+#[test]
+fn preset_fixed_points() {
+    for key in ["hyperbolic", "elliptic_order_3", "loxodromic"] {
+        let fixed = math::compute_fixed_points(&preset_tform(key)).unwrap();
+        let mut fixed = fixed.map(|p| match p {
+            ExtComplex::Val(v) => v.re,
+            ExtComplex::Inf => f64::INFINITY,
+        });
+        fixed.sort_by(f64::total_cmp);
+        assert!(
+            (fixed[0] + 5.0).abs() < 1e-9 && (fixed[1] - 5.0).abs() < 1e-9,
+            "{key}: {fixed:?}"
+        );
+    }
+}
+
+#[test]
+fn load_preset_unknown() {
+    assert!(matches!(
+        load_preset("nonexistent"),
+        Err(Error::UnknownPreset { key }) if key == "nonexistent"
+    ));
+}
@@ -0,0 +1,281 @@
+//! Contains a CPU rasterizer used to draw [`Curves`][Curve] into images without the help of the frontend.
+
+use std::f64::consts::TAU;
+
+use super::*;
+
+/// A color with red, green, blue and alpha channels, in that order.
+pub type Rgba = [u8; 4];
+
+/// The maximum number of pixels in a [`Canvas`] (e.g. 8192 x 8192),
+/// which bounds the memory used to draw a single image.
+pub const MAX_CANVAS_PIXELS: usize = 1 << 26;
+
+/// The number of pixels in a [`Canvas`] of the given size,
+/// or [`None`] if it would have more than [`MAX_CANVAS_PIXELS`] pixels.
+pub fn canvas_pixels(width: u32, height: u32) -> Option<usize> {
+    (width as usize)
+        .checked_mul(height as usize)
+        .filter(|&pixels| pixels <= MAX_CANVAS_PIXELS)
+}
+
+/// An RGBA image onto which [`Curves`][Curve] can be drawn with antialiasing.
+///
+/// A [`Viewport`] of the complex plane is mapped onto the image.
+/// The viewport is scaled uniformly to fit inside the image and centered within it,
+/// so that circles are never distorted into ellipses.
+pub struct Canvas {
+    width: u32,
+    height: u32,
+    /// the complex number at the center of the image
+    center: Complexf,
+    /// number of pixels corresponding to one unit along either the real/imaginary axis
+    scale: f64,
+    /// pixel data, stored row by row starting from the top-left corner
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    /// Create a canvas of the given size filled with the `background` color.
+    ///
+    /// # Panics
+    /// If the canvas would have more than [`MAX_CANVAS_PIXELS`] pixels, see [`canvas_pixels`].
+    pub fn new(width: u32, height: u32, viewport: &Viewport, background: Rgba) -> Self {
+        let scale = (width as f64 / viewport.width()).min(height as f64 / viewport.height());
+        let pixels = canvas_pixels(width, height).expect("canvas is too large");
+        Canvas {
+            width,
+            height,
+            center: viewport.center(),
+            scale,
+            pixels: background.repeat(pixels),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The RGBA pixel data of the image, stored row by row starting from the top-left corner.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn into_pixels(self) -> Vec<u8> {
+        self.pixels
+    }
+
+    /// Draw a curve as a stroke of width `line_width` (in pixels).
+    pub fn draw_curve(&mut self, curve: &Curve, color: Rgba, line_width: f64) {
+        let half_width = 0.5 * line_width;
+        // the y-axis points downwards in pixel coordinates, hence directions are conjugated
+        match *curve {
+            Curve::Line { point, slope } => self.draw_line(
+                self.to_pixel(point),
+                slope.conj(),
+                (f64::NEG_INFINITY, f64::INFINITY),
+                color,
+                half_width,
+            ),
+            Curve::Ray { point, direction } => self.draw_line(
+                self.to_pixel(point),
+                direction.conj(),
+                (0.0, f64::INFINITY),
+                color,
+                half_width,
+            ),
+            Curve::Segment { start, end } => {
+                let start = self.to_pixel(start);
+                let direction = self.to_pixel(end) - start;
+                self.draw_line(start, direction, (0.0, direction.norm()), color, half_width);
+            }
+            Curve::Circle { center, radius } => {
+                self.draw_circle(
+                    self.to_pixel(center),
+                    radius * self.scale,
+                    None,
+                    color,
+                    half_width,
+                );
+            }
+            Curve::Arc {
+                center,
+                radius,
+                start,
+                end,
+            } => {
+                self.draw_circle(
+                    self.to_pixel(center),
+                    radius * self.scale,
+                    Some((self.to_pixel(start), self.to_pixel(end))),
+                    color,
+                    half_width,
+                );
+            }
+        }
+    }
+
+    /// Convert a complex number to pixel coordinates, where (0, 0) is the top-left corner of the image.
+    ///
+    /// The pixel coordinates are returned as a complex number for convenience.
+    fn to_pixel(&self, c: Complexf) -> Complexf {
+        let offset = (c - self.center) * self.scale;
+        Complexf::new(
+            0.5 * self.width as f64 + offset.re,
+            0.5 * self.height as f64 - offset.im,
+        )
+    }
+
+    /// Draw the set of pixels within `half_width + 1` of the points `point + direction * t`,
+    /// where `t` lies in the given range and `direction` is normalized to a unit vector.
+    fn draw_line(
+        &mut self,
+        point: Complexf,
+        direction: Complexf,
+        (t_min, t_max): (f64, f64),
+        color: Rgba,
+        half_width: f64,
+    ) {
+        if direction.norm_sqr() == 0.0 {
+            return;
+        }
+        let direction = direction.unscale(direction.norm());
+        let reach = half_width + 1.0;
+        let distance = |x: f64, y: f64| {
+            let offset = Complexf::new(x, y) - point;
+            let t = (offset.re * direction.re + offset.im * direction.im).clamp(t_min, t_max);
+            (offset - direction * t).norm()
+        };
+
+        // walk along whichever axis the line is closer to being parallel to,
+        // so that each step only needs to cover a small band of pixels;
+        // the walk is limited to the extent of the line along that axis
+        if direction.re.abs() >= direction.im.abs() {
+            let band = reach / direction.re.abs();
+            let (x_0, x_1) = (
+                point.re + direction.re * t_min,
+                point.re + direction.re * t_max,
+            );
+            for col in self.pixel_range(x_0.min(x_1) - reach, x_0.max(x_1) + reach, self.width) {
+                let x = col as f64 + 0.5;
+                let y = point.im + (x - point.re) * direction.im / direction.re;
+                for row in self.pixel_range(y - band, y + band, self.height) {
+                    let coverage = coverage(distance(x, row as f64 + 0.5), half_width);
+                    self.blend(col, row, color, coverage);
+                }
+            }
+        } else {
+            let band = reach / direction.im.abs();
+            let (y_0, y_1) = (
+                point.im + direction.im * t_min,
+                point.im + direction.im * t_max,
+            );
+            for row in self.pixel_range(y_0.min(y_1) - reach, y_0.max(y_1) + reach, self.height) {
+                let y = row as f64 + 0.5;
+                let x = point.re + (y - point.im) * direction.re / direction.im;
+                for col in self.pixel_range(x - band, x + band, self.width) {
+                    let coverage = coverage(distance(col as f64 + 0.5, y), half_width);
+                    self.blend(col, row, color, coverage);
+                }
+            }
+        }
+    }
+
+    /// Draw the set of pixels within `half_width + 1` of the circle `|z - center| = radius`, in pixel coordinates.
+    ///
+    /// If endpoints are provided, only the arc running counterclockwise (in the complex plane) from the first endpoint
+    /// to the second is drawn.
+    fn draw_circle(
+        &mut self,
+        center: Complexf,
+        radius: f64,
+        endpoints: Option<(Complexf, Complexf)>,
+        color: Rgba,
+        half_width: f64,
+    ) {
+        let outer = radius + half_width + 1.0;
+        let inner = radius - half_width - 1.0;
+        // angles are measured after flipping the y-axis back, so that they increase counterclockwise in the complex plane
+        let angle = |p: Complexf| (p - center).conj().arg();
+        let distance = |x: f64, y: f64| {
+            let p = Complexf::new(x, y);
+            match endpoints {
+                Some((start, end)) => {
+                    let sweep = (angle(end) - angle(start)).rem_euclid(TAU);
+                    if (angle(p) - angle(start)).rem_euclid(TAU) <= sweep {
+                        ((p - center).norm() - radius).abs()
+                    } else {
+                        (p - start).norm().min((p - end).norm())
+                    }
+                }
+                None => ((p - center).norm() - radius).abs(),
+            }
+        };
+
+        // each row intersects the annulus around the circle in at most two spans of pixels
+        for row in self.pixel_range(center.im - outer, center.im + outer, self.height) {
+            let y = row as f64 + 0.5;
+            let dy = y - center.im;
+            let outer_extent = (outer * outer - dy * dy).max(0.0).sqrt();
+            let inner_extent = if dy.abs() < inner {
+                (inner * inner - dy * dy).sqrt()
+            } else {
+                0.0
+            };
+
+            let left = self.pixel_range(
+                center.re - outer_extent,
+                center.re - inner_extent,
+                self.width,
+            );
+            let right = self.pixel_range(
+                center.re + inner_extent,
+                center.re + outer_extent,
+                self.width,
+            );
+            // avoid visiting (and hence blending) the same pixel twice if the spans overlap
+            let right = right.start.max(left.end)..right.end;
+            for col in left.chain(right) {
+                let coverage = coverage(distance(col as f64 + 0.5, y), half_width);
+                self.blend(col, row, color, coverage);
+            }
+        }
+    }
+
+    /// Compute the range of pixel indices overlapping the interval `[lo, hi]`, clamped to `[0, len)`.
+    fn pixel_range(&self, lo: f64, hi: f64, len: u32) -> std::ops::Range<u32> {
+        // clamp in floating point first, since the bounds may be too large to fit in an integer
+        let lo = lo.floor().clamp(0.0, len as f64) as u32;
+        let hi = (hi.floor() + 1.0).clamp(0.0, len as f64) as u32;
+        lo..hi.max(lo)
+    }
+
+    /// Blend `color` onto a pixel, weighted by the fraction of the pixel covered by the shape being drawn.
+    fn blend(&mut self, col: u32, row: u32, color: Rgba, coverage: f64) {
+        if coverage <= 0.0 {
+            return;
+        }
+        let alpha = coverage * color[3] as f64 / 255.0;
+        let offset = 4 * (row as usize * self.width as usize + col as usize);
+        let pixel = &mut self.pixels[offset..offset + 4];
+        for i in 0..3 {
+            pixel[i] = (color[i] as f64 * alpha + pixel[i] as f64 * (1.0 - alpha)).round() as u8;
+        }
+        pixel[3] = (255.0 * alpha + pixel[3] as f64 * (1.0 - alpha)).round() as u8;
+    }
+}
+
+/// Approximates the fraction of a pixel covered by a stroke,
+/// given the distance from the pixel's center to the center of the stroke.
+#[inline(always)]
+fn coverage(distance: f64, half_width: f64) -> f64 {
+    (half_width + 0.5 - distance).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+#[path = "render_tests.rs"]
+mod tests;
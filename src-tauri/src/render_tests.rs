@@ -0,0 +1,140 @@
+use super::*;
+
+const BLACK: Rgba = [0, 0, 0, 255];
+const WHITE: Rgba = [255, 255, 255, 255];
+
+// canvas where each pixel corresponds to one unit, with the origin at the center
+fn unit_canvas() -> Canvas {
+    let viewport = Viewport {
+        min: Complexf::new(-10.0, -10.0),
+        max: Complexf::new(10.0, 10.0),
+    };
+    Canvas::new(20, 20, &viewport, BLACK)
+}
+
+fn pixel(canvas: &Canvas, col: u32, row: u32) -> Rgba {
+    let offset = 4 * (row * canvas.width() + col) as usize;
+    canvas.pixels()[offset..offset + 4].try_into().unwrap()
+}
+
+#[test]
+fn canvas_background() {
+    let canvas = unit_canvas();
+
+    assert_eq!(canvas.pixels().len(), 20 * 20 * 4);
+    assert!(canvas.pixels().chunks(4).all(|p| p == BLACK));
+}
+
+#[test]
+fn canvas_pixels_bounded() {
+    assert_eq!(canvas_pixels(20, 20), Some(400));
+    assert_eq!(canvas_pixels(8192, 8192), Some(MAX_CANVAS_PIXELS));
+    assert_eq!(canvas_pixels(8192, 8193), None);
+    // the number of pixels overflows a u32
+    assert_eq!(canvas_pixels(65536, 65536), None);
+    assert_eq!(canvas_pixels(u32::MAX, u32::MAX), None);
+}
+
+#[test]
+fn canvas_draw_circle() {
+    let mut canvas = unit_canvas();
+    let circle = Curve::Circle {
+        center: Complexf::ZERO,
+        radius: 5.5f64.hypot(0.5),
+    };
+    canvas.draw_curve(&circle, WHITE, 1.0);
+
+    // pixel (15, 10) is centered at 5.5 - 0.5i, which lies on the circle
+    assert_eq!(pixel(&canvas, 15, 10), WHITE);
+    // pixel (4, 9) is centered at -5.5 + 0.5i, which lies on the circle
+    assert_eq!(pixel(&canvas, 4, 9), WHITE);
+    // the center and corners are far from the circle
+    assert_eq!(pixel(&canvas, 10, 10), BLACK);
+    assert_eq!(pixel(&canvas, 0, 0), BLACK);
+}
+
+#[test]
+fn canvas_draw_line_antialiased() {
+    let mut canvas = unit_canvas();
+    // horizontal line running along the boundary between rows 9 and 10
+    let line = Curve::Line {
+        point: Complexf::ZERO,
+        slope: Complexf::ONE,
+    };
+    canvas.draw_curve(&line, WHITE, 1.0);
+
+    // both rows are only partially covered by the line
+    for row in [9, 10] {
+        let p = pixel(&canvas, 3, row);
+        assert!(
+            p[0] > 0 && p[0] < 255,
+            "pixel should be partially covered: {p:?}"
+        );
+    }
+    assert_eq!(pixel(&canvas, 3, 5), BLACK);
+}
+
+#[test]
+fn canvas_draw_curve_offscreen() {
+    let mut canvas = unit_canvas();
+    let circle = Curve::Circle {
+        center: Complexf::new(1e9, -1e9),
+        radius: 1.0,
+    };
+    canvas.draw_curve(&circle, WHITE, 1.0);
+
+    assert!(canvas.pixels().chunks(4).all(|p| p == BLACK));
+}
+
+#[test]
+fn canvas_draw_arc() {
+    let mut canvas = unit_canvas();
+    // upper half of the circle from the circle test
+    let radius = 5.5f64.hypot(0.5);
+    let arc = Curve::Arc {
+        center: Complexf::ZERO,
+        radius,
+        start: Complexf::new(radius, 0.0),
+        end: Complexf::new(-radius, 0.0),
+    };
+    canvas.draw_curve(&arc, WHITE, 1.0);
+
+    // pixel (4, 9) is centered at -5.5 + 0.5i, which lies on the arc
+    assert_eq!(pixel(&canvas, 4, 9), WHITE);
+    // pixel (15, 10) is centered at 5.5 - 0.5i, which lies on the other half of the circle
+    assert_ne!(pixel(&canvas, 15, 10), WHITE);
+    // the bottom of the circle is not drawn
+    assert_eq!(pixel(&canvas, 10, 16), BLACK);
+}
+
+#[test]
+fn canvas_draw_segment() {
+    let mut canvas = unit_canvas();
+    // horizontal segment through the middle of row 4, from column 5 to column 14
+    let segment = Curve::Segment {
+        start: Complexf::new(-4.5, 5.5),
+        end: Complexf::new(4.5, 5.5),
+    };
+    canvas.draw_curve(&segment, WHITE, 1.0);
+
+    assert_eq!(pixel(&canvas, 5, 4), WHITE);
+    assert_eq!(pixel(&canvas, 14, 4), WHITE);
+    // pixels past the endpoints are not drawn
+    assert_eq!(pixel(&canvas, 2, 4), BLACK);
+    assert_eq!(pixel(&canvas, 17, 4), BLACK);
+}
+
+#[test]
+fn canvas_draw_ray() {
+    let mut canvas = unit_canvas();
+    // vertical ray through the middle of column 12, pointing upwards from row 14
+    let ray = Curve::Ray {
+        point: Complexf::new(2.5, -4.5),
+        direction: Complexf::I,
+    };
+    canvas.draw_curve(&ray, WHITE, 1.0);
+
+    assert_eq!(pixel(&canvas, 12, 14), WHITE);
+    assert_eq!(pixel(&canvas, 12, 0), WHITE);
+    assert_eq!(pixel(&canvas, 12, 17), BLACK);
+}
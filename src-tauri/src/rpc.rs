@@ -0,0 +1,271 @@
+//! Contains a [JSON-RPC 2.0](https://www.jsonrpc.org/specification) server exposing the [`api`] without the app,
+//! e.g. so that notebooks and scripts can drive the exact same computations as the frontend.
+//!
+//! Messages are exchanged one per line, either over stdio or a TCP connection (see [`RpcServer::serve`]).
+//! The methods take their parameters by name, matching the arguments of the Tauri [`commands`]:
+//!  - `generate_mobius_transformation`: `inputs`, `outputs`, `curves` and optionally `viewport`
+//!  - `map_points`: `inputs`, `outputs`, `points` and optionally `inverse`
+//!  - `list_curve_families`: no parameters
+//!
+//! Results are serialized in the same way as for the frontend, e.g.
+//! ```json
+//! --> {"jsonrpc": "2.0", "method": "map_points", "params": {"inputs": [[0, 0], [1, 0], "inf"], "outputs": ["inf", [1, 0], [0, 0]], "points": [[2, 0]]}, "id": 1}
+//! <-- {"jsonrpc": "2.0", "result": [[0.5, 0.0]], "id": 1}
+//! ```
+//! An [`Error`] returned by the API is reported with the code [`API_ERROR_CODE`], and the serialized error as its `data`.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::{Condvar, Mutex, PoisonError};
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::*;
+
+/// The JSON-RPC error code used for the errors returned by the API, from the range reserved for servers.
+pub const API_ERROR_CODE: i64 = -32000;
+
+/// The maximum number of connections served at once by [`RpcServer::serve_tcp`].
+pub const MAX_RPC_CONNECTIONS: usize = 16;
+
+/// An error object of a JSON-RPC response.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        RpcError {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    fn parse_error() -> Self {
+        Self::new(-32700, "Parse error")
+    }
+
+    fn invalid_request() -> Self {
+        Self::new(-32600, "Invalid Request")
+    }
+
+    fn method_not_found(method: &str) -> Self {
+        Self::new(-32601, format!("Method not found: {method}"))
+    }
+
+    fn invalid_params(err: serde_json::Error) -> Self {
+        Self::new(-32602, format!("Invalid params: {err}"))
+    }
+
+    fn internal_error(err: serde_json::Error) -> Self {
+        Self::new(-32603, format!("Internal error: {err}"))
+    }
+}
+
+impl From<Error> for RpcError {
+    fn from(err: Error) -> Self {
+        RpcError {
+            code: API_ERROR_CODE,
+            message: err.to_string(),
+            data: serde_json::to_value(&err).ok(),
+        }
+    }
+}
+
+/// Serves the API over JSON-RPC, using its own set of curve families.
+#[derive(Default)]
+pub struct RpcServer {
+    registry: CurveFamilyRegistry,
+}
+
+impl RpcServer {
+    /// Create a server which renders the curve families in `registry`.
+    pub fn new(registry: CurveFamilyRegistry) -> Self {
+        RpcServer { registry }
+    }
+
+    /// Handle a single message (i.e. a request, notification or batch), returning the response to send back if any.
+    pub fn handle_message(&self, message: &str) -> Option<String> {
+        let response = match serde_json::from_str(message) {
+            Err(_) => Some(error_response(Value::Null, RpcError::parse_error())),
+            Ok(Value::Array(batch)) if batch.is_empty() => {
+                Some(error_response(Value::Null, RpcError::invalid_request()))
+            }
+            Ok(Value::Array(batch)) => {
+                let responses: Vec<_> = batch
+                    .iter()
+                    .filter_map(|request| self.handle_request(request))
+                    .collect();
+                // a batch made up only of notifications gets no response at all
+                (!responses.is_empty()).then_some(Value::Array(responses))
+            }
+            Ok(request) => self.handle_request(&request),
+        };
+        response.map(|response| response.to_string())
+    }
+
+    /// Serve messages read one per line from `reader`, writing each response on its own line to `writer`,
+    /// until the reader is exhausted.
+    pub fn serve<R: BufRead, W: Write>(&self, reader: R, mut writer: W) -> io::Result<()> {
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(response) = self.handle_message(&line) {
+                writeln!(writer, "{response}")?;
+                writer.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Serve each connection accepted by `listener` (see [`RpcServer::serve`]) on its own thread,
+    /// with at most [`MAX_RPC_CONNECTIONS`] served at once.
+    ///
+    /// Never returns, since failing to accept or serve a connection (e.g. because it was reset)
+    /// only affects that connection. Such failures are passed to `on_error`, e.g. so that they can be logged.
+    pub fn serve_tcp(&self, listener: &TcpListener, on_error: impl Fn(io::Error) + Sync) -> ! {
+        let slots = ConnectionSlots::default();
+        thread::scope(|scope| loop {
+            // further clients wait in the listener's backlog until a connection closes
+            let slot = slots.acquire();
+            let stream = match listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(err) => {
+                    on_error(err);
+                    continue;
+                }
+            };
+            let on_error = &on_error;
+            scope.spawn(move || {
+                let _slot = slot;
+                let result = stream
+                    .try_clone()
+                    .and_then(|reader| self.serve(BufReader::new(reader), stream));
+                if let Err(err) = result {
+                    on_error(err);
+                }
+            });
+        })
+    }
+
+    /// Internal helper used by [`RpcServer::handle_message`], which handles a request or notification.
+    fn handle_request(&self, request: &Value) -> Option<Value> {
+        let id = request.get("id").cloned();
+        let method = match (request.get("jsonrpc"), request.get("method")) {
+            (Some(Value::String(version)), Some(Value::String(method))) if version == "2.0" => {
+                method
+            }
+            _ => {
+                return Some(error_response(
+                    id.unwrap_or(Value::Null),
+                    RpcError::invalid_request(),
+                ))
+            }
+        };
+        let empty = Value::Object(Default::default());
+        let result = self.call(method, request.get("params").unwrap_or(&empty));
+
+        // notifications get no response, even when they fail
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "result": result, "id": id }),
+            Err(err) => error_response(id, err),
+        })
+    }
+
+    /// Internal helper used by [`RpcServer::handle_request`], which calls the API.
+    fn call(&self, method: &str, params: &Value) -> Result<Value, RpcError> {
+        let result = match method {
+            "generate_mobius_transformation" => {
+                #[derive(Deserialize)]
+                #[serde(deny_unknown_fields)]
+                struct Params<'a> {
+                    inputs: [ExtComplex; 3],
+                    outputs: [ExtComplex; 3],
+                    #[serde(borrow)]
+                    curves: Vec<CurveFamilyRequest<'a>>,
+                    #[serde(default)]
+                    viewport: Option<Viewport>,
+                }
+                let p = Params::deserialize(params).map_err(RpcError::invalid_params)?;
+                let response = generate_mobius_transformation(
+                    &self.registry,
+                    p.inputs,
+                    p.outputs,
+                    p.curves,
+                    p.viewport,
+                )?;
+                serde_json::to_value(response)
+            }
+            "map_points" => {
+                #[derive(Deserialize)]
+                #[serde(deny_unknown_fields)]
+                struct Params {
+                    inputs: [ExtComplex; 3],
+                    outputs: [ExtComplex; 3],
+                    points: Vec<ExtComplex>,
+                    #[serde(default)]
+                    inverse: bool,
+                }
+                let p = Params::deserialize(params).map_err(RpcError::invalid_params)?;
+                serde_json::to_value(map_points(p.inputs, p.outputs, p.points, p.inverse)?)
+            }
+            "list_curve_families" => {
+                #[derive(Deserialize)]
+                #[serde(deny_unknown_fields)]
+                struct Params {}
+                Params::deserialize(params).map_err(RpcError::invalid_params)?;
+                serde_json::to_value(list_curve_families(&self.registry))
+            }
+            _ => return Err(RpcError::method_not_found(method)),
+        };
+        result.map_err(RpcError::internal_error)
+    }
+}
+
+fn error_response(id: Value, err: RpcError) -> Value {
+    json!({ "jsonrpc": "2.0", "error": err, "id": id })
+}
+
+/// Internal helper used by [`RpcServer::serve_tcp`], which limits the number of connections served at once.
+#[derive(Default)]
+struct ConnectionSlots {
+    active: Mutex<usize>,
+    freed: Condvar,
+}
+
+/// A connection counted by [`ConnectionSlots`], which frees up its slot when dropped.
+struct ConnectionSlot<'a>(&'a ConnectionSlots);
+
+impl ConnectionSlots {
+    /// Wait until fewer than [`MAX_RPC_CONNECTIONS`] connections are being served, then take up a slot.
+    fn acquire(&self) -> ConnectionSlot<'_> {
+        let active = self.active.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut active = self
+            .freed
+            .wait_while(active, |active| *active >= MAX_RPC_CONNECTIONS)
+            .unwrap_or_else(PoisonError::into_inner);
+        *active += 1;
+        ConnectionSlot(self)
+    }
+}
+
+impl Drop for ConnectionSlot<'_> {
+    fn drop(&mut self) {
+        *self.0.active.lock().unwrap_or_else(PoisonError::into_inner) -= 1;
+        self.0.freed.notify_one();
+    }
+}
+
+#[cfg(test)]
+#[path = "rpc_tests.rs"]
+mod tests;
@@ -0,0 +1,220 @@
+use std::io::Cursor;
+use std::net::TcpStream;
+use std::time::Duration;
+
+use super::*;
+
+fn call(server: &RpcServer, request: Value) -> Value {
+    let response = server
+        .handle_message(&request.to_string())
+        .expect("request should get a response");
+    serde_json::from_str(&response).unwrap()
+}
+
+#[test]
+fn rpc_map_points() {
+    let response = call(
+        &RpcServer::default(),
+        json!({
+            "jsonrpc": "2.0",
+            "method": "map_points",
+            "params": {
+                "inputs": [[0, 0], [1, 0], "inf"],
+                "outputs": ["inf", [1, 0], [0, 0]],
+                "points": [[2, 0], [0, 0]]
+            },
+            "id": 1
+        }),
+    );
+
+    assert_eq!(
+        response,
+        json!({ "jsonrpc": "2.0", "result": [[0.5, 0.0], "inf"], "id": 1 })
+    );
+}
+
+#[test]
+fn rpc_generate_mobius_transformation() {
+    let response = call(
+        &RpcServer::default(),
+        json!({
+            "jsonrpc": "2.0",
+            "method": "generate_mobius_transformation",
+            "params": {
+                "inputs": [[0, 0], [1, 0], "inf"],
+                "outputs": [[1, 1], [2, 1], "inf"],
+                "curves": ["polar"]
+            },
+            "id": "a"
+        }),
+    );
+
+    assert_eq!(response["id"], "a");
+    let result = &response["result"];
+    assert!(!result["curves"]["polar"].as_array().unwrap().is_empty());
+    assert_eq!(result["fixed_points"], json!(["inf", "inf"]));
+}
+
+#[test]
+fn rpc_list_curve_families() {
+    let server = RpcServer::default();
+    let response = call(
+        &server,
+        json!({ "jsonrpc": "2.0", "method": "list_curve_families", "id": 1 }),
+    );
+
+    let families = response["result"].as_array().unwrap();
+    assert_eq!(families.len(), server.registry.iter().count());
+    assert_eq!(families[0]["key"], "xy");
+}
+
+#[test]
+fn rpc_api_error() {
+    let response = call(
+        &RpcServer::default(),
+        json!({
+            "jsonrpc": "2.0",
+            "method": "map_points",
+            "params": {
+                "inputs": [[0, 0], [0, 0], "inf"],
+                "outputs": [[0, 0], [1, 0], "inf"],
+                "points": []
+            },
+            "id": 2
+        }),
+    );
+
+    let error = &response["error"];
+    assert_eq!(error["code"], API_ERROR_CODE);
+    assert_eq!(error["data"]["kind"], "DuplicateInputs");
+    assert_eq!(error["data"]["message"], error["message"]);
+    assert_eq!(response["id"], 2);
+}
+
+#[test]
+fn rpc_protocol_errors() {
+    let server = RpcServer::default();
+    let code = |message: &str| {
+        let response: Value =
+            serde_json::from_str(&server.handle_message(message).unwrap()).unwrap();
+        response["error"]["code"].clone()
+    };
+
+    assert_eq!(code("{"), -32700);
+    assert_eq!(code("[]"), -32600);
+    assert_eq!(
+        code(r#"{"method": "list_curve_families", "id": 1}"#),
+        -32600
+    );
+    assert_eq!(
+        code(r#"{"jsonrpc": "2.0", "method": "nonexistent", "id": 1}"#),
+        -32601
+    );
+    assert_eq!(
+        code(r#"{"jsonrpc": "2.0", "method": "map_points", "params": [], "id": 1}"#),
+        -32602
+    );
+    assert_eq!(
+        code(
+            r#"{"jsonrpc": "2.0", "method": "list_curve_families", "params": {"extra": 1}, "id": 1}"#
+        ),
+        -32602
+    );
+}
+
+#[test]
+fn rpc_notifications_and_batches() {
+    let server = RpcServer::default();
+    let notification = json!({ "jsonrpc": "2.0", "method": "list_curve_families" });
+    assert_eq!(server.handle_message(&notification.to_string()), None);
+
+    let batch = json!([
+        notification,
+        { "jsonrpc": "2.0", "method": "nonexistent", "id": 1 },
+        1
+    ]);
+    let responses = call(&server, batch);
+    assert_eq!(
+        responses,
+        json!([
+            { "jsonrpc": "2.0", "error": { "code": -32601, "message": "Method not found: nonexistent" }, "id": 1 },
+            { "jsonrpc": "2.0", "error": { "code": -32600, "message": "Invalid Request" }, "id": null }
+        ])
+    );
+}
+
+#[test]
+fn rpc_serve_lines() {
+    let input = concat!(
+        r#"{"jsonrpc": "2.0", "method": "list_curve_families"}"#,
+        "\n\n",
+        r#"{"jsonrpc": "2.0", "method": "nonexistent", "id": 1}"#,
+        "\n",
+        r#"{"jsonrpc": "2.0", "method": "nonexistent", "id": 2}"#,
+    );
+    let mut output = Vec::new();
+    RpcServer::default()
+        .serve(Cursor::new(input), &mut output)
+        .unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    let ids: Vec<_> = output
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap()["id"].clone())
+        .collect();
+    assert_eq!(ids, [1, 2]);
+}
+
+#[test]
+fn rpc_serve_tcp() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    // the server runs until the test process exits
+    let server: &'static RpcServer = Box::leak(Box::default());
+    thread::spawn(move || server.serve_tcp(&listener, |_| {}));
+
+    let mut stream = TcpStream::connect(address).unwrap();
+    let response = tcp_call(&mut stream, 7).unwrap();
+    assert_eq!(response["id"], 7);
+    assert!(response["result"].is_array());
+}
+
+#[test]
+fn rpc_serve_tcp_limits_connections() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let server: &'static RpcServer = Box::leak(Box::default());
+    thread::spawn(move || server.serve_tcp(&listener, |_| {}));
+
+    let mut open: Vec<_> = (0..MAX_RPC_CONNECTIONS)
+        .map(|id| {
+            let mut stream = TcpStream::connect(address).unwrap();
+            tcp_call(&mut stream, id).expect("connection should be served");
+            stream
+        })
+        .collect();
+
+    // the extra connection is only served once another one closes
+    let mut extra = TcpStream::connect(address).unwrap();
+    extra
+        .set_read_timeout(Some(Duration::from_millis(200)))
+        .unwrap();
+    assert!(tcp_call(&mut extra, MAX_RPC_CONNECTIONS).is_err());
+    open.pop();
+    extra.set_read_timeout(None).unwrap();
+    let mut line = String::new();
+    BufReader::new(&extra).read_line(&mut line).unwrap();
+    let response: Value = serde_json::from_str(&line).unwrap();
+    assert_eq!(response["id"], MAX_RPC_CONNECTIONS);
+}
+
+// sends a request over a connection and reads the response
+fn tcp_call(stream: &mut TcpStream, id: usize) -> io::Result<Value> {
+    writeln!(
+        stream,
+        r#"{{"jsonrpc": "2.0", "method": "list_curve_families", "id": {id}}}"#
+    )?;
+    let mut line = String::new();
+    BufReader::new(&*stream).read_line(&mut line)?;
+    Ok(serde_json::from_str(&line).unwrap())
+}
@@ -0,0 +1,162 @@
+//! Contains the [`Scene`] file format, which saves the state of a [`Session`] so that it can be loaded again later.
+//!
+//! Scenes are stored as JSON in files ending in [`SCENE_FILE_EXTENSION`], e.g.
+//! ```json
+//! {
+//!     "version": 1,
+//!     "points": [
+//!         { "in": [0, 0], "out": [1, 0] },
+//!         { "in": [-5, 0], "out": [-5, 0] },
+//!         { "in": [5, 0], "out": "inf" }
+//!     ],
+//!     "families": [
+//!         { "name": "xy" },
+//!         { "name": "polar", "params": { "step": 0.5 } }
+//!     ],
+//!     "viewport": null
+//! }
+//! ```
+//!
+//! Every file records the version of the format it was written in, so that files in older versions can be migrated
+//! when loaded once the format changes.
+
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::*;
+
+/// The extension of scene files, which are JSON under the hood.
+pub const SCENE_FILE_EXTENSION: &str = ".mobius.json";
+
+/// The version of the format which scenes are written in.
+///
+/// Must be incremented whenever the schema of [`Scene`] changes, along with adding a migration from the previous version.
+pub const SCENE_VERSION: u64 = 1;
+
+/// The state of a [`Session`] which is saved to a scene file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scene {
+    /// The version of the format, which is always [`SCENE_VERSION`] once loaded.
+    pub version: u64,
+    /// The three control points, each mapping an input onto an output.
+    pub points: [ScenePoint; 3],
+    /// The enabled curve families, in the order they were enabled.
+    pub families: Vec<SceneFamily>,
+    /// The region the curves are clipped to, if any.
+    pub viewport: Option<Viewport>,
+}
+
+/// A control point saved in a [`Scene`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenePoint {
+    #[serde(rename = "in")]
+    pub input: ExtComplex,
+    #[serde(rename = "out")]
+    pub output: ExtComplex,
+}
+
+/// A curve family saved in a [`Scene`], along with the parameters it is generated with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SceneFamily {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "FamilyParams::is_empty")]
+    pub params: FamilyParams,
+}
+
+impl Scene {
+    /// The input control points, in order.
+    pub fn inputs(&self) -> [ExtComplex; 3] {
+        self.points.map(|p| p.input)
+    }
+
+    /// The output control points, in order.
+    pub fn outputs(&self) -> [ExtComplex; 3] {
+        self.points.map(|p| p.output)
+    }
+
+    /// The enabled curve families, in the form used to request them.
+    pub fn family_requests(&self) -> Vec<CurveFamilyRequest<'_>> {
+        self.families
+            .iter()
+            .map(|family| CurveFamilyRequest::WithParams {
+                name: &family.name,
+                params: family.params.clone(),
+            })
+            .collect()
+    }
+}
+
+/// An error encountered while reading or writing a scene file.
+#[derive(Debug)]
+pub enum SceneError {
+    /// The file could not be read or written.
+    Io(io::Error),
+    /// The file is not valid JSON, or does not match the schema of its version.
+    Parse(serde_json::Error),
+    /// The file is missing its version, or was written by a newer version of the app.
+    UnsupportedVersion { version: Option<u64> },
+}
+
+impl fmt::Display for SceneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not access the scene file: {err}"),
+            Self::Parse(err) => write!(f, "the scene file is malformed: {err}"),
+            Self::UnsupportedVersion { version: None } => {
+                write!(f, "the scene file does not have a valid version")
+            }
+            Self::UnsupportedVersion {
+                version: Some(version),
+            } => write!(
+                f,
+                "the scene file has version {version}, but only versions up to {SCENE_VERSION} are supported"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SceneError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Parse(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Read a scene from a file, migrating it to the current version if needed.
+pub fn read_scene_file(path: &Path) -> Result<Scene, SceneError> {
+    let json = std::fs::read_to_string(path).map_err(SceneError::Io)?;
+    parse_scene(&json)
+}
+
+/// Write a scene to a file, in the current version of the format.
+pub fn write_scene_file(scene: &Scene, path: &Path) -> Result<(), SceneError> {
+    let json = serde_json::to_string_pretty(scene).map_err(SceneError::Parse)?;
+    std::fs::write(path, json).map_err(SceneError::Io)
+}
+
+/// Parse the contents of a scene file, migrating it to the current version if needed.
+pub fn parse_scene(json: &str) -> Result<Scene, SceneError> {
+    let value: Value = serde_json::from_str(json).map_err(SceneError::Parse)?;
+
+    // migrations from older versions go here, each bringing `value` up to the next version
+    match value.get("version").and_then(Value::as_u64) {
+        Some(SCENE_VERSION) => {}
+        version => return Err(SceneError::UnsupportedVersion { version }),
+    }
+
+    serde_json::from_value(value).map_err(SceneError::Parse)
+}
+
+#[cfg(test)]
+#[path = "scene_tests.rs"]
+mod tests;
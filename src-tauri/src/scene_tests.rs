@@ -0,0 +1,113 @@
+use serde_json::json;
+
+use super::*;
+
+fn scene() -> Scene {
+    Scene {
+        version: SCENE_VERSION,
+        points: [
+            ScenePoint {
+                input: ExtComplex::new(0.0, 0.0),
+                output: ExtComplex::new(1.0, 0.0),
+            },
+            ScenePoint {
+                input: ExtComplex::new(-5.0, 0.0),
+                output: ExtComplex::new(-5.0, 0.0),
+            },
+            ScenePoint {
+                input: ExtComplex::new(5.0, 0.0),
+                output: ExtComplex::Inf,
+            },
+        ],
+        families: vec![
+            SceneFamily {
+                name: "xy".to_owned(),
+                params: FamilyParams::new(),
+            },
+            SceneFamily {
+                name: "polar".to_owned(),
+                params: json!({ "step": 0.5 }).as_object().unwrap().clone(),
+            },
+        ],
+        viewport: None,
+    }
+}
+
+#[test]
+fn serialize_scene() {
+    let json = serde_json::to_value(scene()).expect("serialization should succeed");
+    let expected = json!({
+        "version": 1,
+        "points": [
+            { "in": [0.0, 0.0], "out": [1.0, 0.0] },
+            { "in": [-5.0, 0.0], "out": [-5.0, 0.0] },
+            { "in": [5.0, 0.0], "out": "inf" }
+        ],
+        "families": [
+            { "name": "xy" },
+            { "name": "polar", "params": { "step": 0.5 } }
+        ],
+        "viewport": null
+    });
+
+    assert_eq!(json, expected);
+}
+
+#[test]
+fn parse_scene_roundtrip() {
+    let json = serde_json::to_string(&scene()).unwrap();
+    assert_eq!(parse_scene(&json).unwrap(), scene());
+}
+
+#[test]
+fn parse_scene_unsupported_version() {
+    for (json, expected) in [
+        (r#"{ "version": 2 }"#, Some(2)),
+        (r#"{ "points": [], "families": [] }"#, None),
+        (r#"{ "version": "1" }"#, None),
+        (r#"{ "version": -1 }"#, None),
+    ] {
+        assert!(matches!(
+            parse_scene(json),
+            Err(SceneError::UnsupportedVersion { version }) if version == expected
+        ));
+    }
+}
+
+#[test]
+fn parse_scene_malformed() {
+    for json in [
+        "not json",
+        r#"{ "version": 1 }"#,
+        r#"{ "version": 1, "points": [], "families": [], "viewport": null }"#,
+    ] {
+        let err = parse_scene(json).expect_err("scene should be malformed");
+        assert!(matches!(err, SceneError::Parse(_)));
+        assert!(err.to_string().starts_with("the scene file is malformed"));
+    }
+}
+
+#[test]
+fn save_and_load_session() {
+    let registry = CurveFamilyRegistry::default();
+    let scene = scene();
+    let mut session = Session::default();
+    session
+        .start(
+            &registry,
+            scene.inputs(),
+            scene.outputs(),
+            scene.family_requests(),
+            None,
+        )
+        .unwrap();
+
+    let path = std::env::temp_dir().join(format!("mobius_app_save_scene{SCENE_FILE_EXTENSION}"));
+    let saved = save_scene(&session, path.clone());
+    let loaded = load_scene(path.clone());
+    std::fs::remove_file(&path).unwrap();
+
+    saved.unwrap();
+    assert_eq!(loaded.unwrap(), scene);
+    assert!(matches!(load_scene(path), Err(Error::SceneFailed { .. })));
+}
@@ -0,0 +1,516 @@
+//! Contains the [`Session`], which holds the state being edited so that the frontend can update it incrementally.
+//!
+//! Rather than resending every control point and curve family whenever anything changes (as with
+//! [`generate_mobius_transformation`]), the frontend [starts][Session::start] a session once and then sends
+//! each change on its own, getting back only the curves which changed.
+//! Edits to the control points and curve families are recorded in a [`History`] so that they can be undone.
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use nalgebra::Matrix2;
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+/// Identifies one side of a control point mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum ControlPointSide {
+    #[serde(rename = "in")]
+    Input,
+    #[serde(rename = "out")]
+    Output,
+}
+
+/// Thresholds used by a [`Session`] when computing transformations.
+///
+/// These are settings of the session rather than part of what is being edited,
+/// so they are neither recorded in its history nor saved in a [`Scene`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Tolerances {
+    /// The threshold on the determinant below which a transformation counts as singular, see [`SINGULAR_TOLERANCE`].
+    pub singular: f64,
+}
+
+impl Default for Tolerances {
+    fn default() -> Self {
+        Self {
+            singular: SINGULAR_TOLERANCE,
+        }
+    }
+}
+
+/// The state of the transformation being edited by the frontend.
+///
+/// The session is updated even when the resulting transformation doesn't exist, so that the user can keep moving
+/// control points until it does. Updates which are invalid (e.g. non-finite control points) are rejected without
+/// changing the session.
+#[derive(Debug, Clone)]
+pub struct Session {
+    inputs: [ExtComplex; 3],
+    outputs: [ExtComplex; 3],
+    /// The enabled curve families, in the order they were enabled.
+    families: Vec<(String, FamilyParams)>,
+    viewport: Option<Viewport>,
+    tolerances: Tolerances,
+    // partial transformations of the inputs and the outputs (see `math::compute_partial_mobius_tform`),
+    // cached so that moving a control point only rebuilds the side it is on
+    input_map: Matrix2<Complexf>,
+    output_map: Matrix2<Complexf>,
+    history: History<SessionSnapshot>,
+}
+
+/// The part of a [`Session`] which is restored when undoing an edit.
+#[derive(Debug, Clone)]
+struct SessionSnapshot {
+    inputs: [ExtComplex; 3],
+    outputs: [ExtComplex; 3],
+    families: Vec<(String, FamilyParams)>,
+}
+
+/// The edits which can be undone and redone in a [`Session`], along with its current state.
+#[derive(Debug, Serialize)]
+pub struct SessionHistory {
+    #[serde(flatten)]
+    pub edits: HistorySummary,
+    pub inputs: [ExtComplex; 3],
+    pub outputs: [ExtComplex; 3],
+    /// The names of the enabled curve families, in the order they were enabled.
+    pub families: Vec<String>,
+}
+
+/// The changes resulting from an update to a [`Session`].
+#[derive(Debug, Serialize)]
+pub struct SessionUpdate {
+    /// The curve families whose curves changed, transformed by the current transformation.
+    ///
+    /// Enabled families which are missing are unchanged.
+    pub curves: HashMap<String, Vec<Curve>>,
+    /// The curve families which were disabled, whose curves should no longer be displayed.
+    pub removed: Vec<String>,
+    /// The fixed points of the current transformation, see [`GenerateMobiusResponse::fixed_points`].
+    pub fixed_points: Option<[ExtComplex; 2]>,
+    /// The coefficients of the current transformation, along with some of its properties.
+    pub transform: MobiusTformInfo,
+}
+
+impl Default for Session {
+    /// A session with the identity transformation (mapping 0, 1 and infinity onto themselves) and no curve families.
+    fn default() -> Self {
+        let points = [
+            ExtComplex::new(0.0, 0.0),
+            ExtComplex::new(1.0, 0.0),
+            ExtComplex::Inf,
+        ];
+        let map = math::compute_partial_mobius_tform(&points);
+        Self {
+            inputs: points,
+            outputs: points,
+            families: Vec::new(),
+            viewport: None,
+            tolerances: Tolerances::default(),
+            input_map: map,
+            output_map: map,
+            history: History::default(),
+        }
+    }
+}
+
+impl Session {
+    /// Replaces the entire state of the session (forgetting its history), returning the curves of every requested family.
+    ///
+    /// Fails under the same conditions as [`generate_mobius_transformation`],
+    /// in which case the session is only updated if the transformation fails to exist.
+    pub fn start(
+        &mut self,
+        registry: &CurveFamilyRegistry,
+        inputs: [ExtComplex; 3],
+        outputs: [ExtComplex; 3],
+        curves: Vec<CurveFamilyRequest>,
+        viewport: Option<Viewport>,
+    ) -> Result<SessionUpdate, Error> {
+        lookup_curve_families(registry, &curves)?;
+
+        self.inputs = inputs;
+        self.outputs = outputs;
+        self.families = curves
+            .iter()
+            .map(|request| (request.name().to_owned(), request.params()))
+            .collect();
+        self.viewport = viewport;
+        self.history.clear();
+        self.input_map = math::compute_partial_mobius_tform(&self.inputs);
+        self.output_map = math::compute_partial_mobius_tform(&self.outputs);
+        self.update_all(registry)
+    }
+
+    /// Moves one side of the control point at the given index (0, 1 or 2), returning the curves of every enabled family.
+    ///
+    /// Consecutive moves of the same control point are recorded as a single edit, see [`COALESCE_WINDOW`].
+    pub fn set_control_point(
+        &mut self,
+        registry: &CurveFamilyRegistry,
+        index: usize,
+        side: ControlPointSide,
+        value: ExtComplex,
+    ) -> Result<SessionUpdate, Error> {
+        self.set_control_point_at(registry, index, side, value, Instant::now())
+    }
+
+    /// Internal helper for [`Session::set_control_point`], which takes the time of the edit to allow testing.
+    fn set_control_point_at(
+        &mut self,
+        registry: &CurveFamilyRegistry,
+        index: usize,
+        side: ControlPointSide,
+        value: ExtComplex,
+        now: Instant,
+    ) -> Result<SessionUpdate, Error> {
+        self.move_control_point(index, side, value, now)?;
+        self.update_all(registry)
+    }
+
+    /// Does the same as [`Session::set_control_point`], but only answers the latest of a stream of moves
+    /// tagged with sequence numbers (see [`RequestSequence`]), e.g. those sent while the user drags a control point.
+    ///
+    /// Since each move gives the new position outright, a move which was superseded before it is handled is skipped,
+    /// leaving the session to the later move. A move superseded while its curves are being transformed is kept,
+    /// but the curves are abandoned (between families) so that the later move isn't held up.
+    /// Moves may still be answered out of order, so the caller should discard an answer older than one it has already
+    /// received, which is why each answer carries the sequence number of its move.
+    ///
+    /// The move must already have been recorded with [`RequestSequence::begin`] (answering it as superseded if that
+    /// fails), which the caller does before waiting for the session so that a move still holding it sees the new one.
+    pub fn set_latest_control_point(
+        &mut self,
+        registry: &CurveFamilyRegistry,
+        sequence: &RequestSequence,
+        seq: u64,
+        index: usize,
+        side: ControlPointSide,
+        value: ExtComplex,
+    ) -> Sequenced<SessionUpdate> {
+        let superseded = || Sequenced::Superseded {
+            seq,
+            latest: sequence.latest(),
+        };
+        let is_superseded = || sequence.is_superseded(seq);
+        if is_superseded() {
+            return superseded();
+        }
+
+        let result = self
+            .move_control_point(index, side, value, Instant::now())
+            .and_then(|()| self.update_all_unless(registry, &is_superseded));
+        match result {
+            // even a finished update is stale if a later move came in meanwhile
+            _ if is_superseded() => superseded(),
+            Ok(Some(result)) => Sequenced::Completed { seq, result },
+            Ok(None) => superseded(),
+            Err(error) => Sequenced::Failed { seq, error },
+        }
+    }
+
+    /// Internal helper for [`Session::set_control_point`], which moves the control point (recording the edit)
+    /// without transforming any curves.
+    fn move_control_point(
+        &mut self,
+        index: usize,
+        side: ControlPointSide,
+        value: ExtComplex,
+        now: Instant,
+    ) -> Result<(), Error> {
+        if index >= 3 {
+            return Err(Error::UnknownControlPoint { index });
+        }
+        let (mut inputs, mut outputs) = (self.inputs, self.outputs);
+        match side {
+            ControlPointSide::Input => inputs[index] = value,
+            ControlPointSide::Output => outputs[index] = value,
+        }
+
+        let description = match side {
+            ControlPointSide::Input => format!("Move input {}", index + 1),
+            ControlPointSide::Output => format!("Move output {}", index + 1),
+        };
+        self.history.record(description, self.snapshot(), true, now);
+
+        // only the partial transformation of the side which moved needs to be rebuilt
+        match side {
+            ControlPointSide::Input => {
+                self.inputs = inputs;
+                self.input_map = math::compute_partial_mobius_tform(&self.inputs);
+            }
+            ControlPointSide::Output => {
+                self.outputs = outputs;
+                self.output_map = math::compute_partial_mobius_tform(&self.outputs);
+            }
+        }
+        Ok(())
+    }
+
+    /// Enables or disables a curve family, returning just the curves of that family if it was enabled.
+    ///
+    /// Enabling a family which is already enabled replaces its parameters, while disabling a family which isn't enabled
+    /// does nothing. Unlike moving a control point, the session is left unchanged if the toggle fails,
+    /// e.g. because the transformation doesn't exist.
+    pub fn toggle_family(
+        &mut self,
+        registry: &CurveFamilyRegistry,
+        family: CurveFamilyRequest,
+        enabled: bool,
+    ) -> Result<SessionUpdate, Error> {
+        // the update is worked out before the session is changed, so that a failed toggle leaves it (and its history)
+        // as it was, matching the frontend which discards the toggle
+        let name = family.name();
+        if !enabled {
+            let mut update = self.update_none()?;
+            if self.families.iter().any(|(n, _)| n == name) {
+                let before = self.snapshot();
+                self.families.retain(|(n, _)| n != name);
+                self.history
+                    .record(format!("Disable {name}"), before, false, Instant::now());
+                update.removed = vec![name.to_owned()];
+            }
+            return Ok(update);
+        }
+
+        lookup_curve_families(registry, std::slice::from_ref(&family))?;
+        let params = family.params();
+        let inv_tform = self.inv_tform()?;
+        let curves = self.transform_families(
+            registry,
+            vec![CurveFamilyRequest::WithParams {
+                name,
+                params: params.clone(),
+            }],
+            &inv_tform,
+        )?;
+
+        let before = self.snapshot();
+        match self.families.iter_mut().find(|(n, _)| n == name) {
+            Some((_, old_params)) if *old_params == params => {}
+            Some((_, old_params)) => {
+                *old_params = params;
+                self.history
+                    .record(format!("Change {name}"), before, false, Instant::now());
+            }
+            None => {
+                self.families.push((name.to_owned(), params));
+                self.history
+                    .record(format!("Enable {name}"), before, false, Instant::now());
+            }
+        }
+        Ok(self.update(curves, vec![], &inv_tform))
+    }
+
+    /// Changes the thresholds used when computing transformations, returning the curves of every enabled family.
+    ///
+    /// Fails without changing the session if any of the thresholds is negative or not finite.
+    pub fn set_tolerances(
+        &mut self,
+        registry: &CurveFamilyRegistry,
+        tolerances: Tolerances,
+    ) -> Result<SessionUpdate, Error> {
+        if !(tolerances.singular.is_finite() && tolerances.singular >= 0.0) {
+            return Err(Error::InvalidTolerance {
+                name: "singular".to_owned(),
+            });
+        }
+        self.tolerances = tolerances;
+        self.update_all(registry)
+    }
+
+    /// Undoes the last edit to the control points or curve families, returning the curves of every enabled family.
+    ///
+    /// Does nothing if there are no edits to undo.
+    /// As with the edit itself, fails if the transformation doesn't exist after the edit is undone.
+    pub fn undo(&mut self, registry: &CurveFamilyRegistry) -> Result<SessionUpdate, Error> {
+        match self.history.undo(self.snapshot()) {
+            Some(snapshot) => self.restore(registry, snapshot),
+            None => self.update_none(),
+        }
+    }
+
+    /// Redoes the last undone edit, returning the curves of every enabled family.
+    ///
+    /// Does nothing if there are no edits to redo.
+    pub fn redo(&mut self, registry: &CurveFamilyRegistry) -> Result<SessionUpdate, Error> {
+        match self.history.redo(self.snapshot()) {
+            Some(snapshot) => self.restore(registry, snapshot),
+            None => self.update_none(),
+        }
+    }
+
+    /// Describes the edits which can be undone and redone, along with the current state of the session,
+    /// e.g. for the frontend to catch up after undoing an edit.
+    pub fn history(&self) -> SessionHistory {
+        SessionHistory {
+            edits: self.history.summary(),
+            inputs: self.inputs,
+            outputs: self.outputs,
+            families: self.families.iter().map(|(name, _)| name.clone()).collect(),
+        }
+    }
+
+    /// Captures the control points, curve families and viewport of the session, so that they can be saved.
+    pub fn to_scene(&self) -> Scene {
+        Scene {
+            version: SCENE_VERSION,
+            points: [0, 1, 2].map(|i| ScenePoint {
+                input: self.inputs[i],
+                output: self.outputs[i],
+            }),
+            families: self
+                .families
+                .iter()
+                .map(|(name, params)| SceneFamily {
+                    name: name.clone(),
+                    params: params.clone(),
+                })
+                .collect(),
+            viewport: self.viewport,
+        }
+    }
+
+    /// The current input control points.
+    pub fn inputs(&self) -> &[ExtComplex; 3] {
+        &self.inputs
+    }
+
+    /// The current output control points.
+    pub fn outputs(&self) -> &[ExtComplex; 3] {
+        &self.outputs
+    }
+
+    /// The current thresholds used when computing transformations.
+    pub fn tolerances(&self) -> Tolerances {
+        self.tolerances
+    }
+
+    /// The inverse of the current transformation, which is what curves are transformed by
+    /// (see [`generate_mobius_transformation`]).
+    fn inv_tform(&self) -> Result<Matrix2<Complexf>, Error> {
+        math::compose_partial_mobius_tforms(
+            &self.output_map,
+            &self.input_map,
+            self.tolerances.singular,
+        )
+        .ok_or_else(|| diagnose_singular_tform(&self.inputs, &self.outputs))
+    }
+
+    /// Internal helper which captures the state restored when undoing an edit.
+    fn snapshot(&self) -> SessionSnapshot {
+        SessionSnapshot {
+            inputs: self.inputs,
+            outputs: self.outputs,
+            families: self.families.clone(),
+        }
+    }
+
+    /// Internal helper which returns to a snapshot taken from the history.
+    fn restore(
+        &mut self,
+        registry: &CurveFamilyRegistry,
+        snapshot: SessionSnapshot,
+    ) -> Result<SessionUpdate, Error> {
+        let removed = self
+            .families
+            .iter()
+            .filter(|(name, _)| !snapshot.families.iter().any(|(n, _)| n == name))
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        if self.inputs != snapshot.inputs {
+            self.inputs = snapshot.inputs;
+            self.input_map = math::compute_partial_mobius_tform(&self.inputs);
+        }
+        if self.outputs != snapshot.outputs {
+            self.outputs = snapshot.outputs;
+            self.output_map = math::compute_partial_mobius_tform(&self.outputs);
+        }
+        self.families = snapshot.families;
+
+        let mut update = self.update_all(registry)?;
+        update.removed = removed;
+        Ok(update)
+    }
+
+    /// Internal helper for updates which don't change anything.
+    fn update_none(&self) -> Result<SessionUpdate, Error> {
+        let inv_tform = self.inv_tform()?;
+        Ok(self.update(HashMap::new(), vec![], &inv_tform))
+    }
+
+    /// Internal helper which transforms every enabled family, for updates which change the transformation.
+    fn update_all(&self, registry: &CurveFamilyRegistry) -> Result<SessionUpdate, Error> {
+        let update = self.update_all_unless(registry, &|| false)?;
+        Ok(update.expect("an update which is never abandoned should finish"))
+    }
+
+    /// Internal helper for [`Session::update_all`], which abandons the update (returning `None`)
+    /// once `is_superseded` returns true, checking it before transforming each family.
+    fn update_all_unless(
+        &self,
+        registry: &CurveFamilyRegistry,
+        is_superseded: &dyn Fn() -> bool,
+    ) -> Result<Option<SessionUpdate>, Error> {
+        let inv_tform = self.inv_tform()?;
+        let requests: Vec<_> = self
+            .families
+            .iter()
+            .map(|(name, params)| CurveFamilyRequest::WithParams {
+                name,
+                params: params.clone(),
+            })
+            .collect();
+        // every family is looked up first, so that an unknown family is reported before any work is done
+        lookup_curve_families(registry, &requests)?;
+
+        let mut curves = HashMap::new();
+        for request in requests {
+            if is_superseded() {
+                return Ok(None);
+            }
+            curves.extend(self.transform_families(registry, vec![request], &inv_tform)?);
+        }
+        Ok(Some(self.update(curves, vec![], &inv_tform)))
+    }
+
+    /// Internal helper which transforms the given families, keyed by their (owned) names.
+    fn transform_families(
+        &self,
+        registry: &CurveFamilyRegistry,
+        requests: Vec<CurveFamilyRequest>,
+        inv_tform: &Matrix2<Complexf>,
+    ) -> Result<HashMap<String, Vec<Curve>>, Error> {
+        let families = lookup_curve_families(registry, &requests)?;
+        let curves =
+            transform_curve_families(families, requests, inv_tform, self.viewport.as_ref())?;
+        Ok(curves
+            .into_iter()
+            .map(|(name, curves)| (name.to_owned(), curves))
+            .collect())
+    }
+
+    /// Internal helper which packages the changed curves along with the current transformation.
+    fn update(
+        &self,
+        curves: HashMap<String, Vec<Curve>>,
+        removed: Vec<String>,
+        inv_tform: &Matrix2<Complexf>,
+    ) -> SessionUpdate {
+        SessionUpdate {
+            curves,
+            removed,
+            // a transformation has the same fixed points as its inverse
+            fixed_points: math::compute_fixed_points(inv_tform),
+            transform: describe_mobius_tform(&math::invert_mobius_tform(inv_tform)),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "session_tests.rs"]
+mod tests;
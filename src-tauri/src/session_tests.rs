@@ -0,0 +1,401 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use super::*;
+
+fn inputs() -> [ExtComplex; 3] {
+    [
+        ExtComplex::new(0.0, 0.0),
+        ExtComplex::new(-5.0, 0.0),
+        ExtComplex::new(5.0, 0.0),
+    ]
+}
+
+// starts a session with the identity transformation and the given families enabled
+fn start(registry: &CurveFamilyRegistry, curves: Vec<CurveFamilyRequest>) -> Session {
+    let mut session = Session::default();
+    session
+        .start(registry, inputs(), inputs(), curves, None)
+        .expect("transformation should exist");
+    session
+}
+
+#[test]
+fn session_start_returns_all_families() {
+    let registry = CurveFamilyRegistry::default();
+    let mut session = Session::default();
+    let update = session
+        .start(
+            &registry,
+            inputs(),
+            inputs(),
+            vec!["xy".into(), "polar".into()],
+            None,
+        )
+        .unwrap();
+
+    assert_eq!(update.curves.len(), 2);
+    assert!(update.removed.is_empty());
+    assert_eq!(update.transform.formula, "z");
+}
+
+#[test]
+fn session_set_control_point_matches_api() {
+    let registry = CurveFamilyRegistry::default();
+    let mut session = start(&registry, vec!["xy".into()]);
+
+    let mut outputs = inputs();
+    outputs[1] = ExtComplex::new(-4.0, 2.0);
+    session
+        .set_control_point(&registry, 1, ControlPointSide::Output, outputs[1])
+        .unwrap();
+    outputs[2] = ExtComplex::Inf;
+    let update = session
+        .set_control_point(&registry, 2, ControlPointSide::Output, outputs[2])
+        .unwrap();
+    let expected =
+        generate_mobius_transformation(&registry, inputs(), outputs, vec!["xy".into()], None)
+            .unwrap();
+
+    assert_eq!(session.outputs(), &outputs);
+    assert_eq!(update.curves["xy"], expected.curves["xy"]);
+    assert_eq!(update.fixed_points, expected.fixed_points);
+    assert_eq!(update.transform.formula, expected.transform.formula);
+}
+
+#[test]
+fn session_keeps_singular_state() {
+    let registry = CurveFamilyRegistry::default();
+    let mut session = start(&registry, vec!["xy".into()]);
+
+    // moving an input onto another one is recorded, even though no transformation exists
+    let result = session.set_control_point(
+        &registry,
+        2,
+        ControlPointSide::Input,
+        ExtComplex::new(0.0, 0.0),
+    );
+    assert!(matches!(
+        result,
+        Err(Error::DuplicateInputs { indices: [0, 2] })
+    ));
+    assert_eq!(session.inputs()[2], ExtComplex::new(0.0, 0.0));
+
+    // families can't be toggled in the meantime, and the enabled ones are returned once the transformation exists again
+    assert!(session
+        .toggle_family(&registry, "polar".into(), true)
+        .is_err());
+    assert!(session
+        .toggle_family(&registry, "xy".into(), false)
+        .is_err());
+    let update = session
+        .set_control_point(&registry, 2, ControlPointSide::Input, ExtComplex::Inf)
+        .unwrap();
+    assert_eq!(update.curves.keys().collect::<Vec<_>>(), ["xy"]);
+}
+
+#[test]
+fn session_failed_toggle_changes_nothing() {
+    const CURVES: &[Matrix2<Complexf>] = &[Matrix2::new(
+        Complexf::ZERO,
+        Complexf::ZERO,
+        Complexf::ZERO,
+        Complexf::ZERO,
+    )];
+    let mut registry = CurveFamilyRegistry::default();
+    registry.register(StaticFamily {
+        name: "degenerate",
+        display_name: "Degenerate",
+        curves: CURVES,
+    });
+    let mut session = start(&registry, vec!["xy".into()]);
+    session
+        .toggle_family(&registry, "polar".into(), true)
+        .unwrap();
+    let before = session.history();
+
+    assert!(matches!(
+        session.toggle_family(&registry, "degenerate".into(), true),
+        Err(Error::DegenerateCurve { .. })
+    ));
+    let after = session.history();
+    assert_eq!(after.families, before.families);
+    assert_eq!(after.edits.undo, before.edits.undo);
+
+    // the toggle is rejected rather than recorded, so undoing still disables the family enabled before it
+    let update = session.undo(&registry).unwrap();
+    assert_eq!(update.removed, ["polar"]);
+}
+
+#[test]
+fn session_rejects_invalid_updates() {
+    let registry = CurveFamilyRegistry::default();
+    let mut session = start(&registry, vec![]);
+
+    assert!(matches!(
+        session.set_control_point(&registry, 3, ControlPointSide::Input, ExtComplex::Inf),
+        Err(Error::UnknownControlPoint { index: 3 })
+    ));
+    assert!(matches!(
+        session.toggle_family(&registry, "unknown".into(), true),
+        Err(Error::UnknownCurveFamily { .. })
+    ));
+    assert_eq!(session.outputs(), &inputs());
+}
+
+#[test]
+fn session_toggle_family_returns_only_changes() {
+    let registry = CurveFamilyRegistry::default();
+    let mut session = start(&registry, vec!["xy".into()]);
+
+    let update = session
+        .toggle_family(&registry, "polar".into(), true)
+        .unwrap();
+    assert_eq!(update.curves.keys().collect::<Vec<_>>(), ["polar"]);
+
+    let update = session
+        .toggle_family(&registry, "xy".into(), false)
+        .unwrap();
+    assert!(update.curves.is_empty());
+    assert_eq!(update.removed, ["xy"]);
+
+    // disabling a family twice does nothing
+    let update = session
+        .toggle_family(&registry, "xy".into(), false)
+        .unwrap();
+    assert!(update.removed.is_empty());
+
+    let update = session
+        .set_control_point(
+            &registry,
+            0,
+            ControlPointSide::Output,
+            ExtComplex::new(1.0, 1.0),
+        )
+        .unwrap();
+    assert_eq!(update.curves.keys().collect::<Vec<_>>(), ["polar"]);
+}
+
+#[test]
+fn session_latest_control_point() {
+    let registry = CurveFamilyRegistry::default();
+    let mut session = start(&registry, vec!["xy".into()]);
+    let mut expected = session.clone();
+    let sequence = RequestSequence::default();
+    let value = ExtComplex::new(1.0, 1.0);
+
+    let expected = expected
+        .set_control_point(&registry, 0, ControlPointSide::Output, value)
+        .unwrap();
+    assert!(sequence.begin(1));
+    match session.set_latest_control_point(
+        &registry,
+        &sequence,
+        1,
+        0,
+        ControlPointSide::Output,
+        value,
+    ) {
+        Sequenced::Completed { seq: 1, result } => {
+            assert_eq!(result.curves, expected.curves);
+            assert_eq!(result.transform.formula, expected.transform.formula);
+        }
+        other => panic!("unexpected outcome {other:?}"),
+    }
+
+    assert!(sequence.begin(2));
+    let result = session.set_latest_control_point(
+        &registry,
+        &sequence,
+        2,
+        0,
+        ControlPointSide::Output,
+        ExtComplex::new(-5.0, 0.0),
+    );
+    assert!(matches!(
+        result,
+        Sequenced::Failed {
+            seq: 2,
+            error: Error::DuplicateOutputs { .. }
+        }
+    ));
+}
+
+#[test]
+fn session_latest_control_point_skips_superseded() {
+    let registry = CurveFamilyRegistry::default();
+    let mut session = start(&registry, vec!["xy".into()]);
+    let sequence = RequestSequence::default();
+    assert!(sequence.begin(5));
+
+    let result = session.set_latest_control_point(
+        &registry,
+        &sequence,
+        3,
+        0,
+        ControlPointSide::Output,
+        ExtComplex::new(1.0, 1.0),
+    );
+    assert!(matches!(
+        result,
+        Sequenced::Superseded { seq: 3, latest: 5 }
+    ));
+    assert_eq!(
+        serde_json::to_value(result).unwrap(),
+        serde_json::json!({ "status": "superseded", "seq": 3, "latest": 5 })
+    );
+    // the later move gives the position to use, so the stale one isn't applied
+    assert_eq!(session.outputs(), &inputs());
+    assert!(session.history().edits.undo.is_empty());
+}
+
+#[test]
+fn session_latest_control_point_abandons_superseded() {
+    /// A family which makes a later move while it is being generated during a move, as if the user kept dragging.
+    struct InterruptingFamily(Arc<RequestSequence>);
+
+    impl CurveFamily for InterruptingFamily {
+        fn name(&self) -> &str {
+            "interrupting"
+        }
+
+        fn display_name(&self) -> &str {
+            "Interrupting"
+        }
+
+        fn generate(&self, _params: &FamilyParams) -> Vec<Matrix2<Complexf>> {
+            // no move has been made yet while the session is being started
+            let latest = self.0.latest();
+            if latest > 0 {
+                self.0.begin(latest + 1);
+            }
+            vec![]
+        }
+    }
+
+    let sequence = Arc::new(RequestSequence::default());
+    let mut registry = CurveFamilyRegistry::default();
+    registry.register(InterruptingFamily(sequence.clone()));
+    let mut session = Session::default();
+    session
+        .start(
+            &registry,
+            inputs(),
+            inputs(),
+            vec!["interrupting".into(), "xy".into()],
+            None,
+        )
+        .unwrap();
+
+    let value = ExtComplex::new(1.0, 1.0);
+    assert!(sequence.begin(1));
+    let result = session.set_latest_control_point(
+        &registry,
+        &sequence,
+        1,
+        0,
+        ControlPointSide::Output,
+        value,
+    );
+    assert!(matches!(
+        result,
+        Sequenced::Superseded { seq: 1, latest: 2 }
+    ));
+    // the move itself is kept, only its curves are abandoned
+    assert_eq!(session.outputs()[0], value);
+}
+
+#[test]
+fn session_tolerances() {
+    let registry = CurveFamilyRegistry::default();
+    let mut session = start(&registry, vec![]);
+
+    assert_eq!(session.tolerances(), Tolerances::default());
+    assert!(matches!(
+        session.set_tolerances(&registry, Tolerances { singular: 1e6 }),
+        Err(Error::DuplicateInputs { .. } | Error::DuplicateOutputs { .. })
+    ));
+    assert!(session
+        .set_tolerances(&registry, Tolerances::default())
+        .is_ok());
+
+    for singular in [-1.0, f64::NAN, f64::INFINITY] {
+        assert!(matches!(
+            session.set_tolerances(&registry, Tolerances { singular }),
+            Err(Error::InvalidTolerance { .. })
+        ));
+    }
+    assert_eq!(session.tolerances(), Tolerances::default());
+}
+
+#[test]
+fn session_undo_redo_drag() {
+    let registry = CurveFamilyRegistry::default();
+    let mut session = start(&registry, vec!["xy".into()]);
+    let now = Instant::now();
+
+    // a continuous drag into a singular configuration is undone in one go
+    for (i, x) in [1.0, 3.0, -5.0].into_iter().enumerate() {
+        let _ = session.set_control_point_at(
+            &registry,
+            0,
+            ControlPointSide::Output,
+            ExtComplex::new(x, 0.0),
+            now + COALESCE_WINDOW / 2 * i as u32,
+        );
+    }
+    assert_eq!(session.history().edits.undo, ["Move output 1"]);
+    assert!(session.redo(&registry).is_err());
+
+    let update = session
+        .undo(&registry)
+        .expect("transformation should exist");
+    assert_eq!(session.outputs(), &inputs());
+    assert_eq!(update.transform.formula, "z");
+    assert_eq!(update.curves.len(), 1);
+
+    assert!(session.redo(&registry).is_err());
+    assert_eq!(session.outputs()[0], ExtComplex::new(-5.0, 0.0));
+}
+
+#[test]
+fn session_undo_family_toggles() {
+    let registry = CurveFamilyRegistry::default();
+    let mut session = start(&registry, vec!["xy".into()]);
+    session
+        .toggle_family(&registry, "polar".into(), true)
+        .unwrap();
+    session
+        .toggle_family(&registry, "xy".into(), false)
+        .unwrap();
+    // disabling a family which isn't enabled isn't recorded
+    session
+        .toggle_family(&registry, "xy".into(), false)
+        .unwrap();
+    assert_eq!(session.history().edits.undo, ["Enable polar", "Disable xy"]);
+
+    let update = session.undo(&registry).unwrap();
+    assert_eq!(update.curves.len(), 2);
+    assert!(update.removed.is_empty());
+
+    let update = session.undo(&registry).unwrap();
+    assert_eq!(update.removed, ["polar"]);
+    assert_eq!(session.history().families, ["xy"]);
+
+    // nothing is left to undo, so nothing changes
+    let update = session.undo(&registry).unwrap();
+    assert!(update.curves.is_empty());
+    assert_eq!(session.history().edits.redo, ["Disable xy", "Enable polar"]);
+}
+
+#[test]
+fn session_start_clears_history() {
+    let registry = CurveFamilyRegistry::default();
+    let mut session = start(&registry, vec![]);
+    session.toggle_family(&registry, "xy".into(), true).unwrap();
+    session
+        .start(&registry, inputs(), inputs(), vec![], None)
+        .unwrap();
+
+    assert!(session.history().edits.undo.is_empty());
+}
@@ -0,0 +1,203 @@
+//! Contains share codes, which pack the control points and curve families of a [`Scene`] into a short string
+//! that can be pasted into a chat message or an issue.
+//!
+//! A share code is a binary encoding of the scene, written with URL-safe base64 (without padding).
+//! The binary encoding is laid out as follows, with numbers stored in little-endian order:
+//!  - the version of the encoding, as a `u8` (see [`SHARE_CODE_VERSION`])
+//!  - a `u8` whose lowest 6 bits flag which of the inputs and outputs (in that order) are infinite
+//!  - the real and imaginary parts of each finite input and output, as `f64`s
+//!  - the number of curve families, as a `u8`, followed by each family as:
+//!    - the length of its name as a `u8`, followed by the name in UTF-8
+//!    - the length of its parameters as a `u16`, followed by the parameters as JSON (or nothing if there are none)
+//!  - a CRC-32 checksum of everything before it, as a `u32`
+//!
+//! The viewport of the scene is not included, since it depends on the size of the window.
+
+use std::fmt;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+
+use super::*;
+
+/// The version of the encoding which share codes are written in.
+pub const SHARE_CODE_VERSION: u8 = 1;
+
+/// An error encountered while encoding or decoding a share code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShareCodeError {
+    /// The code is not valid URL-safe base64.
+    InvalidBase64,
+    /// The checksum doesn't match the contents of the code, e.g. because part of it was lost when copying it.
+    ChecksumMismatch,
+    /// The code was written by a newer version of the app.
+    UnsupportedVersion { version: u8 },
+    /// The code has a valid checksum, but its contents don't match the encoding.
+    Malformed { reason: String },
+    /// The scene can't be encoded, because it has too many curve families
+    /// or a family whose name or parameters are too long.
+    TooLarge { reason: String },
+}
+
+impl fmt::Display for ShareCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidBase64 => write!(f, "the share code contains invalid characters"),
+            Self::ChecksumMismatch => {
+                write!(f, "the share code is corrupted or incomplete")
+            }
+            Self::UnsupportedVersion { version } => write!(
+                f,
+                "the share code has version {version}, but only versions up to {SHARE_CODE_VERSION} are supported"
+            ),
+            Self::Malformed { reason } => write!(f, "the share code is malformed: {reason}"),
+            Self::TooLarge { reason } => write!(f, "the scene is too large to share: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ShareCodeError {}
+
+/// Encode the control points and curve families of a scene as a share code.
+///
+/// Fails if the scene has more than 255 curve families, or a family whose name is longer than 255 bytes
+/// or whose parameters take up more than 64 KiB as JSON, which can happen for user-defined families.
+pub fn encode_share_code(scene: &Scene) -> Result<String, ShareCodeError> {
+    let mut bytes = vec![SHARE_CODE_VERSION];
+
+    let points = scene.inputs().into_iter().chain(scene.outputs());
+    let infinite = points
+        .clone()
+        .enumerate()
+        .filter(|(_, p)| *p == ExtComplex::Inf)
+        .fold(0u8, |flags, (i, _)| flags | 1 << i);
+    bytes.push(infinite);
+    for p in points {
+        if let ExtComplex::Val(v) = p {
+            bytes.extend(v.re.to_le_bytes());
+            bytes.extend(v.im.to_le_bytes());
+        }
+    }
+
+    let count = u8::try_from(scene.families.len())
+        .map_err(|_| too_large("there are too many curve families"))?;
+    bytes.push(count);
+    for family in &scene.families {
+        let name = family.name.as_bytes();
+        let length = u8::try_from(name.len())
+            .map_err(|_| too_large(&format!("the name of \"{}\" is too long", family.name)))?;
+        bytes.push(length);
+        bytes.extend(name);
+        let params = if family.params.is_empty() {
+            Vec::new()
+        } else {
+            serde_json::to_vec(&family.params).expect("parameters should be serializable")
+        };
+        let length = u16::try_from(params.len()).map_err(|_| {
+            too_large(&format!(
+                "the parameters of \"{}\" are too long",
+                family.name
+            ))
+        })?;
+        bytes.extend(length.to_le_bytes());
+        bytes.extend(params);
+    }
+
+    let checksum = crc32fast::hash(&bytes);
+    bytes.extend(checksum.to_le_bytes());
+    Ok(URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// Decode a share code into a scene (without a viewport).
+///
+/// Surrounding whitespace is ignored, since it is easily picked up when copying the code.
+pub fn decode_share_code(code: &str) -> Result<Scene, ShareCodeError> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(code.trim())
+        .map_err(|_| ShareCodeError::InvalidBase64)?;
+    let (bytes, checksum) = match bytes.len().checked_sub(4) {
+        Some(split) => bytes.split_at(split),
+        None => return Err(ShareCodeError::ChecksumMismatch),
+    };
+    if crc32fast::hash(bytes).to_le_bytes() != checksum {
+        return Err(ShareCodeError::ChecksumMismatch);
+    }
+
+    let mut reader = ByteReader(bytes);
+    let version = reader.read::<1>()?[0];
+    if version != SHARE_CODE_VERSION {
+        return Err(ShareCodeError::UnsupportedVersion { version });
+    }
+
+    let infinite = reader.read::<1>()?[0];
+    let mut points = [ExtComplex::Inf; 6];
+    for (i, p) in points.iter_mut().enumerate() {
+        if infinite & 1 << i == 0 {
+            let re = f64::from_le_bytes(reader.read()?);
+            let im = f64::from_le_bytes(reader.read()?);
+            *p = ExtComplex::try_new(re, im).ok_or_else(|| malformed("a control point is nan"))?;
+        }
+    }
+
+    let count = reader.read::<1>()?[0];
+    let mut families = Vec::with_capacity(count.into());
+    for _ in 0..count {
+        let length = reader.read::<1>()?[0];
+        let name = String::from_utf8(reader.read_slice(length.into())?.to_vec())
+            .map_err(|_| malformed("a curve family name is not valid UTF-8"))?;
+        let length = u16::from_le_bytes(reader.read()?);
+        let params = match reader.read_slice(length.into())? {
+            [] => FamilyParams::new(),
+            params => serde_json::from_slice(params)
+                .map_err(|_| malformed("curve family parameters are not a JSON object"))?,
+        };
+        families.push(SceneFamily { name, params });
+    }
+    if !reader.0.is_empty() {
+        return Err(malformed("unexpected data at the end"));
+    }
+
+    Ok(Scene {
+        version: SCENE_VERSION,
+        points: [0, 1, 2].map(|i| ScenePoint {
+            input: points[i],
+            output: points[i + 3],
+        }),
+        families,
+        viewport: None,
+    })
+}
+
+fn malformed(reason: &str) -> ShareCodeError {
+    ShareCodeError::Malformed {
+        reason: reason.to_owned(),
+    }
+}
+
+fn too_large(reason: &str) -> ShareCodeError {
+    ShareCodeError::TooLarge {
+        reason: reason.to_owned(),
+    }
+}
+
+/// Internal helper used by [`decode_share_code`] to read through the decoded bytes.
+struct ByteReader<'a>(&'a [u8]);
+
+impl<'a> ByteReader<'a> {
+    fn read_slice(&mut self, length: usize) -> Result<&'a [u8], ShareCodeError> {
+        if self.0.len() < length {
+            return Err(malformed("unexpected end of data"));
+        }
+        let (read, rest) = self.0.split_at(length);
+        self.0 = rest;
+        Ok(read)
+    }
+
+    fn read<const N: usize>(&mut self) -> Result<[u8; N], ShareCodeError> {
+        Ok(self.read_slice(N)?.try_into().unwrap())
+    }
+}
+
+#[cfg(test)]
+#[path = "share_tests.rs"]
+mod tests;
@@ -0,0 +1,182 @@
+use serde_json::json;
+
+use super::*;
+
+fn scene() -> Scene {
+    Scene {
+        version: SCENE_VERSION,
+        points: [
+            ScenePoint {
+                input: ExtComplex::new(0.0, 0.0),
+                output: ExtComplex::new(0.1, -2.5),
+            },
+            ScenePoint {
+                input: ExtComplex::Inf,
+                output: ExtComplex::new(-5.0, 1e-300),
+            },
+            ScenePoint {
+                input: ExtComplex::new(5.0, 0.0),
+                output: ExtComplex::Inf,
+            },
+        ],
+        families: vec![
+            SceneFamily {
+                name: "xy".to_owned(),
+                params: FamilyParams::new(),
+            },
+            SceneFamily {
+                name: "apollo".to_owned(),
+                params: json!({ "foci": [[0, 0], "inf"], "count": 5 })
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+            },
+        ],
+        viewport: None,
+    }
+}
+
+#[test]
+fn share_code_roundtrip() {
+    let code = encode_share_code(&scene()).unwrap();
+
+    assert!(code
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+    assert_eq!(decode_share_code(&code), Ok(scene()));
+    assert_eq!(decode_share_code(&format!("  {code}\n")), Ok(scene()));
+}
+
+#[test]
+fn share_code_drops_viewport() {
+    let mut scene = scene();
+    scene.viewport = Some(Viewport {
+        min: Complexf::new(-1.0, -1.0),
+        max: Complexf::new(1.0, 1.0),
+    });
+    let code = encode_share_code(&scene).unwrap();
+
+    assert_eq!(decode_share_code(&code).unwrap().viewport, None);
+}
+
+#[test]
+fn share_code_is_short() {
+    let mut scene = scene();
+    scene.families.truncate(1);
+
+    // 1 byte of version, 1 of flags, 4 * 16 of points, 1 + 1 + 2 + 2 of families and 4 of checksum, in base64
+    assert_eq!(
+        encode_share_code(&scene).unwrap().len(),
+        (76 * 4_usize).div_ceil(3)
+    );
+}
+
+#[test]
+fn share_code_too_large() {
+    let family = SceneFamily {
+        name: "xy".to_owned(),
+        params: FamilyParams::new(),
+    };
+    let long_name = SceneFamily {
+        name: "x".repeat(256),
+        params: FamilyParams::new(),
+    };
+    let long_params = SceneFamily {
+        name: "custom".to_owned(),
+        params: json!({ "label": "x".repeat(u16::MAX.into()) })
+            .as_object()
+            .unwrap()
+            .clone(),
+    };
+    let many_families = vec![family; 256];
+
+    for families in [vec![long_name], vec![long_params], many_families] {
+        let scene = Scene {
+            families,
+            ..scene()
+        };
+        assert!(matches!(
+            encode_share_code(&scene),
+            Err(ShareCodeError::TooLarge { .. })
+        ));
+    }
+}
+
+#[test]
+fn share_code_corrupted() {
+    let code = encode_share_code(&scene()).unwrap();
+
+    assert_eq!(
+        decode_share_code("not a share code!"),
+        Err(ShareCodeError::InvalidBase64)
+    );
+    assert_eq!(
+        decode_share_code(&code[..code.len() - 4]),
+        Err(ShareCodeError::ChecksumMismatch)
+    );
+    assert_eq!(decode_share_code(""), Err(ShareCodeError::ChecksumMismatch));
+
+    // flip a character in the middle of the code
+    let mut corrupted = code.into_bytes();
+    corrupted[10] = if corrupted[10] == b'A' { b'B' } else { b'A' };
+    assert_eq!(
+        decode_share_code(std::str::from_utf8(&corrupted).unwrap()),
+        Err(ShareCodeError::ChecksumMismatch)
+    );
+}
+
+#[test]
+fn share_code_unsupported_version() {
+    let mut bytes = vec![SHARE_CODE_VERSION + 1];
+    bytes.extend(crc32fast::hash(&bytes).to_le_bytes());
+    let code = URL_SAFE_NO_PAD.encode(bytes);
+
+    assert_eq!(
+        decode_share_code(&code),
+        Err(ShareCodeError::UnsupportedVersion {
+            version: SHARE_CODE_VERSION + 1
+        })
+    );
+}
+
+#[test]
+fn share_code_malformed() {
+    // codes with valid checksums, but which are cut short or contain nan
+    let mut nan = vec![SHARE_CODE_VERSION, 0b111110];
+    nan.extend(f64::NAN.to_le_bytes());
+    nan.extend(0.0f64.to_le_bytes());
+    for mut bytes in [vec![SHARE_CODE_VERSION, 0b111111], nan] {
+        bytes.extend(crc32fast::hash(&bytes).to_le_bytes());
+        let code = URL_SAFE_NO_PAD.encode(bytes);
+
+        assert!(matches!(
+            decode_share_code(&code),
+            Err(ShareCodeError::Malformed { .. })
+        ));
+    }
+}
+
+#[test]
+fn invoke_share_scene() {
+    let registry = CurveFamilyRegistry::default();
+    let scene = scene();
+    let mut session = Session::default();
+    session
+        .start(
+            &registry,
+            scene.inputs(),
+            scene.outputs(),
+            scene.family_requests(),
+            None,
+        )
+        .unwrap();
+
+    assert_eq!(
+        load_share_code(&share_scene(&session).unwrap()).unwrap(),
+        scene
+    );
+    assert!(matches!(
+        load_share_code("AAAA"),
+        Err(Error::InvalidShareCode { .. })
+    ));
+}
@@ -1,18 +1,19 @@
 import { useContext, useEffect, useRef } from "react";
 import { Extent2d, PIXELS_PER_UNIT } from "../model/coord";
 import styles from "./graph.module.css";
-import { Curve, CurveSet } from "../model/backend";
+import { Curve, CurveFamilyInfo, CurveSet } from "../model/backend";
 import { ThemeContext } from "../theme/schema";
 
 interface GraphCanvasProps {
     readonly extent: Extent2d;
     readonly curves: CurveSet;
+    readonly families: CurveFamilyInfo[];
 }
 
 /**
  * Displays a 2d grid and draws the Mobius transformation contour lines via the Canvas API.
  */
-export function GraphCanvas({ extent, curves }: GraphCanvasProps) {
+export function GraphCanvas({ extent, curves, families }: GraphCanvasProps) {
     const ref = useRef<CanvasRenderingContext2D | null>(null);
     const theme = useContext(ThemeContext);
 
@@ -38,18 +39,23 @@ export function GraphCanvas({ extent, curves }: GraphCanvasProps) {
             ctxt.strokeStyle = theme.props.background3;
             drawGridlines(ctxt);
 
-            ctxt.strokeStyle = theme.props.foreground2;
-            for (const curveList of Object.values(curves)) {
+            for (const [key, curveList] of Object.entries(curves)) {
+                const style = families.find(family => family.key === key)?.default_style;
+                ctxt.strokeStyle = style?.color ?? theme.props.foreground2;
+                ctxt.lineWidth = style?.line_width ?? 1;
+                ctxt.setLineDash(style?.dashed ? [6, 4] : []);
                 for (const curve of curveList) {
                     drawCurve(ctxt, curve, extent);
                 }
             }
+            ctxt.lineWidth = 1;
+            ctxt.setLineDash([]);
         });
 
         return () => {
             window.cancelAnimationFrame(handle);
         };
-    }, [extent, curves, theme]);
+    }, [extent, curves, families, theme]);
 
     function getContextFromRef(element: HTMLCanvasElement | null) {
         if (element === null) {
@@ -137,5 +143,36 @@ function drawCurve(ctxt: CanvasRenderingContext2D, curve: Curve, extent: Extent2
             ctxt.stroke();
             break;
         }
+        case 'arc': {
+            // the canvas is flipped vertically, so increasing angles run counterclockwise on screen
+            const [cx, cy] = curve.center;
+            ctxt.beginPath();
+            ctxt.arc(
+                cx * PIXELS_PER_UNIT,
+                cy * PIXELS_PER_UNIT,
+                curve.radius * PIXELS_PER_UNIT,
+                Math.atan2(curve.start[1] - cy, curve.start[0] - cx),
+                Math.atan2(curve.end[1] - cy, curve.end[0] - cx));
+            ctxt.stroke();
+            break;
+        }
+        case 'segment': {
+            ctxt.beginPath();
+            ctxt.moveTo(curve.start[0] * PIXELS_PER_UNIT, curve.start[1] * PIXELS_PER_UNIT);
+            ctxt.lineTo(curve.end[0] * PIXELS_PER_UNIT, curve.end[1] * PIXELS_PER_UNIT);
+            ctxt.stroke();
+            break;
+        }
+        case 'ray': {
+            // use extent to determine how far to extend the ray
+            const t = Math.max(extent.width, extent.height) / Math.hypot(curve.direction[0], curve.direction[1]);
+            const x = curve.point[0] * PIXELS_PER_UNIT;
+            const y = curve.point[1] * PIXELS_PER_UNIT;
+            ctxt.beginPath();
+            ctxt.moveTo(x, y);
+            ctxt.lineTo(x + t * curve.direction[0], y + t * curve.direction[1]);
+            ctxt.stroke();
+            break;
+        }
     }
 }
@@ -4,7 +4,7 @@ import { Extent2d } from "../model/coord";
 import { useElementExtent } from "../hooks/useElementExtent";
 import { GraphCanvas } from "./GraphCanvas";
 import { MovablePoint } from "./MovablePoint";
-import { ControlPointKey, ExtComplex, GlobalState } from "../model/backend";
+import { ControlPointKey, CurveFamilyInfo, ExtComplex, GlobalState, isControlPointAtFault } from "../model/backend";
 import { GlobalStateDispatch } from "../hooks/useGlobalState";
 
 // Initial value based on 1100x720 window size.
@@ -16,6 +16,7 @@ const INITIAL_EXTENT: Extent2d = {
 };
 
 interface MobiusGraphProps {
+    readonly families: CurveFamilyInfo[];
     readonly globalState: GlobalState;
     readonly dispatch: GlobalStateDispatch;
 }
@@ -23,7 +24,7 @@ interface MobiusGraphProps {
 /**
  * Renders a visualization of the current Mobius transformation.
  */
-export function MobiusGraph({ globalState, dispatch }: MobiusGraphProps) {
+export function MobiusGraph({ families, globalState, dispatch }: MobiusGraphProps) {
     const ref = useRef<HTMLDivElement>(null);
     const extent = useElementExtent(ref, INITIAL_EXTENT);
 
@@ -36,6 +37,7 @@ export function MobiusGraph({ globalState, dispatch }: MobiusGraphProps) {
         return <MovablePoint
             key={key}
             value={value.out}
+            alert={isControlPointAtFault(globalState.error, key as ControlPointKey, 'out')}
             onChange={action}
             container={extent}
         />;
@@ -43,7 +45,7 @@ export function MobiusGraph({ globalState, dispatch }: MobiusGraphProps) {
 
     return (
         <div ref={ref} className={styles.graphContainer}>
-            <GraphCanvas extent={extent} curves={globalState.curves} />
+            <GraphCanvas extent={extent} curves={globalState.curves} families={families} />
             {points}
         </div>
     );
@@ -5,6 +5,8 @@ import styles from "./graph.module.css";
 
 interface MovablePointProps {
     readonly value: ExtComplex;
+    /** Whether to highlight the point, e.g. because it collides with another point. */
+    readonly alert?: boolean;
     readonly onChange: (c: C.Complex) => void;
     readonly container: C.Extent2d;
 }
@@ -15,7 +17,7 @@ interface MovablePointProps {
  * NOTE: for the transform to be computed correctly,
  * the parent element for this component should have 0 padding and 0 border.
  */
-export function MovablePoint({ value, onChange, container }: MovablePointProps) {
+export function MovablePoint({ value, alert, onChange, container }: MovablePointProps) {
     const ref = useRef<HTMLDivElement>(null);
     const [clicked, setClicked] = useState(false);
 
@@ -44,7 +46,7 @@ export function MovablePoint({ value, onChange, container }: MovablePointProps)
     return (
         <div
             ref={ref}
-            className={styles.controlPoint}
+            className={alert ? `${styles.controlPoint} ${styles.controlPointAlert}` : styles.controlPoint}
             style={style}
             onPointerMove={e => {
                 if (clicked) {
@@ -1,4 +1,4 @@
-import { useRef, useState } from "react";
+import { useState } from "react";
 import * as M from "../model/backend";
 
 interface SetControlPointAction {
@@ -14,12 +14,16 @@ interface ToggleCurveFamilyAction {
     value: boolean;
 }
 
+interface UndoAction {
+    type: 'undo' | 'redo';
+}
+
 /**
  * Defines the schema for the valid actions that can be passed into the dispatch function returned by `useGlobalState`.
  * 
  * Each action must specify a unique 'type' label that identifies it.
  */
-export type GlobalStateDispatchAction = SetControlPointAction | ToggleCurveFamilyAction;
+export type GlobalStateDispatchAction = SetControlPointAction | ToggleCurveFamilyAction | UndoAction;
 
 export type GlobalStateDispatch = (a: GlobalStateDispatchAction) => void;
 
@@ -27,34 +31,30 @@ export type GlobalStateDispatch = (a: GlobalStateDispatchAction) => void;
  * A hook that providers all the state needed to represent a Mobius transformation and its derivative data,
  * as well as a dispatch function to modify this state.
  * 
+ * The state mirrors the session held by the backend (see `startSession`), which is updated one change at a time.
  * The dispatch function makes a backend API call and can not be used in pure code.
  */
 export function useGlobalState(initial: M.GlobalState): [M.GlobalState, GlobalStateDispatch] {
     const [globalState, setGlobalState] = useState(initial);
-    // hidden de-normalized state caching the list of used curves to make backend API call more convenient
-    const usedCurves = useRef(getUsedCurves(initial.curves));
 
     // format is similar to reducer pattern. We don't use reducer since non-pure operations are needed.
     // this dispatch function can only be invoked outside of rendering (e.g. in event handlers)
     const dispatch = async (action: GlobalStateDispatchAction) => {
         switch (action.type) {
             case 'set-mapping': {
-                const oldMapping = globalState.points[action.key];
-                const newMapping: M.SamplePointMapping = {
-                    in: action.in || oldMapping.in,
-                    out: action.out || oldMapping.out,
-                };
-                const newMappingSet: M.MappingSet = {
-                    ...globalState.points,
-                    [action.key]: newMapping
-                };
-
-                const newGlobalState = await M.generateMobiusTransformation({
-                    points: newMappingSet,
-                    usedCurves: usedCurves.current
-                });
+                // only send the sides of the control point which were provided
+                let newGlobalState: M.GlobalState | null = globalState;
+                if (action.in && newGlobalState) {
+                    newGlobalState = await M.setControlPoint(newGlobalState, action.key, 'in', action.in);
+                }
+                if (action.out && newGlobalState) {
+                    newGlobalState = await M.setControlPoint(newGlobalState, action.key, 'out', action.out);
+                }
 
-                setGlobalState(newGlobalState);
+                // a move is answered with null once a later move was made, whose state is rendered instead
+                if (newGlobalState) {
+                    setGlobalState(newGlobalState);
+                }
                 break;
             }
             case "toggle-curves": {
@@ -64,36 +64,12 @@ export function useGlobalState(initial: M.GlobalState): [M.GlobalState, GlobalSt
                     return;
                 }
 
-                let newGlobalState;
-                if (action.value) {
-                    // toggle on
-                    const response = await M.generateMobiusTransformation({
-                        points: globalState.points,
-                        usedCurves: [action.key]
-                    });
-
-                    const newCurves = {
-                        ...globalState.curves,
-                        ...response.curves
-                    }
-                    newGlobalState = {
-                        points: globalState.points,
-                        curves: newCurves,
-                        exists: response.exists,
-                    };
-                } else {
-                    // toggle off
-                    const newCurves = { ...globalState.curves };
-                    delete newCurves[action.key];
-                    newGlobalState = {
-                        ...globalState,
-                        curves: newCurves
-                    };
-                }
-
-                setGlobalState(newGlobalState);
-                // update reference to keep it in sync with globalState
-                usedCurves.current = getUsedCurves(newGlobalState.curves);
+                setGlobalState(await M.toggleFamily(globalState, action.key, action.value));
+                break;
+            }
+            case 'undo':
+            case 'redo': {
+                setGlobalState(await M.undoEdit(globalState, action.type));
                 break;
             }
         }
@@ -101,8 +77,3 @@ export function useGlobalState(initial: M.GlobalState): [M.GlobalState, GlobalSt
 
     return [globalState, dispatch];
 }
-
-function getUsedCurves(curves: M.CurveSet): M.CurveFamilyKey[] {
-    return (Object.keys(M.CURVE_FAMILY_NAMES) as M.CurveFamilyKey[])
-        .filter(key => curves[key] !== undefined);
-}
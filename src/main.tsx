@@ -19,11 +19,14 @@ const INITIAL_MAPPING: B.GenerateMobiusTransformationProps = {
 const INITIAL_THEME: T.Theme = T.lookupTheme(T.DEFAULT_THEME)!;
 T.syncCSSWithTheme(INITIAL_THEME);
 
-// first call backend to generate the initial set of curves to display, prior to rendering
-B.generateMobiusTransformation(INITIAL_MAPPING).then(initialState =>
+// first call backend to list the curve families and generate the initial set of curves to display, prior to rendering
+Promise.all([
+    B.listCurveFamilies(),
+    B.startSession(INITIAL_MAPPING),
+]).then(([families, initialState]) =>
     createRoot(document.getElementById("root") as HTMLElement).render(
         <StrictMode>
-            <App initial={initialState} initialTheme={INITIAL_THEME} />
+            <App families={families} initial={initialState} initialTheme={INITIAL_THEME} />
         </StrictMode>
     )
 );
@@ -12,7 +12,7 @@
  */
 
 import { Complex } from "./coord";
-import { invoke } from "@tauri-apps/api/tauri";
+import { invoke, InvokeArgs } from "@tauri-apps/api/tauri";
 
 /** An number in the extended complex plane, which includes the point at infinity. */
 export type ExtComplex = Complex | 'inf';
@@ -29,19 +29,83 @@ interface Line {
     slope: Complex;
 }
 
-/** A curve which is either a circle or a line. Can be drawn onto a canvas. */
-export type Curve = Circle | Line;
+/** The part of a circle traced counterclockwise from `start` to `end`. */
+interface Arc {
+    type: 'arc';
+    center: Complex;
+    radius: number;
+    start: Complex;
+    end: Complex;
+}
+
+interface Segment {
+    type: 'segment';
+    start: Complex;
+    end: Complex;
+}
+
+/** The half of a line starting at `point` and extending in the direction of `direction`. */
+interface Ray {
+    type: 'ray';
+    point: Complex;
+    direction: Complex;
+}
+
+/** A curve which is either a circle or a line, or a piece of one. Can be drawn onto a canvas. */
+export type Curve = Circle | Line | Arc | Segment | Ray;
+
+/** The key of a curve family recognized by the backend (see `listCurveFamilies`). */
+export type CurveFamilyKey = string;
+
+/** Describes a parameter accepted by a curve family. */
+export interface ParamSchema {
+    name: string;
+    description: string;
+    type: 'number' | 'integer' | 'points';
+    /** The value used when the parameter is not provided. */
+    default: unknown;
+}
+
+/** How the curves of a curve family are drawn. */
+export interface CurveStyle {
+    /** A CSS color, or null to use the color of the current theme. */
+    color: string | null;
+    /** Width of the curves, in pixels. */
+    line_width: number;
+    dashed: boolean;
+}
+
+/** Describes a curve family available in the backend. */
+export interface CurveFamilyInfo {
+    key: CurveFamilyKey;
+    display_name: string;
+    description: string;
+    params: ParamSchema[];
+    default_style: CurveStyle;
+}
+
+/**
+ * Calls the backend to list the available curve families, including any defined by the user.
+ * @returns The curve families, in the order they should be listed.
+ */
+export async function listCurveFamilies(): Promise<CurveFamilyInfo[]> {
+    return await invoke('list_curve_families') as CurveFamilyInfo[];
+}
 
-/** Defines the keys for valid curve families recognized by the backend, as well as their display names. */
-export const CURVE_FAMILY_NAMES = {
-    xy: 'Cartesian gridlines',
-    polar: 'Polar gridlines',
-    apollo: 'Apollonian circles'
-} as const;
-export type CurveFamilyKey = keyof typeof CURVE_FAMILY_NAMES;
+/**
+ * A request for a curve family, either by key alone (using the default parameters)
+ * or by key along with parameters controlling how the family is generated, e.g. `{ name: 'xy', step: 0.25, extent: 5 }`.
+ */
+export type CurveFamilyRequest = CurveFamilyKey | { name: CurveFamilyKey, [param: string]: unknown };
 
 /** Contains a set of curve families transformed under the current Mobius transformation being rendered. */
-export type CurveSet = Partial<Record<CurveFamilyKey, Curve[]>>;
+export type CurveSet = Record<CurveFamilyKey, Curve[]>;
+
+/** An axis-aligned rectangle of the complex plane, given by its bottom-left and top-right corners. */
+export interface Viewport {
+    min: Complex;
+    max: Complex;
+}
 
 /** A sample input/output pair for a data point mapped under a Mobius transformation. */
 export interface SamplePointMapping {
@@ -86,11 +150,82 @@ export interface GlobalState {
 
     /** Whether or not a valid Mobius transformation exists for the provided `MappingSet`. Used as an error flag. */
     exists: boolean;
+
+    /** The reason the Mobius transformation could not be computed, if it doesn't exist. */
+    error: ApiError | null;
+
+    /**
+     * The fixed points of the Mobius transformation, which the foci of the Apollonian circles can be snapped to.
+     * Null if the transformation is the identity or does not exist.
+     */
+    fixedPoints: [ExtComplex, ExtComplex] | null;
+
+    /** The coefficients and properties of the Mobius transformation, or null if it does not exist. */
+    transform: MobiusTransformInfo | null;
+}
+
+/** Describes a Mobius transformation `f(z) = (az + b) / (cz + d)` (refer to `MobiusTformInfo` in `src-tauri/src/api.rs`). */
+export interface MobiusTransformInfo {
+    /** The coefficients `[[a, b], [c, d]]`, scaled so that `c = 1`, or `d = 1` if `c = 0`. */
+    matrix: [[Complex, Complex], [Complex, Complex]];
+    /** The coefficients of the inverse transformation, scaled in the same way. */
+    inverse: [[Complex, Complex], [Complex, Complex]];
+    /** The transformation written out as a formula in `z`, e.g. `(2z + i) / (z - 1)`. */
+    formula: string;
+    /** The determinant `ad - bc` of the scaled coefficients. */
+    determinant: Complex;
+    /** The images of 0, 1 and infinity under the transformation, in that order. */
+    images: [ExtComplex, ExtComplex, ExtComplex];
+}
+
+/**
+ * The errors returned by the backend API (refer to `Error` in `src-tauri/src/api.rs`).
+ * Each contains a human-readable message that can be shown to the user.
+ *
+ * Indices of control points count from 0.
+ */
+export type ApiError = { message: string } & (
+    | { kind: 'DuplicateInputs', indices: [number, number] }
+    | { kind: 'DuplicateOutputs', indices: [number, number] }
+    | { kind: 'UnknownControlPoint', index: number }
+    | { kind: 'InvalidTolerance', name: string }
+    | { kind: 'NoIsometry' }
+    | { kind: 'UnknownCurveFamily', name: string }
+    | { kind: 'DegenerateCurve', family: string, index: number }
+    | { kind: 'ExportFailed', reason: string }
+    | { kind: 'SceneFailed', reason: string }
+    | { kind: 'ShareFailed', reason: string }
+    | { kind: 'InvalidShareCode', reason: string }
+    | { kind: 'UnknownPreset', key: string }
+);
+
+/** The order of the control points, as passed to the backend. */
+const CONTROL_POINT_KEYS: ControlPointKey[] = ['val1', 'val2', 'val3'];
+
+/**
+ * Checks whether an error was caused by one of the input or output control points,
+ * e.g. so that the colliding control points can be highlighted.
+ * @param error The error returned by the backend, if any.
+ * @param key The control point to check.
+ * @param side Whether to check the input or output of the control point.
+ */
+export function isControlPointAtFault(error: ApiError | null, key: ControlPointKey, side: 'in' | 'out'): boolean {
+    const index = CONTROL_POINT_KEYS.indexOf(key);
+    switch (error?.kind) {
+        case 'DuplicateInputs':
+            return side === 'in' && error.indices.includes(index);
+        case 'DuplicateOutputs':
+            return side === 'out' && error.indices.includes(index);
+        default:
+            return false;
+    }
 }
 
 /** The response schema for the backend API `generate_mobius_transformation`. */
 interface GenerateMobiusTransformationResponse {
     curves: CurveSet;
+    fixed_points: [ExtComplex, ExtComplex] | null;
+    transform: MobiusTransformInfo;
 }
 
 /** The request schema for `generateMobiusTransformation`. */
@@ -99,20 +234,48 @@ export interface GenerateMobiusTransformationProps {
     points: MappingSet;
 
     /** The list of curve families to be rendered. These determine what is included in the `curves` property. */
-    usedCurves: CurveFamilyKey[];
+    usedCurves: CurveFamilyRequest[];
+
+    /** If provided, the returned curves are trimmed to the pieces visible inside this region. */
+    viewport?: Viewport;
 }
 
+/**
+ * The outcome of a backend request tagged with a sequence number (refer to `Sequenced` in `src-tauri/src/api.rs`).
+ * `seq` is the sequence number of the request it answers.
+ */
+type Sequenced<T> =
+    | { status: 'completed'; seq: number; result: T }
+    | { status: 'failed'; seq: number; error: ApiError }
+    | { status: 'superseded'; seq: number; latest: number };
+
+/**
+ * The sequence number of the latest call to `generateMobiusTransformation` or `setControlPoint`,
+ * which share a sequence since either gives the latest frame to render.
+ * Starts from the current time rather than 0, since the backend remembers the latest request across page reloads.
+ */
+let latestFrameSeq = Date.now();
+
+/** The sequence number of the latest call to `generateMobiusTransformation` or `setControlPoint` which was answered. */
+let latestAnsweredFrameSeq = 0;
+
 /**
  * Calls the backend to generate the Mobius transformation conforming to the props passed in,
  * and computes the action of the transformation on the provided curves.
  * The promise returned by this function should never reject.
+ *
+ * As with `setControlPoint`, only the latest request is answered, so that rapid requests never render an outdated frame:
+ * the promise resolves to null instead once a later request (or move) has been made, or has already been answered.
  * @param props All the data requested by the backend.
- * @returns The GlobalState needed to render the Mobius transformation.
+ * @returns The GlobalState needed to render the Mobius transformation, or null if it is out of date.
  */
-export async function generateMobiusTransformation({ points, usedCurves }: GenerateMobiusTransformationProps): Promise<GlobalState> {
+export async function generateMobiusTransformation({ points, usedCurves, viewport }: GenerateMobiusTransformationProps): Promise<GlobalState | null> {
+    const seq = ++latestFrameSeq;
+    let response: Sequenced<GenerateMobiusTransformationResponse>;
     try {
         // make call to backend
-        const response = await invoke('generate_mobius_transformation', {
+        response = await invoke('generate_mobius_transformation', {
+            seq,
             inputs: [
                 points.val1.in,
                 points.val2.in,
@@ -124,19 +287,344 @@ export async function generateMobiusTransformation({ points, usedCurves }: Gener
                 points.val3.out,
             ],
             curves: usedCurves,
-        }) as GenerateMobiusTransformationResponse;
-        return {
-            points: points,
-            curves: response.curves,
-            exists: true,
-        };
+            viewport: viewport ?? null,
+        }) as Sequenced<GenerateMobiusTransformationResponse>;
     } catch (err) {
-        // error is thrown when no valid Mobius transformation exists for the inputs.
+        // the request itself failed (e.g. it was malformed), which is treated like any other error
+        response = { status: 'failed', seq, error: err as ApiError };
+    }
+
+    // requests can be answered out of order, so an answer older than one already returned is discarded too
+    if (response.status === 'superseded' || response.seq < latestAnsweredFrameSeq) {
+        return null;
+    }
+    latestAnsweredFrameSeq = response.seq;
+    if (response.status === 'failed') {
+        // error is returned when no valid Mobius transformation exists for the inputs
+        // (or when a curve family is unknown, which should not happen since the families are listed by the backend).
         // in this scenario, set the exists flag to indicate this failure, and do not return any curves to render
         return {
             points: points,
             curves: {},
             exists: false,
+            error: response.error,
+            fixedPoints: null,
+            transform: null,
         };
     }
+    return {
+        points: points,
+        curves: response.result.curves,
+        exists: true,
+        error: null,
+        fixedPoints: response.result.fixed_points,
+        transform: response.result.transform,
+    };
+}
+
+/** The response schema for the session commands (refer to `SessionUpdate` in `src-tauri/src/session.rs`). */
+interface SessionUpdate {
+    /** The curve families whose curves changed. Enabled families which are missing are unchanged. */
+    curves: CurveSet;
+    /** The curve families which were disabled. */
+    removed: CurveFamilyKey[];
+    fixed_points: [ExtComplex, ExtComplex] | null;
+    transform: MobiusTransformInfo;
+}
+
+/**
+ * Calls the backend to start a session with the given control points and curve families,
+ * which can then be updated one change at a time using `setControlPoint` and `toggleFamily`.
+ * The promise returned by this function should never reject.
+ * @param props All the data requested by the backend.
+ * @returns The GlobalState needed to render the Mobius transformation.
+ */
+export async function startSession({ points, usedCurves, viewport }: GenerateMobiusTransformationProps): Promise<GlobalState> {
+    const args = {
+        inputs: [points.val1.in, points.val2.in, points.val3.in],
+        outputs: [points.val1.out, points.val2.out, points.val3.out],
+        curves: usedCurves,
+        viewport: viewport ?? null,
+    };
+    return await updateSession(null, points, 'start_session', args);
+}
+
+/**
+ * Calls the backend to move one side of a control point in the current session.
+ * The promise returned by this function should never reject.
+ *
+ * Only the latest move is answered, so that rapid moves (e.g. while dragging a control point) never render
+ * an outdated frame: the promise of a move resolves to null instead once a later move has been made,
+ * or once a later move has already been answered.
+ * @param state The current GlobalState, which the changes are applied to.
+ * @param key The control point to move.
+ * @param side Whether to move the input or output of the control point.
+ * @param value The new position of the control point.
+ * @returns The GlobalState needed to render the updated Mobius transformation, or null if it is out of date.
+ */
+export async function setControlPoint(state: GlobalState, key: ControlPointKey, side: 'in' | 'out', value: ExtComplex): Promise<GlobalState | null> {
+    const points: MappingSet = {
+        ...state.points,
+        [key]: { ...state.points[key], [side]: value },
+    };
+    const seq = ++latestFrameSeq;
+    const args = { seq, index: CONTROL_POINT_KEYS.indexOf(key), side, value };
+    let response: Sequenced<SessionUpdate>;
+    try {
+        response = await invoke('set_control_point', args) as Sequenced<SessionUpdate>;
+    } catch (err) {
+        // the request itself failed (e.g. it was malformed), which is treated like any other error
+        response = { status: 'failed', seq, error: err as ApiError };
+    }
+
+    // moves can be answered out of order, so an answer older than one already returned is discarded too
+    if (response.status === 'superseded' || response.seq < latestAnsweredFrameSeq) {
+        return null;
+    }
+    latestAnsweredFrameSeq = response.seq;
+    return response.status === 'completed'
+        ? applySessionUpdate(state, points, response.result)
+        : failedSessionState(points, response.error);
+}
+
+/**
+ * Calls the backend to enable or disable a curve family in the current session.
+ * The promise returned by this function should never reject.
+ * @param state The current GlobalState, which the changes are applied to.
+ * @param family The curve family to toggle, along with its parameters if it is being enabled.
+ * @param enabled Whether the curve family should be enabled.
+ * @returns The GlobalState needed to render the updated set of curve families.
+ */
+export async function toggleFamily(state: GlobalState, family: CurveFamilyRequest, enabled: boolean): Promise<GlobalState> {
+    return await updateSession(state, state.points, 'toggle_family', { family, enabled });
+}
+
+/** The response schema for the backend API `history` (refer to `SessionHistory` in `src-tauri/src/session.rs`). */
+export interface SessionHistory {
+    /** Descriptions of the edits which can be undone, with the next one to be undone last. */
+    undo: string[];
+    /** Descriptions of the edits which can be redone, with the next one to be redone last. */
+    redo: string[];
+    inputs: [ExtComplex, ExtComplex, ExtComplex];
+    outputs: [ExtComplex, ExtComplex, ExtComplex];
+    /** The enabled curve families, in the order they were enabled. */
+    families: CurveFamilyKey[];
+}
+
+/**
+ * Calls the backend to describe the edits made in the current session which can be undone and redone,
+ * along with the current control points.
+ */
+export async function getHistory(): Promise<SessionHistory> {
+    return await invoke('history') as SessionHistory;
+}
+
+/**
+ * Calls the backend to undo (or redo) the last edit to the control points or curve families in the current session.
+ * The promise returned by this function should never reject.
+ * @param state The current GlobalState, which the changes are applied to.
+ * @param command Whether to undo or redo the edit.
+ * @returns The GlobalState needed to render the restored Mobius transformation.
+ */
+export async function undoEdit(state: GlobalState, command: 'undo' | 'redo'): Promise<GlobalState> {
+    const newState = await updateSession(state, state.points, command, {});
+    // the restored control points are needed even if the transformation doesn't exist
+    const history = await getHistory();
+    const points: MappingSet = {
+        val1: { in: history.inputs[0], out: history.outputs[0] },
+        val2: { in: history.inputs[1], out: history.outputs[1] },
+        val3: { in: history.inputs[2], out: history.outputs[2] },
+    };
+    return { ...newState, points };
+}
+
+/** A saved scene (refer to `Scene` in `src-tauri/src/scene.rs`). */
+export interface Scene {
+    version: number;
+    points: [SamplePointMapping, SamplePointMapping, SamplePointMapping];
+    families: { name: CurveFamilyKey, params?: Record<string, unknown> }[];
+    viewport: Viewport | null;
+}
+
+/**
+ * Calls the backend to save the current session to a scene file.
+ * @param path The file to write to, which should end in `.mobius.json`.
+ */
+export async function saveScene(path: string): Promise<void> {
+    await invoke('save_scene', { path });
+}
+
+/**
+ * Calls the backend to load a scene file, and starts a new session with it.
+ * Unlike `startSession`, the returned promise rejects with an `ApiError` if the file can't be loaded.
+ * @param path The file to read from.
+ * @returns The GlobalState needed to render the Mobius transformation saved in the scene.
+ */
+export async function loadScene(path: string): Promise<GlobalState> {
+    return await startSceneSession(await invoke('load_scene', { path }) as Scene);
+}
+
+/**
+ * Calls the backend to encode the control points and curve families of the current session as a short share code,
+ * which can be pasted elsewhere and loaded again using `loadShareCode`.
+ * The returned promise rejects with an `ApiError` if the scene is too large to encode,
+ * e.g. because a user-defined curve family has a very long name.
+ */
+export async function shareScene(): Promise<string> {
+    return await invoke('share_scene') as string;
+}
+
+/**
+ * Calls the backend to decode a share code, and starts a new session with the scene it contains.
+ * The returned promise rejects with an `ApiError` if the code is invalid, e.g. because it was not copied in full.
+ * @param code The share code, as returned by `shareScene`.
+ * @returns The GlobalState needed to render the Mobius transformation encoded in the share code.
+ */
+export async function loadShareCode(code: string): Promise<GlobalState> {
+    return await startSceneSession(await invoke('load_share_code', { code }) as Scene);
+}
+
+/** Describes a built-in preset transformation (refer to `Preset` in `src-tauri/src/presets.rs`). */
+export interface Preset {
+    key: string;
+    display_name: string;
+    description: string;
+    inputs: [ExtComplex, ExtComplex, ExtComplex];
+    outputs: [ExtComplex, ExtComplex, ExtComplex];
+    families: CurveFamilyKey[];
+}
+
+/**
+ * Calls the backend to list the built-in presets, e.g. the Cayley transform or a loxodromic spiral.
+ * @returns The presets, in the order they should be listed.
+ */
+export async function listPresets(): Promise<Preset[]> {
+    return await invoke('list_presets') as Preset[];
+}
+
+/**
+ * Calls the backend to look up a preset, and starts a new session with it.
+ * @param key The key of the preset, as returned by `listPresets`.
+ * @returns The GlobalState needed to render the Mobius transformation of the preset.
+ */
+export async function loadPreset(key: string): Promise<GlobalState> {
+    return await startSceneSession(await invoke('load_preset', { key }) as Scene);
+}
+
+/** Starts a new session with the control points and curve families of a scene. */
+async function startSceneSession(scene: Scene): Promise<GlobalState> {
+    return await startSession({
+        points: {
+            val1: scene.points[0],
+            val2: scene.points[1],
+            val3: scene.points[2],
+        },
+        usedCurves: scene.families.map(({ name, params }) => ({ name, ...params })),
+        viewport: scene.viewport ?? undefined,
+    });
+}
+
+/** Invokes one of the session commands, and applies the returned changes to the current GlobalState (if any). */
+async function updateSession(state: GlobalState | null, points: MappingSet, command: string, args: InvokeArgs): Promise<GlobalState> {
+    try {
+        return applySessionUpdate(state, points, await invoke(command, args) as SessionUpdate);
+    } catch (err) {
+        return failedSessionState(points, err as ApiError);
+    }
+}
+
+/** Applies the changes returned by one of the session commands to the current GlobalState (if any). */
+function applySessionUpdate(state: GlobalState | null, points: MappingSet, update: SessionUpdate): GlobalState {
+    // curves are cleared while the transformation doesn't exist, in which case the backend returns every enabled family
+    const curves = { ...(state?.exists ? state.curves : {}), ...update.curves };
+    for (const key of update.removed) {
+        delete curves[key];
+    }
+    return {
+        points,
+        curves,
+        exists: true,
+        error: null,
+        fixedPoints: update.fixed_points,
+        transform: update.transform,
+    };
+}
+
+/** The GlobalState after one of the session commands failed. */
+function failedSessionState(points: MappingSet, error: ApiError): GlobalState {
+    // as in `generateMobiusTransformation`, set the exists flag to indicate failure, and do not return any curves
+    return {
+        points,
+        curves: {},
+        exists: false,
+        error,
+        fixedPoints: null,
+        transform: null,
+    };
+}
+
+/**
+ * Calls the backend to map arbitrary points (e.g. probe points or the cursor) under the Mobius transformation
+ * conforming to the given control points. Unlike `generateMobiusTransformation`, the returned promise rejects
+ * with an `ApiError` if the transformation does not exist.
+ * @param points The set of sample mapping points based on which the Mobius transformation is determined.
+ * @param toMap The points to map.
+ * @param inverse Whether to map the points under the inverse transformation, i.e. to find their preimages.
+ * @returns The images (or preimages) of the points, in the same order.
+ */
+export async function mapPoints(points: MappingSet, toMap: ExtComplex[], inverse = false): Promise<ExtComplex[]> {
+    return await invoke('map_points', {
+        inputs: [
+            points.val1.in,
+            points.val2.in,
+            points.val3.in,
+        ],
+        outputs: [
+            points.val1.out,
+            points.val2.out,
+            points.val3.out,
+        ],
+        points: toMap,
+        inverse,
+    }) as ExtComplex[];
+}
+
+/** The models of the hyperbolic plane which the backend can restrict transformations to. */
+export type HyperbolicModel = 'disk' | 'half_plane';
+
+/** The response schema for the backend API `generate_hyperbolic_transformation`. */
+export interface GenerateHyperbolicTransformationResponse {
+    curves: CurveSet;
+    /** The images of the input points under the nearest hyperbolic isometry, in the same order as the inputs. */
+    outputs: [ExtComplex, ExtComplex, ExtComplex];
+    /** Hyperbolic distances between the pairs of inputs (1, 2), (2, 3) and (3, 1), or null if a point lies outside the model. */
+    distances: [number | null, number | null, number | null];
+}
+
+/**
+ * Calls the backend to restrict the Mobius transformation conforming to the props passed in
+ * to the nearest isometry of the given hyperbolic model.
+ * Unlike `generateMobiusTransformation`, the returned promise rejects if no such isometry exists.
+ * @param model The model of the hyperbolic plane to use.
+ * @param props All the data requested by the backend.
+ */
+export async function generateHyperbolicTransformation(
+    model: HyperbolicModel,
+    { points, usedCurves, viewport }: GenerateMobiusTransformationProps,
+): Promise<GenerateHyperbolicTransformationResponse> {
+    return await invoke('generate_hyperbolic_transformation', {
+        model,
+        inputs: [
+            points.val1.in,
+            points.val2.in,
+            points.val3.in,
+        ],
+        outputs: [
+            points.val1.out,
+            points.val2.out,
+            points.val3.out,
+        ],
+        curves: usedCurves,
+        viewport: viewport ?? null,
+    }) as GenerateHyperbolicTransformationResponse;
 }
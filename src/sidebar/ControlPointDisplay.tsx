@@ -1,21 +1,29 @@
-import { ExtComplex, SamplePointMapping } from "../model/backend";
+import { ApiError, ControlPointKey, ExtComplex, SamplePointMapping, isControlPointAtFault } from "../model/backend";
 import styles from "./sidebar.module.css";
 
 const DIGITS_OF_PRECISION = 3;
 
 interface DisplayProps {
+    readonly pointKey: ControlPointKey;
     readonly mapping: SamplePointMapping;
+    /** The error preventing the transformation from being computed, used to highlight the points causing it. */
+    readonly error: ApiError | null;
 }
 
-export function ControlPointDisplay({ mapping }: DisplayProps) {
+export function ControlPointDisplay({ pointKey, mapping, error }: DisplayProps) {
+    const inputClass = isControlPointAtFault(error, pointKey, 'in') ? styles.pointAtFault : undefined;
+    const outputClass = isControlPointAtFault(error, pointKey, 'out') ? styles.pointAtFault : undefined;
     return (
         <div className={styles.pointDisplay}>
-            {`${display(mapping.in)} ↦ ${display(mapping.out)}`}
+            <span className={inputClass}>{displayExtComplex(mapping.in)}</span>
+            {' ↦ '}
+            <span className={outputClass}>{displayExtComplex(mapping.out)}</span>
         </div>
     );
 }
 
-function display(c: ExtComplex): string {
+/** Formats a point of the extended complex plane for displaying in the sidebar. */
+export function displayExtComplex(c: ExtComplex): string {
     if (c === 'inf') {
         return 'Infinity';
     } else {
@@ -1,27 +1,28 @@
 import { CheckBox } from "../components/CheckBox";
 import { GlobalStateDispatch } from "../hooks/useGlobalState";
-import { CURVE_FAMILY_NAMES, CurveFamilyKey, GlobalState } from "../model/backend";
+import { CurveFamilyInfo, GlobalState } from "../model/backend";
 import styles from "./sidebar.module.css";
 
 interface CurveFamilyToggleProps {
+    readonly families: CurveFamilyInfo[];
     readonly globalState: GlobalState;
     readonly dispatch: GlobalStateDispatch;
 }
 
 /** Creates list of curve families that can be toggled on and off in the GraphCanvas. */
-export function CurveFamilyToggle({ globalState, dispatch }: CurveFamilyToggleProps) {
-    const listItems = Object.entries(CURVE_FAMILY_NAMES).map(([key, name]) => {
-        const checked = globalState.curves[key as CurveFamilyKey] !== undefined;
+export function CurveFamilyToggle({ families, globalState, dispatch }: CurveFamilyToggleProps) {
+    const listItems = families.map(family => {
+        const checked = globalState.curves[family.key] !== undefined;
         const action = (value: boolean) => dispatch({
             type: 'toggle-curves',
-            key: key as CurveFamilyKey,
+            key: family.key,
             value: value
         })
         return (
-            <li key={key}>
-                <label className={styles.curveToggle}>
+            <li key={family.key}>
+                <label className={styles.curveToggle} title={family.description}>
                     <CheckBox checked={checked} onChange={action} />
-                    {name}
+                    {family.display_name}
                 </label>
             </li>
         );
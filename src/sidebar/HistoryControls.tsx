@@ -0,0 +1,38 @@
+import { useEffect } from "react";
+import { GlobalStateDispatch } from "../hooks/useGlobalState";
+import styles from "./sidebar.module.css";
+
+interface HistoryControlsProps {
+    readonly dispatch: GlobalStateDispatch;
+}
+
+/**
+ * Buttons to undo and redo edits to the control points and curve families,
+ * which can also be triggered with the usual keyboard shortcuts (Ctrl+Z, and Ctrl+Shift+Z or Ctrl+Y).
+ */
+export function HistoryControls({ dispatch }: HistoryControlsProps) {
+    useEffect(() => {
+        function onKeyDown(e: KeyboardEvent) {
+            if (!(e.ctrlKey || e.metaKey)) {
+                return;
+            }
+            const key = e.key.toLowerCase();
+            if (key === 'z') {
+                e.preventDefault();
+                dispatch({ type: e.shiftKey ? 'redo' : 'undo' });
+            } else if (key === 'y') {
+                e.preventDefault();
+                dispatch({ type: 'redo' });
+            }
+        }
+        window.addEventListener('keydown', onKeyDown);
+        return () => window.removeEventListener('keydown', onKeyDown);
+    }, [dispatch]);
+
+    return (
+        <div className={styles.historyControls}>
+            <button type="button" onClick={() => dispatch({ type: 'undo' })}>Undo</button>
+            <button type="button" onClick={() => dispatch({ type: 'redo' })}>Redo</button>
+        </div>
+    );
+}
@@ -1,28 +1,38 @@
 import { GlobalStateDispatch } from "../hooks/useGlobalState";
-import { GlobalState } from "../model/backend";
+import { ApiError, CurveFamilyInfo, GlobalState } from "../model/backend";
 import { ControlPointDisplay } from "./ControlPointDisplay";
 import { CurveFamilyToggle } from "./CurveFamilyToggle";
 import { AlertDisplay } from "../components/AlertDisplay";
+import { HistoryControls } from "./HistoryControls";
 import { ThemeToggle } from "./ThemeToggle";
+import { TransformDisplay } from "./TransformDisplay";
 import styles from "./sidebar.module.css";
 
 interface SidebarProps {
+    readonly families: CurveFamilyInfo[];
     readonly globalState: GlobalState;
     readonly dispatch: GlobalStateDispatch;
 }
 
 /** The sidebar contains most of the non-GraphCanvas UI controls. */
-export function Sidebar({ globalState, dispatch }: SidebarProps) {
+export function Sidebar({ families, globalState, dispatch }: SidebarProps) {
     return (
         <div className={styles.sidebar}>
             <div className={styles.sidebarContent}>
                 <h2>Curve Families</h2>
-                <CurveFamilyToggle globalState={globalState} dispatch={dispatch} />
+                <CurveFamilyToggle families={families} globalState={globalState} dispatch={dispatch} />
                 <h2>Control Point Mappings</h2>
-                <ControlPointDisplay mapping={globalState.points.val1} />
-                <ControlPointDisplay mapping={globalState.points.val2} />
-                <ControlPointDisplay mapping={globalState.points.val3} />
-                {(globalState.exists) ? null : <ErrorDisplay />}
+                <HistoryControls dispatch={dispatch} />
+                <ControlPointDisplay pointKey="val1" mapping={globalState.points.val1} error={globalState.error} />
+                <ControlPointDisplay pointKey="val2" mapping={globalState.points.val2} error={globalState.error} />
+                <ControlPointDisplay pointKey="val3" mapping={globalState.points.val3} error={globalState.error} />
+                {(globalState.exists) ? null : <ErrorDisplay error={globalState.error} />}
+                {(globalState.transform === null) ? null : (
+                    <>
+                        <h2>Transformation</h2>
+                        <TransformDisplay transform={globalState.transform} />
+                    </>
+                )}
             </div>
             <footer>
                 <ThemeToggle />
@@ -32,14 +42,22 @@ export function Sidebar({ globalState, dispatch }: SidebarProps) {
 }
 
 /** Custom error message when Mobius transformation does not exist. */
-function ErrorDisplay() {
+function ErrorDisplay({ error }: { readonly error: ApiError | null }) {
+    if (error?.kind === 'DuplicateInputs' || error?.kind === 'DuplicateOutputs') {
+        return (
+            <AlertDisplay>
+                <strong>Warning. </strong>
+                Transformation matrix is nearly singular.
+                Mobius transformations are one-to-one mappings,
+                and do not exist/behave well when two inputs map to the same (or nearly the same) output.
+                Move the highlighted control points farther away from each other to correct this.
+            </AlertDisplay>
+        );
+    }
     return (
         <AlertDisplay>
             <strong>Warning. </strong>
-            Transformation matrix is nearly singular.
-            Mobius transformations are one-to-one mappings,
-            and do not exist/behave well when two inputs map to the same (or nearly the same) output.
-            Move some of the control points farther away from each other to correct this.
+            {error?.message ?? 'The transformation could not be computed.'}
         </AlertDisplay>
     );
 }
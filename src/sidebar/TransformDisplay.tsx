@@ -0,0 +1,21 @@
+import { MobiusTransformInfo } from "../model/backend";
+import { displayExtComplex } from "./ControlPointDisplay";
+import styles from "./sidebar.module.css";
+
+interface DisplayProps {
+    readonly transform: MobiusTransformInfo;
+}
+
+/** Shows the formula of the Mobius transformation built from the control points, along with some of its properties. */
+export function TransformDisplay({ transform }: DisplayProps) {
+    const [zero, one, inf] = transform.images;
+    return (
+        <div className={styles.transformDisplay}>
+            <p className={styles.formula}>f(z) = {transform.formula}</p>
+            <p>ad − bc = {displayExtComplex(transform.determinant)}</p>
+            <p>f(0) = {displayExtComplex(zero)}</p>
+            <p>f(1) = {displayExtComplex(one)}</p>
+            <p>f(∞) = {displayExtComplex(inf)}</p>
+        </div>
+    );
+}